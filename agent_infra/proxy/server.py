@@ -227,6 +227,7 @@ class LoadBalancingProxy:
             "strategy": self.strategy,
             "models": model_names,
             "pools": [pool.get_stats() for pool in self.pools.values()],
+            "server_time": time.time(),
         }
         return web.json_response(data)
 
@@ -0,0 +1,52 @@
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Where copied text ended up.
+pub enum CopyTarget {
+    Clipboard(&'static str),
+    File(PathBuf),
+}
+
+/// Clipboard helpers tried in order; the first that accepts stdin wins.
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// Copy `text` to the system clipboard, or write it to a temp file named
+/// after `label` when no clipboard tool is available.
+pub fn copy_text(text: &str, label: &str) -> io::Result<CopyTarget> {
+    for (cmd, args) in CLIPBOARD_COMMANDS {
+        if pipe_to(cmd, args, text).is_ok() {
+            return Ok(CopyTarget::Clipboard(cmd));
+        }
+    }
+
+    let name = format!("dashboard-{}-{}.txt", label, crate::ui::timefmt::format_now("%Y%m%d-%H%M%S"));
+    let path = env::temp_dir().join(name);
+    fs::write(&path, text)?;
+    Ok(CopyTarget::File(path))
+}
+
+fn pipe_to(cmd: &str, args: &[&str], text: &str) -> io::Result<()> {
+    let mut child = Command::new(cmd)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(stdin) = child.stdin.as_mut() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("{} exited with {}", cmd, status)))
+    }
+}
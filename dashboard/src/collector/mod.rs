@@ -1,2 +1,5 @@
 pub mod proxy;
+pub mod replay;
+pub mod shared;
 pub mod throughput;
+pub mod window;
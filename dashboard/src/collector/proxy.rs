@@ -1,53 +1,1046 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crate::model::{ProxySnapshot, QueueResponse, StatsResponse};
+use serde::de::DeserializeOwned;
+
+use super::replay::{Recorder, Replay};
+use super::shared::{self, SharedPoll, SharedWriter};
+use super::window::SampleWindow;
+use crate::model::{Connection, FleetChange, HealthResponse, HealthTransition, ProxySnapshot, QueueResponse, StatsResponse, UnroutedRequests};
+
+/// Where the collector reads proxy state from.
+pub enum ProxySource {
+    /// A live proxy at this base URL.
+    Http(String),
+    /// `stats.json` and `queue_status.json` (plus an optional `health.json`)
+    /// in a directory, re-read on every poll so edits show up live.
+    Fixtures(PathBuf),
+    /// A file another dashboard publishes each poll to (`--share-snapshot`).
+    Shared(PathBuf),
+    /// A `--record` file played back at its recorded pace (`--replay`).
+    Replay(Replay),
+}
+
+impl ProxySource {
+    /// Fetch `/stats` and `/queue/status`, and `/health` if both failed.
+    fn poll(&self, client: &reqwest::blocking::Client) -> Poll {
+        match self {
+            ProxySource::Http(url) => {
+                let stats = fetch_json(client, &format!("{}/stats", url));
+                // Before the queue fetch, which can follow many pages
+                let stats_epoch = wall_clock();
+                Poll::new(
+                    stats,
+                    stats_epoch,
+                    fetch_queue(client, &format!("{}/queue/status", url)),
+                    || read_json(client.get(format!("{}/health", url)).send()?.error_for_status()?),
+                )
+            }
+            ProxySource::Fixtures(dir) => {
+                let stats = read_fixture(dir, "stats.json");
+                Poll::new(stats, wall_clock(), read_fixture(dir, "queue_status.json"), || {
+                    read_fixture(dir, "health.json")
+                })
+            }
+            ProxySource::Shared(path) => read_shared(path),
+            ProxySource::Replay(replay) => {
+                let frame = replay.frame().clone();
+                Poll { recorded_at: Some(frame.written_at), ..Poll::published(frame) }
+            }
+        }
+    }
+}
+
+/// What one poll of the source returned.
+struct Poll {
+    stats: FetchResult<StatsResponse>,
+    /// Local wall clock (epoch seconds) when `/stats` arrived, to compare
+    /// against its `server_time`.
+    stats_epoch: f64,
+    queue: FetchResult<QueueResponse>,
+    /// `/health`, asked for only when both richer endpoints failed.
+    limited: Option<HealthResponse>,
+    /// When a replayed poll was recorded, standing in for the local clock.
+    recorded_at: Option<f64>,
+}
+
+impl Poll {
+    fn new(
+        stats: FetchResult<StatsResponse>,
+        stats_epoch: f64,
+        queue: FetchResult<QueueResponse>,
+        health: impl FnOnce() -> FetchResult<HealthResponse>,
+    ) -> Self {
+        // Older proxies may only expose a lightweight health check
+        let limited = if stats.is_err() && queue.is_err() { health().ok() } else { None };
+        Poll { stats, stats_epoch, queue, limited, recorded_at: None }
+    }
+
+    /// A poll another dashboard published or recorded.
+    fn published(poll: SharedPoll) -> Self {
+        Poll {
+            stats: poll.stats.ok_or_else(|| "publisher's /stats poll failed".into()),
+            // The publisher's clock at publish time stands in for when /stats arrived
+            stats_epoch: poll.written_at,
+            queue: poll.queue.ok_or_else(|| "publisher's /queue/status poll failed".into()),
+            limited: poll.health,
+            recorded_at: None,
+        }
+    }
+}
+
+/// Local wall clock as epoch seconds, comparable with proxy timestamps.
+fn wall_clock() -> f64 {
+    chrono::Utc::now().timestamp_millis() as f64 / 1000.0
+}
+
+/// Credentials for a proxy behind an authenticating gateway or a private CA.
+#[derive(Clone, Debug, Default)]
+pub struct ProxyAuth {
+    /// Sent as `Authorization: Bearer <token>`.
+    pub token: Option<String>,
+    /// PEM file added to the trusted roots.
+    pub ca_cert: Option<PathBuf>,
+}
+
+impl ProxyAuth {
+    /// An HTTP client carrying these credentials. Fails on an unreadable or
+    /// invalid CA file, so a bad path surfaces at startup.
+    pub fn client(&self, timeout: Duration) -> Result<reqwest::blocking::Client, String> {
+        let mut builder = reqwest::blocking::Client::builder().timeout(timeout);
+        if let Some(token) = &self.token {
+            let mut value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|_| "proxy token contains invalid header characters".to_string())?;
+            value.set_sensitive(true);
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert(reqwest::header::AUTHORIZATION, value);
+            builder = builder.default_headers(headers);
+        }
+        if let Some(path) = &self.ca_cert {
+            let pem = fs::read(path).map_err(|e| format!("cannot read CA certificate {}: {}", path.display(), e))?;
+            let cert = reqwest::Certificate::from_pem(&pem)
+                .map_err(|e| format!("invalid CA certificate {}: {}", path.display(), e))?;
+            builder = builder.add_root_certificate(cert);
+        }
+        builder.build().map_err(|e| format!("failed to build HTTP client: {}", e))
+    }
+}
+
+/// Shortest time between two polls, however they are triggered. Keeps
+/// `--interval 0` (or any future refresh trigger) from hammering the proxy;
+/// only tests lower it, via the hidden `--min-poll-interval-ms`.
+pub const MIN_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Enforces the minimum time between polls. A poll requested early waits
+/// out the rest of the floor, so bursts of triggers coalesce into one poll
+/// per floor.
+struct PollGuard {
+    floor: Duration,
+    last: Option<Instant>,
+}
+
+impl PollGuard {
+    fn new(floor: Duration) -> Self {
+        Self { floor, last: None }
+    }
+
+    /// How long a poll at `now` must still wait.
+    fn delay(&self, now: Instant) -> Duration {
+        self.last.map_or(Duration::ZERO, |last| (last + self.floor).saturating_duration_since(now))
+    }
+
+    /// Block until a poll is allowed, then record it.
+    fn wait(&mut self) {
+        let delay = self.delay(Instant::now());
+        if !delay.is_zero() {
+            thread::sleep(delay);
+        }
+        self.last = Some(Instant::now());
+    }
+}
+
+/// How often the collector polls, from `--interval`, `--adaptive`,
+/// `--max-interval`, `--metric-window-secs` and `--min-poll-interval-ms`.
+#[derive(Clone, Copy, Debug)]
+pub struct PollConfig {
+    /// Seconds slept between polls.
+    pub interval_secs: u64,
+    /// Back off towards `max_interval_secs` while nothing changes.
+    pub adaptive: bool,
+    pub max_interval_secs: u64,
+    /// Window for recent latency and turns/min; 0 turns recent latency off.
+    pub metric_window_secs: u64,
+    /// Floor between polls, normally `MIN_POLL_INTERVAL`.
+    pub min_poll: Duration,
+}
+
+/// Where each poll is written besides the snapshot.
+#[derive(Default)]
+pub struct PollSinks {
+    /// The file viewers read (`--share-snapshot`).
+    pub share: Option<SharedWriter>,
+    /// The recording for `--replay` (`--record`).
+    pub record: Option<Recorder>,
+}
+
+impl PollSinks {
+    /// Write a poll; `interval` is the wait until the next one.
+    fn write(&mut self, interval: Duration, poll: impl FnOnce() -> SharedPoll) {
+        if self.share.is_none() && self.record.is_none() {
+            return;
+        }
+        let poll = poll();
+        // Viewers see a failed write as a stale snapshot
+        if let Some(writer) = &mut self.share {
+            let _ = writer.publish(&poll, interval);
+        }
+        // A failed recording write plays back as a longer gap
+        if let Some(recorder) = &mut self.record {
+            let _ = recorder.record(&poll);
+        }
+    }
+}
 
 pub fn spawn_proxy_collector(
-    proxy_url: String,
-    interval_secs: u64,
+    mut source: ProxySource,
+    config: PollConfig,
+    client: reqwest::blocking::Client,
+    mut sinks: PollSinks,
     snapshot: Arc<Mutex<ProxySnapshot>>,
 ) -> thread::JoinHandle<()> {
     thread::spawn(move || {
-        let client = reqwest::blocking::Client::builder()
-            .timeout(Duration::from_secs(2))
-            .build()
-            .expect("failed to build HTTP client");
+        let PollConfig { interval_secs, adaptive, max_interval_secs, metric_window_secs, min_poll } = config;
+        let mut sleep_secs = interval_secs;
+        let mut prev_health: Option<HashMap<String, bool>> = None;
+        let mut prev_fleet: Option<Fleet> = None;
+        let mut prev_fingerprint: Option<IdleFingerprint> = None;
+        let metric_window = Duration::from_secs(metric_window_secs);
+        let mut latency_samples: SampleWindow<HashMap<String, LatencySample>> = SampleWindow::new(metric_window);
+        let mut odometer = TurnOdometer::new(metric_window);
+        let mut unrouted = UnroutedTracker::default();
+        let mut clock_skew: Option<f64> = None;
+        let mut last_update: Option<Instant> = None;
+        let mut guard = PollGuard::new(min_poll);
+        // A replay is paced by its recording and controls, not the interval
+        let replaying = matches!(source, ProxySource::Replay(_));
 
         loop {
-            let stats_url = format!("{}/stats", proxy_url);
-            let queue_url = format!("{}/queue/status", proxy_url);
-
-            let stats_result = client.get(&stats_url).send().and_then(|r| r.json::<StatsResponse>());
-            let queue_result = client.get(&queue_url).send().and_then(|r| r.json::<QueueResponse>());
+            if !replaying {
+                guard.wait();
+            }
+            let Poll { stats: stats_result, stats_epoch, queue: queue_result, limited, recorded_at } =
+                source.poll(&client);
 
+            let queue_fresh = queue_result.is_ok();
+            let local_epoch = recorded_at.unwrap_or_else(wall_clock);
+            // The interval about to be slept; adaptive backoff at most doubles it
+            let interval = Duration::from_secs(sleep_secs).max(min_poll);
+            sinks.write(interval, || SharedPoll {
+                written_at: local_epoch,
+                stats: stats_result.as_ref().ok().cloned(),
+                queue: queue_result.as_ref().ok().cloned(),
+                health: limited.clone(),
+            });
+            if let Ok(stats) = &stats_result {
+                clock_skew = stats.server_time.map(|t| stats_epoch - t);
+            }
             let mut snap = snapshot.lock().unwrap();
+            snap.limited = limited;
 
             match (stats_result, queue_result) {
                 (Ok(stats), Ok(queue)) => {
-                    snap.connected = true;
+                    snap.connected = Connection::Up;
                     snap.stats = stats;
                     snap.queue = queue;
                 }
                 (Ok(stats), Err(_)) => {
-                    snap.connected = true;
+                    snap.connected = Connection::Up;
                     snap.stats = stats;
                     // keep previous queue data
                 }
                 (Err(_), Ok(queue)) => {
-                    snap.connected = true;
+                    snap.connected = Connection::Up;
                     snap.queue = queue;
                     // keep previous stats data
                 }
                 (Err(_), Err(_)) => {
-                    snap.connected = false;
+                    // A completed poll that failed, so no longer just starting up
+                    snap.connected = Connection::Down;
                     // keep all previous data
                 }
             }
 
+            let health = backend_health(&snap.stats);
+            snap.health_transitions = prev_health
+                .as_ref()
+                .map(|prev| diff_health(prev, &health))
+                .unwrap_or_default();
+            let fleet = pool_fleet(&snap.stats);
+            snap.fleet_changes = prev_fleet
+                .as_ref()
+                .map(|prev| diff_fleet(prev, &fleet, local_epoch))
+                .unwrap_or_default();
+            if snap.connected.is_up() {
+                prev_health = Some(health);
+                prev_fleet = Some(fleet);
+            }
+
+            let now = Instant::now();
+            if queue_fresh {
+                odometer.update(&snap.queue, now);
+                snap.turns_completed = odometer.total;
+                snap.turns_per_min = odometer.rate_per_min();
+                snap.unrouted = unrouted.update(&snap.queue, now);
+            }
+            if snap.connected.is_up() && metric_window_secs > 0 {
+                latency_samples.push(now, latency_sample(&snap.stats));
+                if let Some((base, latest)) = latency_samples.ends() {
+                    snap.recent_latency_ms = windowed_latency(&base.1, &latest.1);
+                }
+            }
+
+            if snap.connected.is_up() {
+                // Replay pacing is chosen by the user, so it never lags
+                snap.update_cadence = last_update.filter(|_| !replaying).map(|t| now.duration_since(t));
+                snap.polls_succeeded += 1;
+                last_update = Some(now);
+            }
+            // The interval slept before this poll
+            snap.target_interval = Duration::from_secs(sleep_secs).max(min_poll);
+            snap.clock_skew_secs = clock_skew;
+            snap.fetched_at = Some(now);
+            let fingerprint = IdleFingerprint::from_snapshot(&snap);
             drop(snap);
-            thread::sleep(Duration::from_secs(interval_secs));
+
+            if adaptive {
+                sleep_secs = next_interval(
+                    sleep_secs,
+                    interval_secs,
+                    max_interval_secs,
+                    prev_fingerprint.as_ref(),
+                    fingerprint.as_ref(),
+                );
+                prev_fingerprint = fingerprint;
+            }
+
+            match &mut source {
+                ProxySource::Replay(replay) => replay.wait(),
+                _ => thread::sleep(Duration::from_secs(sleep_secs)),
+            }
         }
     })
 }
+
+/// How long a departed session is remembered by `TurnOdometer`.
+const DEPARTED_TTL: Duration = Duration::from_secs(3600);
+
+/// Most departed sessions `TurnOdometer` remembers; the oldest go first.
+const MAX_DEPARTED: usize = 10_000;
+
+/// Running count of turns completed since launch, built from per-session
+/// deltas between polls. Turns done before the first poll aren't counted,
+/// and sessions the proxy drops keep the turns already credited to them.
+struct TurnOdometer {
+    /// Highest `turns_done` seen per live session.
+    seen: HashMap<String, u64>,
+    /// Sessions gone from the response, with their count and when they left,
+    /// so one that reappears isn't credited its turns again.
+    departed: HashMap<String, (u64, Instant)>,
+    total: u64,
+    primed: bool,
+    samples: SampleWindow<u64>,
+}
+
+impl TurnOdometer {
+    fn new(rate_window: Duration) -> Self {
+        Self {
+            seen: HashMap::new(),
+            departed: HashMap::new(),
+            total: 0,
+            primed: false,
+            samples: SampleWindow::new(rate_window),
+        }
+    }
+
+    fn update(&mut self, queue: &QueueResponse, now: Instant) {
+        let mut current: HashMap<String, u64> = HashMap::new();
+        for sess in queue.all_sessions() {
+            let done = current.entry(sess.session_id.clone()).or_default();
+            *done = (*done).max(sess.turns_done());
+        }
+        for (id, done) in current.iter_mut() {
+            let prev = match self.seen.get(id) {
+                Some(prev) => *prev,
+                None => self.departed.remove(id).map_or(0, |(prev, _)| prev),
+            };
+            if self.primed {
+                self.total += done.saturating_sub(prev);
+            }
+            // A count that dips and recovers must not be credited twice
+            *done = (*done).max(prev);
+        }
+        for (id, done) in std::mem::replace(&mut self.seen, current) {
+            if !self.seen.contains_key(&id) {
+                self.departed.insert(id, (done, now));
+            }
+        }
+        self.departed.retain(|_, (_, left)| now.duration_since(*left) < DEPARTED_TTL);
+        if self.departed.len() > MAX_DEPARTED {
+            let mut by_age: Vec<(String, Instant)> =
+                self.departed.iter().map(|(id, (_, left))| (id.clone(), *left)).collect();
+            by_age.sort_by_key(|(_, left)| *left);
+            for (id, _) in by_age.into_iter().take(self.departed.len() - MAX_DEPARTED) {
+                self.departed.remove(&id);
+            }
+        }
+        self.primed = true;
+
+        self.samples.push(now, self.total);
+    }
+
+    fn rate_per_min(&self) -> Option<f64> {
+        let (first, last) = self.samples.ends()?;
+        let secs = last.0.duration_since(first.0).as_secs_f64();
+        (secs > 0.0).then(|| (last.1 - first.1) as f64 / secs * 60.0)
+    }
+}
+
+/// When each in-flight request without a backend was first seen, so a
+/// routing stall is timed across polls rather than from one snapshot.
+#[derive(Default)]
+struct UnroutedTracker {
+    first_seen: HashMap<String, Instant>,
+}
+
+impl UnroutedTracker {
+    fn update(&mut self, queue: &QueueResponse, now: Instant) -> UnroutedRequests {
+        let ids: HashSet<&str> = queue
+            .in_flight
+            .iter()
+            .filter(|r| r.backend.as_deref().is_none_or(str::is_empty))
+            .map(|r| r.request_id.as_str())
+            .collect();
+        self.first_seen.retain(|id, _| ids.contains(id.as_str()));
+        for id in &ids {
+            self.first_seen.entry(id.to_string()).or_insert(now);
+        }
+        UnroutedRequests {
+            count: ids.len(),
+            oldest: self
+                .first_seen
+                .values()
+                .map(|seen| now.duration_since(*seen))
+                .max()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Most `/queue/status` pages followed in one poll.
+const MAX_QUEUE_PAGES: usize = 50;
+
+/// Fetch `/queue/status`, following `next_cursor` (up to `MAX_QUEUE_PAGES`)
+/// so the snapshot only ever holds a complete set of pages.
+fn fetch_queue(client: &reqwest::blocking::Client, queue_url: &str) -> FetchResult<QueueResponse> {
+    let mut queue = read_json::<QueueResponse>(client.get(queue_url).send()?)?;
+    for _ in 1..MAX_QUEUE_PAGES {
+        let Some(cursor) = queue.next_cursor.take() else {
+            break;
+        };
+        let page = read_json::<QueueResponse>(client.get(queue_url).query(&[("cursor", cursor.as_str())]).send()?)?;
+        queue.merge_page(page);
+    }
+    // A partial queue would undercount sessions without saying so
+    if queue.next_cursor.is_some() {
+        return Err(format!("queue has more than {} pages", MAX_QUEUE_PAGES).into());
+    }
+    Ok(queue)
+}
+
+type FetchResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+fn fetch_json<T: DeserializeOwned>(client: &reqwest::blocking::Client, url: &str) -> FetchResult<T> {
+    read_json(client.get(url).send()?)
+}
+
+/// Decode a JSON response body, tolerating the non-finite float tokens
+/// Python's `json` module emits (see `null_non_finite`).
+fn read_json<T: DeserializeOwned>(resp: reqwest::blocking::Response) -> FetchResult<T> {
+    parse_json(&resp.text()?)
+}
+
+/// Decode `name` in a fixture directory through the same path as live responses.
+fn read_fixture<T: DeserializeOwned>(dir: &Path, name: &str) -> FetchResult<T> {
+    parse_json(&fs::read_to_string(dir.join(name))?)
+}
+
+/// The poll another dashboard published, read once so every part comes
+/// from the same publish. Fails like an unreachable proxy if the file can't
+/// be read or the publisher has gone quiet.
+fn read_shared(path: &Path) -> Poll {
+    let published = shared::read(path).map_err(|e| e.to_string()).and_then(|published| {
+        let age = wall_clock() - published.poll.written_at;
+        if age > shared::stale_after(published.interval).as_secs_f64() {
+            return Err(format!("shared snapshot is {:.0}s old", age));
+        }
+        Ok(published.poll)
+    });
+    match published {
+        Ok(poll) => Poll::published(poll),
+        Err(e) => Poll {
+            stats: Err(e.clone().into()),
+            stats_epoch: wall_clock(),
+            queue: Err(e.into()),
+            limited: None,
+            recorded_at: None,
+        },
+    }
+}
+
+fn parse_json<T: DeserializeOwned>(body: &str) -> FetchResult<T> {
+    Ok(serde_json::from_str(&null_non_finite(body))?)
+}
+
+/// Replace bare `NaN`, `Infinity` and `-Infinity` tokens (invalid JSON, but
+/// produced by Python proxies when a clock goes backwards) with `null`, so
+/// one bad timing value doesn't fail the whole response. Text inside
+/// strings is left alone.
+fn null_non_finite(body: &str) -> Cow<'_, str> {
+    if !body.contains("NaN") && !body.contains("Infinity") {
+        return Cow::Borrowed(body);
+    }
+    let mut out = String::with_capacity(body.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut rest = body;
+    while let Some(ch) = rest.chars().next() {
+        if in_string {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if ch == '"' {
+            in_string = true;
+        } else if let Some(token) = ["NaN", "Infinity", "-Infinity"].into_iter().find(|t| rest.starts_with(t)) {
+            out.push_str("null");
+            rest = &rest[token.len()..];
+            continue;
+        }
+        out.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+    Cow::Owned(out)
+}
+
+fn backend_health(stats: &StatsResponse) -> HashMap<String, bool> {
+    stats
+        .pools
+        .iter()
+        .flat_map(|p| p.backends.iter())
+        .map(|b| (b.url.clone(), b.healthy))
+        .collect()
+}
+
+/// Cumulative (requests, avg_latency_ms) for one backend at one poll.
+type LatencySample = (u64, f64);
+
+fn latency_sample(stats: &StatsResponse) -> HashMap<String, LatencySample> {
+    stats
+        .pools
+        .iter()
+        .flat_map(|p| p.backends.iter())
+        .map(|b| (b.url.clone(), (b.requests, b.avg_latency_ms)))
+        .collect()
+}
+
+/// Mean latency of the requests each backend served between two cumulative
+/// samples. Backends with no new requests (or a counter reset) are omitted.
+fn windowed_latency(
+    base: &HashMap<String, LatencySample>,
+    latest: &HashMap<String, LatencySample>,
+) -> HashMap<String, f64> {
+    latest
+        .iter()
+        .filter_map(|(url, &(n1, avg1))| {
+            let (n0, avg0) = base.get(url).copied().unwrap_or((0, 0.0));
+            if n1 <= n0 {
+                return None;
+            }
+            let total = n1 as f64 * avg1 - n0 as f64 * avg0;
+            Some((url.clone(), (total / (n1 - n0) as f64).max(0.0)))
+        })
+        .collect()
+}
+
+/// Backends whose health flipped since the previous poll. Newly appearing
+/// backends only count if they arrive unhealthy.
+fn diff_health(prev: &HashMap<String, bool>, next: &HashMap<String, bool>) -> Vec<HealthTransition> {
+    let mut transitions: Vec<HealthTransition> = next
+        .iter()
+        .filter(|(url, healthy)| match prev.get(*url) {
+            Some(was) => was != *healthy,
+            None => !**healthy,
+        })
+        .map(|(url, healthy)| HealthTransition { url: url.clone(), healthy: *healthy })
+        .collect();
+    transitions.sort_by(|a, b| a.url.cmp(&b.url));
+    transitions
+}
+
+/// Backend URLs registered under each model.
+type Fleet = BTreeMap<String, BTreeSet<String>>;
+
+fn pool_fleet(stats: &StatsResponse) -> Fleet {
+    stats
+        .pools
+        .iter()
+        .map(|p| (p.model.clone(), p.backends.iter().map(|b| b.url.clone()).collect()))
+        .collect()
+}
+
+/// Backends that appeared in or disappeared from a pool, removals first.
+/// A whole pool appearing or vanishing counts as each of its backends.
+fn diff_fleet(prev: &Fleet, next: &Fleet, at: f64) -> Vec<FleetChange> {
+    let empty = BTreeSet::new();
+    let mut changes = Vec::new();
+    for added in [false, true] {
+        let (from, to) = if added { (prev, next) } else { (next, prev) };
+        for (model, urls) in to {
+            let known = from.get(model).unwrap_or(&empty);
+            for url in urls.difference(known) {
+                changes.push(FleetChange { model: model.clone(), url: url.clone(), added, at });
+            }
+        }
+    }
+    changes
+}
+
+/// The parts of a snapshot that change whenever the proxy is doing work.
+/// `None` when the snapshot shows activity (or no connection), so it never
+/// compares equal to the previous poll.
+#[derive(Clone, Debug, PartialEq)]
+struct IdleFingerprint {
+    total_requests: u64,
+    total_errors: u64,
+    total_tracked: u64,
+}
+
+impl IdleFingerprint {
+    fn from_snapshot(snap: &ProxySnapshot) -> Option<Self> {
+        let summary = &snap.queue.summary;
+        if !snap.connected.is_up() || summary.pending > 0 || summary.in_flight > 0 {
+            return None;
+        }
+        Some(Self {
+            total_requests: snap.stats.total_requests,
+            total_errors: snap.stats.total_errors,
+            total_tracked: summary.total_tracked,
+        })
+    }
+}
+
+/// Double the sleep (up to `max`) while consecutive polls are idle and
+/// identical; snap back to `base` as soon as anything changes. Doubling
+/// starts from at least 1s, so `--interval 0` backs off too.
+fn next_interval(
+    current: u64,
+    base: u64,
+    max: u64,
+    prev: Option<&IdleFingerprint>,
+    next: Option<&IdleFingerprint>,
+) -> u64 {
+    match (prev, next) {
+        (Some(p), Some(n)) if p == n => current.max(1).saturating_mul(2).min(max.max(base)),
+        _ => base,
+    }
+}
+
+/// Longest error or body excerpt echoed by the connection test.
+const DIAG_EXCERPT_CHARS: usize = 300;
+
+/// Hit each endpoint once and print what happened, for diagnosing a
+/// dashboard that shows no data. Returns true if both endpoints parsed.
+pub fn test_connection(proxy_url: &str, auth: &ProxyAuth) -> bool {
+    let client = match auth.client(Duration::from_secs(5)) {
+        Ok(c) => c,
+        Err(e) => {
+            println!("{}", e);
+            return false;
+        }
+    };
+
+    let stats = probe::<StatsResponse>(&client, &format!("{}/stats", proxy_url));
+    if let Some(stats) = &stats {
+        println!(
+            "  uptime={:.0}s requests={} errors={} strategy={} models={} pools={} schema_version={}",
+            stats.uptime_seconds,
+            stats.total_requests,
+            stats.total_errors,
+            if stats.strategy.is_empty() { "-" } else { &stats.strategy },
+            stats.models.len(),
+            stats.pools.len(),
+            stats.schema_version.as_deref().unwrap_or("-"),
+        );
+    }
+    println!();
+
+    let queue = probe::<QueueResponse>(&client, &format!("{}/queue/status", proxy_url));
+    if let Some(queue) = &queue {
+        let s = &queue.summary;
+        println!(
+            "  pending={} in_flight={} completed_last_minute={} tracked={} backends={} clients={} sessions={} orphans={}",
+            s.pending,
+            s.in_flight,
+            s.completed_last_minute,
+            s.total_tracked,
+            queue.backends.len(),
+            queue.clients.len(),
+            queue.sessions.len(),
+            queue.orphan_sessions.len(),
+        );
+    }
+
+    if stats.is_none() && queue.is_none() {
+        println!();
+        if let Some(health) = probe::<HealthResponse>(&client, &format!("{}/health", proxy_url)) {
+            println!("  {} (dashboard will run in limited mode)", health.summary());
+        }
+    }
+
+    stats.is_some() && queue.is_some()
+}
+
+/// GET `url`, report status/size/parse outcome, and return the parsed body.
+fn probe<T: DeserializeOwned>(client: &reqwest::blocking::Client, url: &str) -> Option<T> {
+    println!("GET {}", crate::credentials::redact_userinfo(url));
+    let resp = match client.get(url).send() {
+        Ok(r) => r,
+        Err(e) => {
+            println!("  request failed: {}", excerpt(&error_chain(&e)));
+            return None;
+        }
+    };
+    let status = resp.status();
+    let body = match resp.text() {
+        Ok(b) => b,
+        Err(e) => {
+            println!("  HTTP {} but reading body failed: {}", status, excerpt(&e.to_string()));
+            return None;
+        }
+    };
+    println!("  HTTP {} ({} bytes)", status, body.len());
+    if !status.is_success() {
+        println!("  body: {}", excerpt(&body));
+        return None;
+    }
+    match serde_json::from_str::<T>(&null_non_finite(&body)) {
+        Ok(parsed) => {
+            println!("  parse: ok");
+            Some(parsed)
+        }
+        Err(e) => {
+            println!("  parse: FAILED: {}", excerpt(&e.to_string()));
+            println!("  body: {}", excerpt(&body));
+            None
+        }
+    }
+}
+
+fn excerpt(text: &str) -> String {
+    if text.chars().count() > DIAG_EXCERPT_CHARS {
+        format!("{}...", text.chars().take(DIAG_EXCERPT_CHARS).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
+/// An error and its sources, e.g. "error sending request: connection refused".
+fn error_chain(e: &dyn std::error::Error) -> String {
+    let mut msg = e.to_string();
+    let mut source = e.source();
+    while let Some(inner) = source {
+        msg.push_str(": ");
+        msg.push_str(&inner.to_string());
+        source = inner.source();
+    }
+    msg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Connection;
+    use crate::test_support::{fixture, MockProxy};
+
+    /// Run the collector against `mock` until its first poll lands.
+    fn first_poll(mock: &MockProxy) -> ProxySnapshot {
+        first_poll_from(ProxySource::Http(mock.url.clone()))
+    }
+
+    fn first_poll_from(source: ProxySource) -> ProxySnapshot {
+        let label = match &source {
+            ProxySource::Http(url) => url.clone(),
+            ProxySource::Fixtures(dir) | ProxySource::Shared(dir) => dir.display().to_string(),
+            ProxySource::Replay(replay) => format!("replay at {}", replay.frame().written_at),
+        };
+        let snapshot = Arc::new(Mutex::new(ProxySnapshot::default()));
+        let client = ProxyAuth::default().client(Duration::from_secs(2)).unwrap();
+        let config = PollConfig {
+            interval_secs: 60,
+            adaptive: false,
+            max_interval_secs: 60,
+            metric_window_secs: 60,
+            min_poll: MIN_POLL_INTERVAL,
+        };
+        spawn_proxy_collector(source, config, client, PollSinks::default(), Arc::clone(&snapshot));
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while Instant::now() < deadline {
+            if snapshot.lock().unwrap().fetched_at.is_some() {
+                return snapshot.lock().unwrap().clone();
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        panic!("collector never completed a poll against {}", label);
+    }
+
+    #[test]
+    fn collector_reads_current_and_legacy_schemas() {
+        for queue_fixture in ["queue_status.json", "queue_status_legacy.json"] {
+            let mock = MockProxy::start(&[("/stats", fixture("stats.json")), ("/queue/status", fixture(queue_fixture))]);
+            let snap = first_poll(&mock);
+
+            assert_eq!(snap.connected, Connection::Up, "{}", queue_fixture);
+            assert_eq!(snap.stats.strategy, "least_loaded");
+            assert_eq!(snap.stats.pools[0].backends.len(), 2);
+            assert_eq!((snap.queue.summary.pending, snap.queue.backends.len()), (1, 2));
+            assert_eq!(snap.queue.pending[0].session_id.as_deref(), Some("sess-2"));
+            assert_eq!(snap.queue.in_flight[0].task_id.as_deref(), Some("task-a"));
+
+            let client = &snap.queue.clients[0];
+            assert_eq!(client.client_id, "host:1234");
+            assert_eq!(client.client_command, "python -m agents.run --config eval.yaml");
+            let sess = &client.sessions[0];
+            assert_eq!((sess.session_id.as_str(), sess.task_id.as_str()), ("sess-1", "task-a"));
+            let turn = &sess.completed_turns[0];
+            assert_eq!((turn.agent_obs_ms, turn.agent_act_ms), (Some(10.0), Some(20.0)));
+            assert_eq!((turn.prompt_tokens, turn.completion_tokens), (Some(100), Some(20)));
+            assert_eq!(snap.queue.orphan_sessions[0].session_id, "sess-2");
+            assert_eq!(snap.turns_completed, 0);
+        }
+    }
+
+    #[test]
+    fn collector_reads_fixture_directory() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        let snap = first_poll_from(ProxySource::Fixtures(dir));
+        assert_eq!(snap.connected, Connection::Up);
+        assert_eq!(snap.stats.strategy, "least_loaded");
+        assert_eq!(snap.queue.clients[0].sessions[0].session_id, "sess-1");
+    }
+
+    #[test]
+    fn collector_reads_whole_poll_from_shared_file() {
+        let path = std::env::temp_dir().join(format!("dashboard-shared-source-{}", std::process::id()));
+        let poll = SharedPoll {
+            written_at: chrono::Utc::now().timestamp_millis() as f64 / 1000.0,
+            stats: Some(serde_json::from_str(r#"{"strategy": "round_robin"}"#).unwrap()),
+            queue: None,
+            health: None,
+        };
+        let mut writer = SharedWriter::create(&path).unwrap();
+        writer.publish(&poll, Duration::from_secs(2)).unwrap();
+        let snap = first_poll_from(ProxySource::Shared(path.clone()));
+        assert_eq!(snap.connected, Connection::Up);
+        assert_eq!(snap.stats.strategy, "round_robin");
+
+        let parts = read_shared(&path);
+        assert!(parts.stats.is_ok() && parts.queue.is_err());
+
+        // Ten minutes old is stale for a 2s publisher, not one backing off to 300s
+        let old = SharedPoll { written_at: poll.written_at - 600.0, ..poll };
+        writer.publish(&old, Duration::from_secs(2)).unwrap();
+        assert!(read_shared(&path).stats.is_err());
+        writer.publish(&old, Duration::from_secs(300)).unwrap();
+        assert!(read_shared(&path).stats.is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn collector_falls_back_to_health_endpoint() {
+        let mock = MockProxy::start(&[("/health", r#"{"status": "ok", "healthy": 1, "backends": 2}"#.to_string())]);
+        let snap = first_poll(&mock);
+        assert_eq!(snap.connected, Connection::Down);
+        assert_eq!(snap.limited.map(|h| h.summary()).as_deref(), Some("ok, 1/2 backends healthy"));
+    }
+
+    #[test]
+    fn windowed_latency_uses_request_deltas() {
+        let base = HashMap::from([("a".to_string(), (100, 500.0)), ("b".to_string(), (10, 200.0))]);
+        let latest = HashMap::from([
+            ("a".to_string(), (110, 550.0)),
+            ("b".to_string(), (10, 200.0)),
+            ("c".to_string(), (4, 300.0)),
+        ]);
+        let recent = windowed_latency(&base, &latest);
+        // 110*550 - 100*500 = 10500 over 10 new requests
+        assert_eq!(recent.get("a"), Some(&1050.0));
+        assert_eq!(recent.get("b"), None);
+        assert_eq!(recent.get("c"), Some(&300.0));
+    }
+
+    #[test]
+    fn non_finite_timings_decode_as_missing() {
+        let body = r#"{"sessions": [{"session_id": "NaN \"Infinity\"", "completed_turns": [
+            {"submitted_at": 10.0, "completed_at": NaN, "total_time_ms": Infinity,
+             "wait_time_ms": -Infinity, "processing_time_ms": 5.0, "backend_time_ms": NaN}
+        ]}]}"#;
+        assert_eq!(null_non_finite(r#"{"a": 1.5}"#), Cow::Borrowed(r#"{"a": 1.5}"#));
+
+        let queue: QueueResponse = serde_json::from_str(&null_non_finite(body)).unwrap();
+        let sess = &queue.sessions[0];
+        assert_eq!(sess.session_id, "NaN \"Infinity\"");
+        let turn = &sess.completed_turns[0];
+        assert_eq!((turn.submitted_at, turn.completed_at), (10.0, 0.0));
+        assert_eq!((turn.total_time_ms, turn.wait_time_ms, turn.processing_time_ms), (0.0, 0.0, 5.0));
+        assert_eq!(turn.backend_time_ms, None);
+    }
+
+    #[test]
+    fn odometer_counts_deltas_across_cleanup() {
+        let queue = |sessions: serde_json::Value| -> QueueResponse {
+            serde_json::from_value(serde_json::json!({ "sessions": sessions })).unwrap()
+        };
+        let start = Instant::now();
+        let mut odo = TurnOdometer::new(Duration::from_secs(300));
+
+        // Turns finished before launch are the starting point, not counted
+        odo.update(&queue(serde_json::json!([{"session_id": "a", "completed_requests": 5}])), start);
+        assert_eq!(odo.total, 0);
+        assert_eq!(odo.rate_per_min(), None);
+
+        // Requests cleaned up: completed_requests drops, total_turns survives
+        odo.update(
+            &queue(serde_json::json!([
+                {"session_id": "a", "completed_requests": 1, "total_turns": 7},
+                {"session_id": "b", "completed_requests": 2}
+            ])),
+            start + Duration::from_secs(30),
+        );
+        assert_eq!(odo.total, 4);
+
+        // Session a is gone; its turns stay counted
+        odo.update(
+            &queue(serde_json::json!([{"session_id": "b", "completed_requests": 3}])),
+            start + Duration::from_secs(60),
+        );
+        assert_eq!(odo.total, 5);
+        assert_eq!(odo.rate_per_min(), Some(5.0));
+
+        // Session a reappears; only turns past its last count are new
+        odo.update(
+            &queue(serde_json::json!([
+                {"session_id": "a", "completed_requests": 8},
+                {"session_id": "b", "completed_requests": 3}
+            ])),
+            start + Duration::from_secs(90),
+        );
+        assert_eq!(odo.total, 6);
+    }
+
+    #[test]
+    fn unrouted_tracker_ages_requests_until_routed() {
+        let queue = |in_flight: serde_json::Value| -> QueueResponse {
+            serde_json::from_value(serde_json::json!({ "in_flight": in_flight })).unwrap()
+        };
+        let start = Instant::now();
+        let mut tracker = UnroutedTracker::default();
+
+        let first = tracker.update(
+            &queue(serde_json::json!([
+                {"request_id": "a"},
+                {"request_id": "b", "backend": "http://gpu:8001"}
+            ])),
+            start,
+        );
+        assert_eq!(first, UnroutedRequests { count: 1, oldest: Duration::ZERO });
+
+        let later = tracker.update(
+            &queue(serde_json::json!([{"request_id": "a"}, {"request_id": "c", "backend": ""}])),
+            start + Duration::from_secs(8),
+        );
+        assert_eq!(later, UnroutedRequests { count: 2, oldest: Duration::from_secs(8) });
+
+        // Once routed, a request stops counting and its age is forgotten
+        let routed = tracker.update(
+            &queue(serde_json::json!([{"request_id": "a", "backend": "http://gpu:8001"}, {"request_id": "c"}])),
+            start + Duration::from_secs(10),
+        );
+        assert_eq!(routed, UnroutedRequests { count: 1, oldest: Duration::from_secs(2) });
+    }
+
+    fn health(entries: &[(&str, bool)]) -> HashMap<String, bool> {
+        entries.iter().map(|(u, h)| (u.to_string(), *h)).collect()
+    }
+
+    #[test]
+    fn diff_health_reports_flips_and_new_down_backends() {
+        let prev = health(&[("a", true), ("b", false), ("c", true)]);
+        let next = health(&[("a", false), ("b", true), ("c", true), ("d", false), ("e", true)]);
+        let t = diff_health(&prev, &next);
+        assert_eq!(
+            t,
+            vec![
+                HealthTransition { url: "a".into(), healthy: false },
+                HealthTransition { url: "b".into(), healthy: true },
+                HealthTransition { url: "d".into(), healthy: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_fleet_reports_backends_joining_and_leaving_pools() {
+        let fleet = |pools: &[(&str, &[&str])]| -> Fleet {
+            pools
+                .iter()
+                .map(|(m, urls)| (m.to_string(), urls.iter().map(|u| u.to_string()).collect()))
+                .collect()
+        };
+        let prev = fleet(&[("a", &["u1", "u2"]), ("b", &["u3"])]);
+        let next = fleet(&[("a", &["u2", "u4"]), ("c", &["u5"])]);
+        let change = |model: &str, url: &str, added| FleetChange { model: model.into(), url: url.into(), added, at: 7.0 };
+        assert_eq!(
+            diff_fleet(&prev, &next, 7.0),
+            vec![change("a", "u1", false), change("b", "u3", false), change("a", "u4", true), change("c", "u5", true)]
+        );
+        assert!(diff_fleet(&next, &next, 7.0).is_empty());
+    }
+
+    #[test]
+    fn poll_guard_waits_out_the_floor() {
+        let mut guard = PollGuard::new(Duration::from_millis(250));
+        let now = Instant::now();
+        assert_eq!(guard.delay(now), Duration::ZERO);
+        guard.last = Some(now);
+        assert_eq!(guard.delay(now + Duration::from_millis(100)), Duration::from_millis(150));
+        assert_eq!(guard.delay(now + Duration::from_millis(300)), Duration::ZERO);
+    }
+
+    #[test]
+    fn adaptive_interval_backs_off_only_while_idle() {
+        let idle = IdleFingerprint { total_requests: 5, total_errors: 0, total_tracked: 2 };
+        assert_eq!(next_interval(2, 2, 30, Some(&idle), Some(&idle)), 4);
+        assert_eq!(next_interval(16, 2, 30, Some(&idle), Some(&idle)), 30);
+        assert_eq!(next_interval(16, 2, 30, Some(&idle), None), 2);
+        // From the sub-second floor of --interval 0
+        assert_eq!(next_interval(0, 0, 30, Some(&idle), Some(&idle)), 2);
+        assert_eq!(next_interval(2, 0, 30, Some(&idle), Some(&idle)), 4);
+        assert_eq!(next_interval(4, 0, 30, Some(&idle), None), 0);
+    }
+}
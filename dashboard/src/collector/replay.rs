@@ -0,0 +1,276 @@
+//! Recording polls to a file (`--record`) and playing them back
+//! (`--replay`) with live speed, pause and single-frame controls.
+//!
+//! A recording holds one `SharedPoll` per line as JSON, the same record a
+//! shared snapshot carries, appended as each poll completes. Playback runs
+//! on the collector thread and takes its controls over a channel from the
+//! UI, publishing its position back for the header.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::shared::SharedPoll;
+use crate::model::ReplayStatus;
+
+/// Slowest playback; each `Slower` halves the speed down to this.
+pub const MIN_SPEED: f64 = 1.0 / 16.0;
+/// Fastest playback; each `Faster` doubles the speed up to this.
+pub const MAX_SPEED: f64 = 64.0;
+
+/// Longest recorded gap played between two frames, so a recording that
+/// spans a dashboard restart doesn't stall playback for hours.
+const MAX_GAP_SECS: f64 = 60.0;
+
+/// Appends each poll to a recording.
+pub struct Recorder {
+    out: BufWriter<File>,
+}
+
+impl Recorder {
+    /// Append to `path`, creating it if needed.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Recorder { out: BufWriter::new(file) })
+    }
+
+    pub fn record(&mut self, poll: &SharedPoll) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, poll).map_err(io::Error::other)?;
+        self.out.write_all(b"\n")?;
+        // Flushed per poll so a crash loses at most the poll being written
+        self.out.flush()
+    }
+}
+
+/// Every frame of a recording, oldest first. A last line cut short by a
+/// crash is dropped; a bad line anywhere else is an error.
+pub fn load(path: &Path) -> io::Result<Vec<SharedPoll>> {
+    let lines: Vec<String> = BufReader::new(File::open(path)?).lines().collect::<io::Result<_>>()?;
+    let lines: Vec<(usize, &String)> = lines.iter().enumerate().filter(|(_, l)| !l.trim().is_empty()).collect();
+    let mut frames = Vec::new();
+    for (i, (line_no, line)) in lines.iter().enumerate() {
+        match serde_json::from_str(line) {
+            Ok(poll) => frames.push(poll),
+            Err(_) if i + 1 == lines.len() => break,
+            Err(e) => {
+                let msg = format!("{}:{}: {}", path.display(), line_no + 1, e);
+                return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+            }
+        }
+    }
+    if frames.is_empty() {
+        let msg = format!("{} holds no recorded polls", path.display());
+        return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+    }
+    Ok(frames)
+}
+
+/// A request from the UI to the replay.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplayControl {
+    TogglePause,
+    Faster,
+    Slower,
+    /// Advance exactly one frame; only while paused.
+    Step,
+}
+
+/// The UI's end of a replay: controls go in, the position comes out.
+pub struct ReplayHandle {
+    controls: Sender<ReplayControl>,
+    status: Arc<Mutex<ReplayStatus>>,
+}
+
+impl ReplayHandle {
+    pub fn send(&self, control: ReplayControl) {
+        // The replay only stops with the process, so a send can't be lost
+        let _ = self.controls.send(control);
+    }
+
+    pub fn status(&self) -> ReplayStatus {
+        *self.status.lock().unwrap()
+    }
+}
+
+/// Playback of a recording, driven by the collector thread.
+pub struct Replay {
+    frames: Vec<SharedPoll>,
+    pos: usize,
+    speed: f64,
+    paused: bool,
+    controls: Receiver<ReplayControl>,
+    status: Arc<Mutex<ReplayStatus>>,
+}
+
+impl Replay {
+    /// Start playing `frames` (at least one) from the first, at `speed`.
+    pub fn new(frames: Vec<SharedPoll>, speed: f64) -> (Replay, ReplayHandle) {
+        assert!(!frames.is_empty(), "a replay needs at least one frame");
+        let (tx, rx) = mpsc::channel();
+        let status = Arc::new(Mutex::new(ReplayStatus::default()));
+        let replay = Replay {
+            frames,
+            pos: 0,
+            speed: speed.clamp(MIN_SPEED, MAX_SPEED),
+            paused: false,
+            controls: rx,
+            status: Arc::clone(&status),
+        };
+        replay.publish();
+        (replay, ReplayHandle { controls: tx, status })
+    }
+
+    /// The frame on screen.
+    pub fn frame(&self) -> &SharedPoll {
+        &self.frames[self.pos]
+    }
+
+    fn ended(&self) -> bool {
+        self.pos + 1 == self.frames.len()
+    }
+
+    /// Recorded seconds between the frame on screen and the next.
+    fn gap(&self) -> f64 {
+        let gap = self.frames[self.pos + 1].written_at - self.frame().written_at;
+        if gap.is_finite() { gap.clamp(0.0, MAX_GAP_SECS) } else { 0.0 }
+    }
+
+    fn publish(&self) {
+        *self.status.lock().unwrap() = ReplayStatus {
+            frame: self.pos + 1,
+            frames: self.frames.len(),
+            recorded_at: self.frame().written_at,
+            speed: self.speed,
+            paused: self.paused,
+            ended: self.ended(),
+        };
+    }
+
+    /// Block until the next frame is due, applying controls as they come,
+    /// then move to it. Playing waits out the recorded gap at the current
+    /// speed; paused, only `Step` advances. The last frame is held for good.
+    pub fn wait(&mut self) {
+        // Recorded seconds of the current gap already played
+        let mut played = 0.0;
+        loop {
+            let timeout = (!self.paused && !self.ended())
+                .then(|| Duration::from_secs_f64(((self.gap() - played) / self.speed).max(0.0)));
+            let started = Instant::now();
+            let Some(control) = self.next_control(timeout) else {
+                self.pos += 1;
+                self.publish();
+                return;
+            };
+            if !self.paused {
+                played += started.elapsed().as_secs_f64() * self.speed;
+            }
+            match control {
+                ReplayControl::TogglePause => self.paused = !self.paused,
+                ReplayControl::Faster => self.speed = (self.speed * 2.0).min(MAX_SPEED),
+                ReplayControl::Slower => self.speed = (self.speed / 2.0).max(MIN_SPEED),
+                ReplayControl::Step if self.paused && !self.ended() => {
+                    self.pos += 1;
+                    self.publish();
+                    return;
+                }
+                ReplayControl::Step => {}
+            }
+            self.publish();
+        }
+    }
+
+    /// The next control, or `None` once `timeout` passes (never, without one).
+    fn next_control(&self, timeout: Option<Duration>) -> Option<ReplayControl> {
+        let received = match timeout {
+            Some(timeout) => match self.controls.recv_timeout(timeout) {
+                Ok(control) => Ok(control),
+                Err(RecvTimeoutError::Timeout) => return None,
+                Err(RecvTimeoutError::Disconnected) => Err(()),
+            },
+            None => self.controls.recv().map_err(|_| ()),
+        };
+        received.ok().or_else(|| {
+            // No UI left to send controls: keep playing, or hold for good
+            match timeout {
+                Some(timeout) => thread::sleep(timeout),
+                None => loop {
+                    thread::park();
+                },
+            }
+            None
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(times: &[f64]) -> Vec<SharedPoll> {
+        times.iter().map(|&t| SharedPoll { written_at: t, ..SharedPoll::default() }).collect()
+    }
+
+    #[test]
+    fn recording_round_trips_and_drops_a_torn_tail() {
+        let path = std::env::temp_dir().join(format!("dashboard-record-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut recorder = Recorder::create(&path).unwrap();
+        for poll in frames(&[1.0, 2.0]) {
+            recorder.record(&poll).unwrap();
+        }
+        drop(recorder);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"written_at\": 3.0, \"sta").unwrap();
+        let loaded = load(&path).unwrap();
+        assert_eq!(loaded.iter().map(|p| p.written_at).collect::<Vec<_>>(), [1.0, 2.0]);
+
+        std::fs::write(&path, "not json\n{\"written_at\": 1.0}\n").unwrap();
+        assert_eq!(load(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        std::fs::write(&path, "\n").unwrap();
+        assert_eq!(load(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn plays_at_speed_then_steps_while_paused_and_holds_the_end() {
+        let (mut replay, handle) = Replay::new(frames(&[100.0, 100.02, 130.0, 160.0]), 1.0);
+        assert_eq!(handle.status().frame, 1);
+        assert_eq!(handle.status().recorded_at, 100.0);
+
+        // A 20ms recorded gap plays out on its own
+        replay.wait();
+        assert_eq!(handle.status().frame, 2);
+
+        // Speed changes apply to the wait in progress; a step while
+        // playing is ignored, and paused only a step advances
+        handle.send(ReplayControl::Faster);
+        handle.send(ReplayControl::Step);
+        handle.send(ReplayControl::TogglePause);
+        handle.send(ReplayControl::Step);
+        replay.wait();
+        let status = handle.status();
+        assert_eq!((status.frame, status.speed, status.paused, status.ended), (3, 2.0, true, false));
+
+        handle.send(ReplayControl::Step);
+        replay.wait();
+        assert_eq!(replay.frame().written_at, 160.0);
+        assert!(handle.status().ended);
+    }
+
+    #[test]
+    fn speed_stays_within_bounds() {
+        let (mut replay, handle) = Replay::new(frames(&[0.0, 1.0]), 1000.0);
+        assert_eq!(handle.status().speed, MAX_SPEED);
+        handle.send(ReplayControl::TogglePause);
+        for _ in 0..20 {
+            handle.send(ReplayControl::Slower);
+        }
+        handle.send(ReplayControl::Step);
+        replay.wait();
+        assert_eq!(handle.status().speed, MIN_SPEED);
+    }
+}
@@ -0,0 +1,303 @@
+//! A poll result shared through a memory-mapped file, so one collecting
+//! dashboard can feed many viewers on a login node without each of them
+//! polling the proxy.
+//!
+//! The file is a seqlock: a 24-byte header holding a sequence number, the
+//! payload length and the publisher's poll interval in milliseconds (all
+//! native-endian `u64`), followed by the JSON payload.
+//! The writer makes the sequence odd, rewrites the payload in place, then
+//! makes it even again. A reader retries while the sequence is odd or changed
+//! under it, so it never decodes a half-written poll. Both ends map the file
+//! shared, so on a local filesystem (`/dev/shm` is a good home) every process
+//! reads the same pages. The writer only ever grows the file; nothing else
+//! may truncate it while viewers have it mapped. Mapping is Unix-only;
+//! elsewhere opening either end fails.
+
+#[cfg(unix)]
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+#[cfg(unix)]
+use std::sync::atomic::{fence, AtomicU64, Ordering};
+#[cfg(unix)]
+use std::thread;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{HealthResponse, QueueResponse, StatsResponse};
+
+#[cfg(unix)]
+const HEADER_LEN: usize = 24;
+
+/// Size a new shared file starts at; the writer doubles it when a poll
+/// outgrows it.
+#[cfg(unix)]
+const INITIAL_LEN: usize = 64 * 1024;
+
+/// Attempts before a reader gives up on a writer that keeps racing it.
+#[cfg(unix)]
+const READ_ATTEMPTS: usize = 20;
+
+/// Publisher poll intervals a viewer waits out before treating the
+/// publisher as dead and showing the proxy as down, rather than freezing on
+/// old data. An adaptive publisher at most doubles its interval per poll.
+const STALE_INTERVALS: u32 = 4;
+
+/// Least age at which a published poll counts as stale, however short the
+/// publisher's interval, so a briefly slow poll doesn't flap viewers.
+const STALE_FLOOR: Duration = Duration::from_secs(120);
+
+/// How old a poll may get before viewers treat its publisher as dead.
+pub fn stale_after(interval: Duration) -> Duration {
+    (interval * STALE_INTERVALS).max(STALE_FLOOR)
+}
+
+/// What one poll returned; a part is `None` when its endpoint failed.
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+pub struct SharedPoll {
+    /// Writer wall clock (epoch seconds) at publish time.
+    pub written_at: f64,
+    pub stats: Option<StatsResponse>,
+    pub queue: Option<QueueResponse>,
+    pub health: Option<HealthResponse>,
+}
+
+/// A poll read back from a shared file.
+#[derive(Debug)]
+pub struct Published {
+    pub poll: SharedPoll,
+    /// The publisher's interval until its next poll.
+    pub interval: Duration,
+}
+
+/// A whole file mapped `MAP_SHARED`, unmapped on drop.
+#[cfg(unix)]
+struct Mapping {
+    ptr: *mut u8,
+    len: usize,
+}
+
+#[cfg(unix)]
+impl Mapping {
+    /// Map all of `file`, which must hold at least the header.
+    fn new(file: &File, writable: bool) -> io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let len = file.metadata()?.len() as usize;
+        if len < HEADER_LEN {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "shared snapshot file has no header"));
+        }
+        let prot = if writable { libc::PROT_READ | libc::PROT_WRITE } else { libc::PROT_READ };
+        // SAFETY: a fresh shared mapping of an open file; the kernel picks the address
+        let ptr = unsafe { libc::mmap(std::ptr::null_mut(), len, prot, libc::MAP_SHARED, file.as_raw_fd(), 0) };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Mapping { ptr: ptr.cast(), len })
+    }
+
+    /// The header word at `offset` (0, 8 or 16). Mappings are page-aligned, so
+    /// these are aligned for atomic access.
+    fn word(&self, offset: usize) -> &AtomicU64 {
+        debug_assert!(offset + 8 <= HEADER_LEN);
+        // SAFETY: in bounds (len >= HEADER_LEN), aligned, and shared memory
+        // that other processes only touch through atomics too
+        unsafe { &*self.ptr.add(offset).cast::<AtomicU64>() }
+    }
+
+    fn seq(&self) -> &AtomicU64 {
+        self.word(0)
+    }
+
+    fn payload_len(&self) -> &AtomicU64 {
+        self.word(8)
+    }
+
+    fn interval_ms(&self) -> &AtomicU64 {
+        self.word(16)
+    }
+
+    fn capacity(&self) -> usize {
+        self.len - HEADER_LEN
+    }
+
+    /// Copy the first `len` payload bytes out; the caller checks the
+    /// sequence afterwards to know whether they were torn.
+    fn read_payload(&self, len: usize) -> Vec<u8> {
+        assert!(len <= self.capacity());
+        let mut out = vec![0; len];
+        // SAFETY: in bounds per the assert; a concurrent write only tears
+        // the copy, which the seqlock detects
+        unsafe { std::ptr::copy_nonoverlapping(self.ptr.add(HEADER_LEN), out.as_mut_ptr(), len) };
+        out
+    }
+
+    fn write_payload(&mut self, payload: &[u8]) {
+        assert!(payload.len() <= self.capacity());
+        // SAFETY: in bounds per the assert, on a writable mapping
+        unsafe { std::ptr::copy_nonoverlapping(payload.as_ptr(), self.ptr.add(HEADER_LEN), payload.len()) };
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        // SAFETY: exactly the region returned by mmap in `new`
+        unsafe { libc::munmap(self.ptr.cast(), self.len) };
+    }
+}
+
+// SAFETY: the mapping is owned like a heap buffer; nothing else in this
+// process aliases it, so it can move to the collector thread
+#[cfg(unix)]
+unsafe impl Send for Mapping {}
+
+/// The single collector publishing into a shared file.
+#[cfg(unix)]
+pub struct SharedWriter {
+    file: File,
+    map: Mapping,
+    seq: u64,
+}
+
+#[cfg(unix)]
+impl SharedWriter {
+    /// Open or create `path`, continuing its sequence so viewers already
+    /// reading it see the next poll as new.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+        if (file.metadata()?.len() as usize) < INITIAL_LEN {
+            file.set_len(INITIAL_LEN as u64)?;
+        }
+        let map = Mapping::new(&file, true)?;
+        let seq = map.seq().load(Ordering::Acquire);
+        Ok(SharedWriter { file, map, seq: seq + seq % 2 })
+    }
+
+    /// Publish `poll`, with the interval until the next one so viewers know
+    /// how long to wait before calling it stale.
+    pub fn publish(&mut self, poll: &SharedPoll, interval: Duration) -> io::Result<()> {
+        let payload = serde_json::to_vec(poll).map_err(io::Error::other)?;
+        self.seq += 1;
+        self.map.seq().store(self.seq, Ordering::Relaxed);
+        fence(Ordering::Release);
+        if payload.len() > self.map.capacity() {
+            // Readers holding the old, shorter mapping see the odd sequence and remap
+            let len = (HEADER_LEN + payload.len()).next_power_of_two();
+            self.file.set_len(len as u64)?;
+            self.map = Mapping::new(&self.file, true)?;
+        }
+        self.map.write_payload(&payload);
+        self.map.payload_len().store(payload.len() as u64, Ordering::Relaxed);
+        self.map.interval_ms().store(interval.as_millis() as u64, Ordering::Relaxed);
+        self.seq += 1;
+        self.map.seq().store(self.seq, Ordering::Release);
+        Ok(())
+    }
+}
+
+/// Read the latest consistent poll from a shared file.
+#[cfg(unix)]
+pub fn read(path: &Path) -> io::Result<Published> {
+    let file = File::open(path)?;
+    for _ in 0..READ_ATTEMPTS {
+        // Mapped afresh each attempt, since the writer may have grown the file
+        let map = Mapping::new(&file, false)?;
+        let before = map.seq().load(Ordering::Acquire);
+        if before % 2 == 1 {
+            thread::sleep(Duration::from_millis(1));
+            continue;
+        }
+        let len = map.payload_len().load(Ordering::Relaxed);
+        // A length past the end of the file means a torn header or a file
+        // that isn't a shared snapshot; never allocate on its say-so
+        if len > map.capacity() as u64 {
+            if map.seq().load(Ordering::Acquire) == before {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "not a shared snapshot file"));
+            }
+            continue;
+        }
+        let payload = map.read_payload(len as usize);
+        let interval = Duration::from_millis(map.interval_ms().load(Ordering::Relaxed));
+        fence(Ordering::Acquire);
+        if map.seq().load(Ordering::Relaxed) == before {
+            let poll = serde_json::from_slice(&payload).map_err(io::Error::other)?;
+            return Ok(Published { poll, interval });
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    Err(io::Error::new(io::ErrorKind::WouldBlock, "shared snapshot kept changing while being read"))
+}
+
+#[cfg(not(unix))]
+pub struct SharedWriter;
+
+#[cfg(not(unix))]
+impl SharedWriter {
+    pub fn create(_path: &Path) -> io::Result<Self> {
+        Err(unsupported())
+    }
+
+    pub fn publish(&mut self, _poll: &SharedPoll, _interval: Duration) -> io::Result<()> {
+        Err(unsupported())
+    }
+}
+
+#[cfg(not(unix))]
+pub fn read(_path: &Path) -> io::Result<Published> {
+    Err(unsupported())
+}
+
+#[cfg(not(unix))]
+fn unsupported() -> io::Error {
+    io::Error::new(io::ErrorKind::Unsupported, "shared snapshots need a Unix filesystem")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reader_sees_latest_poll_and_skips_torn_writes() {
+        let path = std::env::temp_dir().join(format!("dashboard-shared-{}", std::process::id()));
+        let mut writer = SharedWriter::create(&path).unwrap();
+        let mut poll = SharedPoll { written_at: 1.0, ..SharedPoll::default() };
+        poll.stats = Some(serde_json::from_str(r#"{"strategy": "least_loaded"}"#).unwrap());
+        writer.publish(&poll, Duration::from_secs(300)).unwrap();
+        let read_back = read(&path).unwrap();
+        assert_eq!(read_back.interval, Duration::from_secs(300));
+        assert_eq!(read_back.poll.stats.unwrap().strategy, "least_loaded");
+        assert!(read_back.poll.queue.is_none());
+
+        // A shorter poll overwrites in place; the stale tail is ignored
+        writer.publish(&SharedPoll { written_at: 2.0, ..SharedPoll::default() }, Duration::from_secs(2)).unwrap();
+        assert_eq!(read(&path).unwrap().poll.written_at, 2.0);
+
+        // A poll larger than the file grows it and remaps
+        let mut big = SharedPoll { written_at: 3.0, ..SharedPoll::default() };
+        big.stats = Some(serde_json::from_value(serde_json::json!({"strategy": "x".repeat(INITIAL_LEN)})).unwrap());
+        writer.publish(&big, Duration::from_secs(2)).unwrap();
+        assert!(std::fs::metadata(&path).unwrap().len() as usize > INITIAL_LEN);
+        assert_eq!(read(&path).unwrap().poll.stats.unwrap().strategy.len(), INITIAL_LEN);
+
+        // A corrupt length is rejected without allocating it
+        writer.map.payload_len().store(u64::MAX, Ordering::Release);
+        assert_eq!(read(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        // A writer that died mid-publish leaves an odd sequence
+        writer.map.seq().store(writer.seq + 1, Ordering::Release);
+        assert_eq!(read(&path).unwrap_err().kind(), io::ErrorKind::WouldBlock);
+
+        // A restarted writer resumes from an even sequence
+        let restarted = SharedWriter::create(&path).unwrap();
+        assert_eq!(restarted.seq, writer.seq + 2);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn staleness_scales_with_the_publisher_interval() {
+        assert_eq!(stale_after(Duration::from_secs(2)), STALE_FLOOR);
+        assert_eq!(stale_after(Duration::from_secs(300)), Duration::from_secs(1200));
+    }
+}
@@ -5,8 +5,6 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, SystemTime};
 
-use chrono::{DateTime, Local};
-
 use crate::model::{CompletionEntry, ThroughputSnapshot};
 
 #[derive(serde::Deserialize)]
@@ -15,24 +13,42 @@ struct VerificationFile {
     overall_success: Option<bool>,
 }
 
+/// Where a run's start time comes from, for per-run duration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RunStartSource {
+    /// `started_at`/`start_time` in artifact.json or verification.json.
+    Fields,
+    /// Modification time of a marker file with this name in the run directory.
+    Marker(String),
+    /// Don't compute run durations.
+    Off,
+}
+
 pub fn spawn_throughput_collector(
-    artifacts_dir: PathBuf,
+    artifact_roots: Vec<PathBuf>,
     interval_secs: u64,
     window_secs: u64,
     recent_count: usize,
+    run_start: RunStartSource,
     snapshot: Arc<Mutex<ThroughputSnapshot>>,
 ) -> thread::JoinHandle<()> {
     thread::spawn(move || {
+        let labels = root_labels(&artifact_roots);
         let mut seen: HashSet<PathBuf> = HashSet::new();
         let mut timestamps: VecDeque<SystemTime> = VecDeque::new();
         let mut all_entries: Vec<(SystemTime, CompletionEntry)> = Vec::new();
         let mut total = 0usize;
         let mut success = 0usize;
         let mut failure = 0usize;
+        let mut duration_sum = 0.0_f64;
+        let mut duration_count = 0usize;
 
         loop {
-            let artifact_dirs = find_artifact_dirs(&artifacts_dir);
-            for path in artifact_dirs {
+            let artifact_dirs = artifact_roots
+                .iter()
+                .zip(&labels)
+                .flat_map(|(root, label)| find_artifact_dirs(root).into_iter().map(move |p| (label, p)));
+            for (root_label, path) in artifact_dirs {
                 if seen.contains(&path) {
                     continue;
                 }
@@ -55,19 +71,27 @@ pub fn spawn_throughput_collector(
                     failure += 1;
                 }
 
-                let dt: DateTime<Local> = modified.into();
-                let time_str = dt.format("%H:%M:%S").to_string();
-                let spec_name = path
+                let mut spec_name = path
                     .file_name()
                     .map(|n| n.to_string_lossy().to_string())
                     .unwrap_or_else(|| "unknown".to_string());
+                if let Some(label) = root_label {
+                    spec_name = format!("{}/{}", label, spec_name);
+                }
+
+                let duration = run_duration(&path, &run_start, modified);
+                if let Some(d) = duration {
+                    duration_sum += d.as_secs_f64();
+                    duration_count += 1;
+                }
 
                 all_entries.push((
                     modified,
                     CompletionEntry {
-                        time: time_str,
+                        modified,
                         spec_name,
                         success: is_success,
+                        duration,
                     },
                 ));
             }
@@ -84,9 +108,7 @@ pub fn spawn_throughput_collector(
             }
 
             // Calculate rate
-            let rate_per_min = if timestamps.is_empty() {
-                0.0
-            } else if timestamps.len() == 1 {
+            let rate_per_min = if timestamps.len() <= 1 {
                 0.0
             } else {
                 let oldest = *timestamps.front().unwrap();
@@ -100,7 +122,7 @@ pub fn spawn_throughput_collector(
             };
 
             // Build recent list (sorted newest first)
-            all_entries.sort_by(|a, b| b.0.cmp(&a.0));
+            all_entries.sort_by_key(|e| std::cmp::Reverse(e.0));
             let recent: Vec<CompletionEntry> = all_entries
                 .iter()
                 .take(recent_count)
@@ -114,7 +136,9 @@ pub fn spawn_throughput_collector(
             snap.success = success;
             snap.failure = failure;
             snap.rate_per_min = rate_per_min;
+            snap.avg_run_secs = (duration_count > 0).then(|| duration_sum / duration_count as f64);
             snap.recent = recent;
+            snap.last_completion = all_entries.first().map(|e| e.0);
             drop(snap);
 
             thread::sleep(Duration::from_secs(interval_secs));
@@ -122,6 +146,34 @@ pub fn spawn_throughput_collector(
     })
 }
 
+/// Prefix for spec names from each root, so specs with the same name under
+/// different roots stay distinct. `None` for a single root (no prefix).
+/// Roots sharing a directory name fall back to their full path.
+fn root_labels(roots: &[PathBuf]) -> Vec<Option<String>> {
+    if roots.len() <= 1 {
+        return vec![None; roots.len()];
+    }
+    let names: Vec<String> = roots
+        .iter()
+        .map(|r| {
+            r.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| r.display().to_string())
+        })
+        .collect();
+    roots
+        .iter()
+        .zip(&names)
+        .map(|(root, name)| {
+            if names.iter().filter(|n| *n == name).count() > 1 {
+                Some(root.display().to_string())
+            } else {
+                Some(name.clone())
+            }
+        })
+        .collect()
+}
+
 /// Recursively find directories containing artifact.json.
 fn find_artifact_dirs(root: &Path) -> Vec<PathBuf> {
     let mut result = Vec::new();
@@ -147,6 +199,48 @@ fn walk_for_artifacts(dir: &Path, result: &mut Vec<PathBuf>) {
     }
 }
 
+/// How long the run in `dir` took, ending at the artifact's mtime unless the
+/// files record an explicit end. `None` when no start time can be found.
+fn run_duration(dir: &Path, source: &RunStartSource, artifact_mtime: SystemTime) -> Option<Duration> {
+    let (start, end) = match source {
+        RunStartSource::Off => return None,
+        RunStartSource::Marker(name) => {
+            let start = dir.join(name).metadata().and_then(|m| m.modified()).ok()?;
+            (start, artifact_mtime)
+        }
+        RunStartSource::Fields => {
+            let files = ["artifact.json", "verification.json"].map(|f| read_json(&dir.join(f)));
+            let field = |keys: &[&str]| {
+                files
+                    .iter()
+                    .flatten()
+                    .find_map(|v| keys.iter().find_map(|k| v.get(*k).and_then(parse_timestamp)))
+            };
+            let start = field(&["started_at", "start_time"])?;
+            let end = field(&["finished_at", "end_time"]).unwrap_or(artifact_mtime);
+            (start, end)
+        }
+    };
+    end.duration_since(start).ok()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Accept epoch seconds or an RFC 3339 string.
+fn parse_timestamp(v: &serde_json::Value) -> Option<SystemTime> {
+    match v {
+        serde_json::Value::Number(n) => {
+            let secs = n.as_f64().filter(|s| *s > 0.0)?;
+            SystemTime::UNIX_EPOCH.checked_add(Duration::try_from_secs_f64(secs).ok()?)
+        }
+        serde_json::Value::String(s) => chrono::DateTime::parse_from_rfc3339(s).ok().map(SystemTime::from),
+        _ => None,
+    }
+}
+
 fn check_success(dir: &Path) -> bool {
     let verification_path = dir.join("verification.json");
     if verification_path.exists() {
@@ -158,3 +252,48 @@ fn check_success(dir: &Path) -> bool {
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_timestamp_accepts_epoch_and_rfc3339() {
+        let epoch = parse_timestamp(&json!(1700000000.5)).unwrap();
+        let iso = parse_timestamp(&json!("2023-11-14T22:13:20.5Z")).unwrap();
+        assert_eq!(epoch, iso);
+        assert!(parse_timestamp(&json!(null)).is_none());
+        // Out of range for SystemTime: rejected rather than panicking
+        assert!(parse_timestamp(&json!(1e300)).is_none());
+    }
+
+    #[test]
+    fn run_duration_from_artifact_fields() {
+        let dir = std::env::temp_dir().join(format!("dashboard-run-duration-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("artifact.json"), r#"{"started_at": 1000.0, "finished_at": 1090.0}"#).unwrap();
+
+        let d = run_duration(&dir, &RunStartSource::Fields, SystemTime::now());
+        assert_eq!(d, Some(Duration::from_secs(90)));
+        assert_eq!(run_duration(&dir, &RunStartSource::Off, SystemTime::now()), None);
+        assert_eq!(run_duration(&dir, &RunStartSource::Marker("start".into()), SystemTime::now()), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn root_labels_disambiguate_shared_names() {
+        assert_eq!(root_labels(&[PathBuf::from("/data/run1")]), vec![None]);
+        let labels = root_labels(&[
+            PathBuf::from("/data/run1"),
+            PathBuf::from("/a/out"),
+            PathBuf::from("/b/out"),
+        ]);
+        assert_eq!(
+            labels,
+            vec![Some("run1".to_string()), Some("/a/out".to_string()), Some("/b/out".to_string())]
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// One poll's value and when it was taken.
+pub type Sample<T> = (Instant, T);
+
+/// Timestamped samples covering the last `span`, plus the newest sample at
+/// or before the window start to difference against. Delta and rate metrics
+/// compare the two ends, so they smooth over `span` rather than one poll.
+/// When polls are further apart than `span`, the ends are simply the last
+/// two polls.
+pub struct SampleWindow<T> {
+    span: Duration,
+    samples: VecDeque<Sample<T>>,
+}
+
+impl<T> SampleWindow<T> {
+    pub fn new(span: Duration) -> Self {
+        Self { span, samples: VecDeque::new() }
+    }
+
+    pub fn push(&mut self, now: Instant, sample: T) {
+        self.samples.push_back((now, sample));
+        while self.samples.len() > 2 && now.duration_since(self.samples[1].0) >= self.span {
+            self.samples.pop_front();
+        }
+    }
+
+    /// The baseline and newest samples; the same sample after a single push.
+    pub fn ends(&self) -> Option<(&Sample<T>, &Sample<T>)> {
+        Some((self.samples.front()?, self.samples.back()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_keeps_one_sample_before_its_start() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut window = SampleWindow::new(Duration::from_secs(15));
+        assert!(window.ends().is_none());
+        for secs in [0, 10, 20, 30] {
+            window.push(at(secs), secs);
+        }
+        let (base, latest) = window.ends().unwrap();
+        assert_eq!((base.1, latest.1), (10, 30));
+
+        // Polls sparser than the window fall back to consecutive samples
+        let mut sparse = SampleWindow::new(Duration::from_secs(5));
+        for secs in [0, 10, 20] {
+            sparse.push(at(secs), secs);
+        }
+        let (base, latest) = sparse.ends().unwrap();
+        assert_eq!((base.1, latest.1), (10, 20));
+    }
+}
@@ -0,0 +1,688 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use ratatui::Terminal;
+use serde::Serialize;
+use unicode_width::UnicodeWidthStr;
+
+use crate::model::{
+    AppState, FocusedPanel, LatencyStat, ProxySnapshot, QueueSummary, SelectableItem, SessionGroup, ThroughputSnapshot,
+};
+use crate::ui::analysis::{collect_gpu_backends, compute_bottleneck, BottleneckReport};
+use crate::ui::format::{format_decimal, format_duration_ms, format_pct, Precision};
+use crate::ui::backends::{build_pool_views, PoolView};
+use crate::ui::theme::ColorScheme;
+use crate::ui::timefmt::format_now;
+
+/// Render the bottleneck analysis as a Markdown capacity-planning report.
+pub fn bottleneck_markdown(proxy: &ProxySnapshot, report: &BottleneckReport, proxy_url: &str, precision: Precision) -> String {
+    let now = format_now("%Y-%m-%d %H:%M:%S %Z");
+    let mut md = String::new();
+
+    md.push_str("# Bottleneck Analysis\n\n");
+    md.push_str(&format!("- **Generated:** {}\n", now));
+    md.push_str(&format!("- **Proxy:** {}\n\n", proxy_url));
+
+    md.push_str("## Diagnosis\n\n");
+    md.push_str(&format!("**{}** \u{2014} {}\n\n", report.diagnosis.label(), report.diagnosis.advice()));
+    md.push_str(&format!("- Active sessions: {}\n", report.active_sessions));
+    md.push_str(&format!("- GPUs: {} healthy / {} total\n", report.healthy_backends, report.total_backends));
+    md.push_str(&format!(
+        "- GPU load_avg={} util={}\n\n",
+        format_decimal(report.avg_gpu_load, 1, precision),
+        format_pct(report.gpu_util, 0, precision),
+    ));
+
+    md.push_str("## Pipeline\n\n");
+    match report.pipeline_summary(LatencyStat::Mean, precision) {
+        Some(p) => md.push_str(&format!("`{}`\n\n", p)),
+        None => md.push_str("No completed turns yet.\n\n"),
+    }
+    if let Some(b) = &report.breakdown {
+        md.push_str(&format!(
+            "Breakdown: {} {}%, agent {}%, proxy {}%\n\n",
+            b.infer_label, b.infer_pct, b.agent_pct, b.proxy_pct,
+        ));
+    }
+    if report.gap_outliers {
+        md.push_str(&format!(
+            "Agent gap: avg {}, trimmed {} \u{2014} a few long gaps skew the mean.\n\n",
+            format_duration_ms(report.timing.avg_gap_ms, precision),
+            format_duration_ms(report.trimmed_gap_ms, precision),
+        ));
+    }
+
+    if let Some(s) = &report.suggestion {
+        md.push_str("## Suggested Parallelism\n\n");
+        md.push_str(&format!(
+            "`--num-parallel {}` ({}/gpu \u{00d7} {} gpus)\n\n",
+            s.optimal, format_decimal(s.per_gpu, 1, precision), s.gpus,
+        ));
+        let mut scoped = report.scoped_suggestions().peekable();
+        let any_scoped = scoped.peek().is_some();
+        for (label, s) in scoped {
+            md.push_str(&format!(
+                "- {}: `--num-parallel {}` ({}/gpu \u{00d7} {} gpus)\n",
+                label, s.optimal, format_decimal(s.per_gpu, 1, precision), s.gpus,
+            ));
+        }
+        if any_scoped {
+            md.push('\n');
+        }
+    }
+
+    md.push_str("## Backends\n\n");
+    md.push_str("| Backend | Healthy | GPU Load | Inflight | Reqs | Avg Lat |\n");
+    md.push_str("|---|---|---:|---:|---:|---:|\n");
+    for b in collect_gpu_backends(proxy) {
+        let lat = if b.healthy && b.requests > 0 {
+            format_duration_ms(b.avg_latency_ms, precision)
+        } else {
+            "-".to_string()
+        };
+        md.push_str(&format!(
+            "| :{} | {} | {} | {} | {} | {} |\n",
+            b.port,
+            if b.healthy { "yes" } else { "no" },
+            if b.load_reported { b.gpu_load.to_string() } else { "-".to_string() },
+            b.inflight,
+            b.requests,
+            lat,
+        ));
+    }
+    if let Some(spread) = &report.spread {
+        md.push_str(&format!(
+            "\nSpread: {} latency diff, load {}-{} ({})\n",
+            format_pct(spread.latency_diff_pct, 0, precision), spread.load_min, spread.load_max, spread.label(),
+        ));
+    }
+
+    md
+}
+
+/// The bottleneck analysis as plain text for pasting into a ticket:
+/// diagnosis, pipeline breakdown and suggested parallelism.
+pub fn bottleneck_text(report: &BottleneckReport, idle_target_util: f64, precision: Precision) -> String {
+    let mut lines = vec![
+        format!("Bottleneck Analysis ({})", format_now("%Y-%m-%d %H:%M:%S %Z")),
+        format!("Diagnosis: {} \u{2014} {}", report.diagnosis.label(), report.diagnosis.advice()),
+        format!(
+            "Sessions: {} active | GPUs: {} healthy / {} total | load_avg={} util={}",
+            report.active_sessions,
+            report.healthy_backends,
+            report.total_backends,
+            format_decimal(report.avg_gpu_load, 1, precision),
+            format_pct(report.gpu_util, 0, precision),
+        ),
+        format!(
+            "Pipeline: {}",
+            report.pipeline_summary(LatencyStat::Mean, precision).unwrap_or_else(|| "no data".to_string())
+        ),
+    ];
+    if let Some(b) = &report.breakdown {
+        lines.push(format!(
+            "Breakdown: {} {}%, agent {}%, proxy {}%",
+            b.infer_label, b.infer_pct, b.agent_pct, b.proxy_pct,
+        ));
+    }
+    if report.gap_outliers {
+        lines.push(format!(
+            "Agent gap: avg {}, trimmed {} (outliers skew the mean)",
+            format_duration_ms(report.timing.avg_gap_ms, precision),
+            format_duration_ms(report.trimmed_gap_ms, precision),
+        ));
+    }
+    if let Some(idle) = report.idle_suggestion(idle_target_util) {
+        lines.push(format!(
+            "Headroom: --num-parallel {} ({} running at {} util \u{2192} {} target)",
+            idle.optimal,
+            idle.running,
+            format_pct(idle.util_pct, 0, precision),
+            format_pct(idle.target_pct, 0, precision),
+        ));
+    }
+    if let Some(s) = &report.suggestion {
+        lines.push(format!(
+            "Suggested: --num-parallel {} ({}/gpu \u{00d7} {} gpus)",
+            s.optimal,
+            format_decimal(s.per_gpu, 1, precision),
+            s.gpus,
+        ));
+    }
+    for (label, s) in report.scoped_suggestions() {
+        lines.push(format!(
+            "  {}: --num-parallel {} ({}/gpu \u{00d7} {} gpus)",
+            label, s.optimal, format_decimal(s.per_gpu, 1, precision), s.gpus,
+        ));
+    }
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+/// Write a timestamped bottleneck report into `dir`, returning its path.
+pub fn write_bottleneck_report(
+    dir: &Path,
+    proxy: &ProxySnapshot,
+    proxy_url: &str,
+    gap_trim: f64,
+    precision: Precision,
+) -> io::Result<PathBuf> {
+    let report = compute_bottleneck(proxy, gap_trim);
+    let name = format!("bottleneck-{}.md", format_now("%Y%m%d-%H%M%S"));
+    let path = dir.join(name);
+    fs::write(&path, bottleneck_markdown(proxy, &report, proxy_url, precision))?;
+    Ok(path)
+}
+
+/// Aggregate metrics served by `--serve`.
+#[derive(Serialize, Debug)]
+pub struct MetricsSummary {
+    pub connected: bool,
+    pub diagnosis: &'static str,
+    pub healthy_backends: u64,
+    pub total_backends: u64,
+    pub pending: u64,
+    pub in_flight: u64,
+    pub completed_last_minute: u64,
+    pub requests_per_minute: f64,
+    pub error_rate: f64,
+    /// Artifact completions per minute; absent without `--artifacts`.
+    pub completions_per_minute: Option<f64>,
+    /// Proxy share of turn time; absent without backend timing.
+    pub proxy_overhead_ratio: Option<f64>,
+}
+
+impl MetricsSummary {
+    pub fn new(proxy: &ProxySnapshot, throughput: &ThroughputSnapshot, gap_trim: f64) -> Self {
+        let report = compute_bottleneck(proxy, gap_trim);
+        let summary = &proxy.queue.summary;
+        Self {
+            connected: proxy.connected.is_up(),
+            diagnosis: report.diagnosis.label(),
+            healthy_backends: report.healthy_backends,
+            total_backends: report.total_backends,
+            pending: summary.pending,
+            in_flight: summary.in_flight,
+            completed_last_minute: summary.completed_last_minute,
+            requests_per_minute: proxy.stats.requests_per_minute,
+            error_rate: proxy.stats.error_rate,
+            completions_per_minute: throughput.enabled.then_some(throughput.rate_per_min),
+            proxy_overhead_ratio: report.timing.proxy_overhead(),
+        }
+    }
+
+    /// Prometheus text exposition format.
+    pub fn prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut gauge = |name: &str, help: &str, value: String| {
+            out.push_str(&format!("# HELP dashboard_{name} {help}\n# TYPE dashboard_{name} gauge\n"));
+            out.push_str(&format!("dashboard_{name} {value}\n"));
+        };
+        gauge("proxy_connected", "Whether the last proxy poll succeeded.", (self.connected as u8).to_string());
+        gauge("healthy_backends", "Healthy GPU backends.", self.healthy_backends.to_string());
+        gauge("total_backends", "Registered GPU backends.", self.total_backends.to_string());
+        gauge("pending_requests", "Requests waiting for a backend.", self.pending.to_string());
+        gauge("in_flight_requests", "Requests being served.", self.in_flight.to_string());
+        gauge("completed_last_minute", "Requests completed in the last minute.", self.completed_last_minute.to_string());
+        gauge("requests_per_minute", "Proxy request rate.", self.requests_per_minute.to_string());
+        gauge("error_rate_percent", "Proxy error rate.", self.error_rate.to_string());
+        if let Some(rate) = self.completions_per_minute {
+            gauge("completions_per_minute", "Artifact completions per minute.", rate.to_string());
+        }
+        if let Some(ratio) = self.proxy_overhead_ratio {
+            gauge("proxy_overhead_ratio", "Share of turn time spent in the proxy.", ratio.to_string());
+        }
+        out.push_str("# HELP dashboard_diagnosis Current bottleneck diagnosis (1 for the active label).\n");
+        out.push_str("# TYPE dashboard_diagnosis gauge\n");
+        out.push_str(&format!("dashboard_diagnosis{{label=\"{}\"}} 1\n", self.diagnosis));
+        out
+    }
+}
+
+/// The aggregated dashboard state printed by `--once --json`.
+#[derive(Serialize)]
+pub struct DashboardState {
+    pub proxy: String,
+    pub generated: String,
+    pub connected: bool,
+    pub diagnosis: &'static str,
+    pub advice: &'static str,
+    pub queue: QueueSummary,
+    pub pools: Vec<PoolView>,
+    /// Absent until a turn has completed.
+    pub timing: Option<TimingSummary>,
+    /// Absent without `--artifacts`.
+    pub throughput: Option<ThroughputSummary>,
+}
+
+/// Pipeline timing means and p95s, in milliseconds.
+#[derive(Serialize)]
+pub struct TimingSummary {
+    pub turns: u64,
+    pub avg_inference_ms: f64,
+    pub avg_wait_ms: f64,
+    pub avg_proxy_ms: f64,
+    pub avg_total_ms: f64,
+    pub avg_gap_ms: f64,
+    pub p95_inference_ms: f64,
+    pub p95_wait_ms: f64,
+    pub p95_proxy_ms: f64,
+    pub p95_total_ms: f64,
+    pub has_backend_time: bool,
+}
+
+#[derive(Serialize)]
+pub struct ThroughputSummary {
+    pub total: usize,
+    pub success: usize,
+    pub failure: usize,
+    pub rate_per_min: f64,
+    pub avg_run_secs: Option<f64>,
+}
+
+impl DashboardState {
+    pub fn new(proxy: &ProxySnapshot, throughput: &ThroughputSnapshot, proxy_url: &str, gap_trim: f64) -> Self {
+        let report = compute_bottleneck(proxy, gap_trim);
+        let t = &report.timing;
+        Self {
+            proxy: proxy_url.to_string(),
+            generated: format_now("%Y-%m-%dT%H:%M:%S%:z"),
+            connected: proxy.connected.is_up(),
+            diagnosis: report.diagnosis.label(),
+            advice: report.diagnosis.advice(),
+            queue: proxy.queue.summary.clone(),
+            pools: build_pool_views(proxy),
+            timing: (t.count > 0).then_some(TimingSummary {
+                turns: t.count,
+                avg_inference_ms: t.avg_inference_ms,
+                avg_wait_ms: t.avg_wait_ms,
+                avg_proxy_ms: t.avg_proxy_ms,
+                avg_total_ms: t.avg_total_ms,
+                avg_gap_ms: t.avg_gap_ms,
+                p95_inference_ms: t.p95_inference_ms,
+                p95_wait_ms: t.p95_wait_ms,
+                p95_proxy_ms: t.p95_proxy_ms,
+                p95_total_ms: t.p95_total_ms,
+                has_backend_time: t.has_backend_time,
+            }),
+            throughput: throughput.enabled.then_some(ThroughputSummary {
+                total: throughput.total,
+                success: throughput.success,
+                failure: throughput.failure,
+                rate_per_min: throughput.rate_per_min,
+                avg_run_secs: throughput.avg_run_secs,
+            }),
+        }
+    }
+}
+
+/// Render the full dashboard into an off-screen `width` x `height` buffer,
+/// free of cursor artifacts or half-drawn rows.
+pub fn render_capture(
+    proxy: &ProxySnapshot,
+    throughput: &ThroughputSnapshot,
+    proxy_url: &str,
+    state: &mut AppState,
+    colors: &ColorScheme,
+    (width, height): (u16, u16),
+) -> io::Result<Buffer> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    let scoped = state.scoped(proxy);
+    terminal.draw(|frame| crate::ui::draw(frame, proxy, &scoped, throughput, proxy_url, state, colors))?;
+    Ok(terminal.backend().buffer().clone())
+}
+
+/// A rendered buffer as lines of text, with SGR color escapes if `ansi`.
+/// Cells hidden behind wide characters are skipped so columns line up.
+pub fn capture_text(buffer: &Buffer, ansi: bool) -> String {
+    let mut out = String::new();
+    for row in buffer.content.chunks(buffer.area.width as usize) {
+        let mut line = String::new();
+        let mut style = None;
+        let mut skip = 0usize;
+        for cell in row {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            if ansi && style != Some((cell.fg, cell.bg, cell.modifier)) {
+                style = Some((cell.fg, cell.bg, cell.modifier));
+                line.push_str(&sgr(cell.fg, cell.bg, cell.modifier));
+            }
+            line.push_str(cell.symbol());
+            skip = cell.symbol().width().saturating_sub(1);
+        }
+        if ansi {
+            line.push_str("\x1b[0m");
+        } else {
+            line.truncate(line.trim_end().len());
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Escape sequence that resets, then applies one cell's colors and modifiers.
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (flag, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ] {
+        if modifier.contains(flag) {
+            codes.push(code.to_string());
+        }
+    }
+    codes.extend(color_code(fg, false));
+    codes.extend(color_code(bg, true));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let base = if background { 40 } else { 30 };
+    let ext = if background { 48 } else { 38 };
+    let named = |offset: u8| Some((base + offset as u16).to_string());
+    let bright = |offset: u8| Some((base + 60 + offset as u16).to_string());
+    match color {
+        Color::Reset => None,
+        Color::Black => named(0),
+        Color::Red => named(1),
+        Color::Green => named(2),
+        Color::Yellow => named(3),
+        Color::Blue => named(4),
+        Color::Magenta => named(5),
+        Color::Cyan => named(6),
+        Color::Gray => named(7),
+        Color::DarkGray => bright(0),
+        Color::LightRed => bright(1),
+        Color::LightGreen => bright(2),
+        Color::LightYellow => bright(3),
+        Color::LightBlue => bright(4),
+        Color::LightMagenta => bright(5),
+        Color::LightCyan => bright(6),
+        Color::White => bright(7),
+        Color::Indexed(i) => Some(format!("{};5;{}", ext, i)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{};{};{}", ext, r, g, b)),
+    }
+}
+
+/// The focused panel's visible rows as tab-separated text, honoring the
+/// current expand/collapse state. `scoped` is `proxy` under `--partition`.
+pub fn panel_tsv(proxy: &ProxySnapshot, scoped: &ProxySnapshot, state: &AppState) -> String {
+    match state.focused_panel {
+        FocusedPanel::Backends => backends_tsv(scoped, state),
+        FocusedPanel::Sessions => sessions_tsv(proxy, state),
+    }
+}
+
+fn backends_tsv(proxy: &ProxySnapshot, state: &AppState) -> String {
+    let mut rows = vec![tsv_row(&[
+        "model/backend", "healthy", "partition", "gpu_load", "inflight", "requests", "errors", "avg_latency_ms",
+        "recent_latency_ms",
+    ])];
+    for pool in build_pool_views(proxy) {
+        rows.push(tsv_row(&[
+            &pool.model,
+            &format!("{}/{}", pool.healthy, pool.total),
+            "",
+            &pool.gpu_load.to_string(),
+            &pool.inflight.to_string(),
+            &pool.requests.to_string(),
+            &pool.errors.to_string(),
+            "",
+            "",
+        ]));
+        if state.backend_expanded.contains(&pool.model) {
+            // `--top-backends` rows as the panel shows them, with its "more" row
+            let (shown, hidden) = pool.visible_backends(state.top_backends);
+            for b in shown {
+                rows.push(tsv_row(&[
+                    &b.url,
+                    if b.healthy { "yes" } else { "no" },
+                    &b.partition,
+                    &b.gpu_load.map_or("-".to_string(), |v| v.to_string()),
+                    &b.inflight.to_string(),
+                    &b.requests.to_string(),
+                    &b.errors.to_string(),
+                    &format!("{:.0}", b.avg_latency_ms),
+                    &b.recent_latency_ms.map_or("-".to_string(), |ms| format!("{:.0}", ms)),
+                ]));
+            }
+            if hidden > 0 {
+                rows.push(tsv_row(&[&format!("(\u{2026} {} more)", hidden), "", "", "", "", "", "", "", ""]));
+            }
+        }
+    }
+    rows.join("\n") + "\n"
+}
+
+fn sessions_tsv(proxy: &ProxySnapshot, state: &AppState) -> String {
+    let queue = &proxy.queue;
+    let mut rows = vec![tsv_row(&[
+        "kind", "id", "detail", "completed/total", "in_flight", "pending", "failed",
+    ])];
+    for item in state.build_selectable_items(queue) {
+        match item {
+            SelectableItem::Client(cid) => {
+                if let Some(client) = queue.clients.iter().find(|c| c.client_id == cid) {
+                    let sum = |f: fn(&SessionGroup) -> u64| -> String {
+                        client.sessions.iter().map(f).sum::<u64>().to_string()
+                    };
+                    rows.push(tsv_row(&[
+                        "client",
+                        &client.client_id,
+                        &client.client_command,
+                        &format!("{} sessions", client.sessions.len()),
+                        &sum(|s| s.in_flight_requests),
+                        &sum(|s| s.pending_requests),
+                        &sum(|s| s.failed_requests),
+                    ]));
+                }
+            }
+            SelectableItem::Session(sid) => {
+                if let Some(sess) = queue.find_session(&sid) {
+                    let total = if sess.total_turns > 0 { sess.total_turns } else { sess.total_requests };
+                    rows.push(tsv_row(&[
+                        "session",
+                        &sess.session_id,
+                        &sess.task_id,
+                        &format!("{}/{}", sess.turns_done(), total),
+                        &sess.in_flight_requests.to_string(),
+                        &sess.pending_requests.to_string(),
+                        &sess.failed_requests.to_string(),
+                    ]));
+                }
+            }
+            SelectableItem::Turn(sid, idx) => {
+                if let Some(turn) = queue.find_session(&sid).and_then(|s| s.completed_turns.get(idx)) {
+                    rows.push(tsv_row(&[
+                        "turn",
+                        &format!("T{}", turn.turn_number.unwrap_or(idx as u64 + 1)),
+                        turn.backend.as_deref().unwrap_or("-"),
+                        &format_duration_ms(turn.total_time_ms, state.precision),
+                        "",
+                        "",
+                        "",
+                    ]));
+                }
+            }
+        }
+    }
+    rows.join("\n") + "\n"
+}
+
+/// Join fields with tabs, flattening any embedded tabs/newlines.
+fn tsv_row(fields: &[&str]) -> String {
+    fields
+        .iter()
+        .map(|f| f.replace(['\t', '\n'], " "))
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_support::snapshot;
+
+    #[test]
+    fn capture_renders_dashboard_as_plain_or_ansi_text() {
+        let proxy = snapshot(
+            json!({"pools": [{"model": "org/model-a", "backends": [{"url": "http://gpu:8001", "healthy": true}]}]}),
+            json!({}),
+        );
+        let mut state = AppState::default();
+        let buffer = render_capture(
+            &proxy,
+            &ThroughputSnapshot::default(),
+            "http://proxy:5800",
+            &mut state,
+            &ColorScheme::dark(),
+            (120, 40),
+        )
+        .unwrap();
+
+        let text = capture_text(&buffer, false);
+        assert_eq!(text.lines().count(), 40);
+        assert!(text.lines().all(|l| l.width() <= 120 && l == l.trim_end()));
+        assert!(text.contains("org/model-a"));
+        assert!(!text.contains('\x1b'));
+
+        let ansi = capture_text(&buffer, true);
+        assert!(ansi.contains("\x1b[0;1"));
+        assert!(ansi.lines().all(|l| l.ends_with("\x1b[0m")));
+    }
+
+    #[test]
+    fn sgr_maps_named_and_rgb_colors() {
+        assert_eq!(sgr(Color::Red, Color::Reset, Modifier::BOLD), "\x1b[0;1;31m");
+        assert_eq!(sgr(Color::Rgb(1, 2, 3), Color::DarkGray, Modifier::empty()), "\x1b[0;38;2;1;2;3;100m");
+    }
+
+    #[test]
+    fn bottleneck_text_covers_diagnosis_pipeline_and_suggestion() {
+        let proxy = snapshot(
+            json!({}),
+            json!({
+                "backends": [{"url": "http://gpu:8001", "healthy": true, "gpu_load": 1}],
+                "sessions": [{
+                    "session_id": "s1",
+                    "completed_turns": [
+                        {"submitted_at": 100.0, "completed_at": 101.0, "processing_time_ms": 1000.0},
+                        {"submitted_at": 102.0, "completed_at": 103.0, "processing_time_ms": 1000.0}
+                    ]
+                }]
+            }),
+        );
+        let report = compute_bottleneck(&proxy, 0.0);
+        let text = bottleneck_text(&report, 80.0, Precision::default());
+        assert!(text.contains("Diagnosis: AGENT-BOUND"));
+        assert!(text.contains("Pipeline: agent=1.0s  proc=1.0s"));
+        assert!(text.contains("Breakdown: proc 50%, agent 50%, proxy 0%"));
+        assert!(text.contains("Suggested: --num-parallel 2 (2.0/gpu \u{00d7} 1 gpus)"));
+        let precise = bottleneck_text(&report, 80.0, Precision::new(Some(2)));
+        assert!(precise.contains("(2.00/gpu \u{00d7} 1 gpus)"));
+    }
+
+    #[test]
+    fn backends_tsv_includes_expanded_backends_only() {
+        let proxy = snapshot(
+            json!({"pools": [
+                {"model": "m1", "backends": [{"url": "http://gpu:8001", "healthy": true, "requests": 3}]},
+                {"model": "m2", "backends": [{"url": "http://gpu:8002", "healthy": true}]}
+            ]}),
+            json!({}),
+        );
+        let mut state = AppState::default();
+        state.backend_expanded.insert("m1".to_string());
+
+        let text = panel_tsv(&proxy, &proxy, &state);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("m1\t1/1"));
+        assert!(lines[2].starts_with("http://gpu:8001\tyes"));
+        assert!(lines[3].starts_with("m2\t"));
+    }
+
+    #[test]
+    fn backends_tsv_follows_top_backends() {
+        let proxy = snapshot(
+            json!({"pools": [{"model": "m1", "backends": [
+                {"url": "http://gpu:8001", "healthy": true},
+                {"url": "http://gpu:8002", "healthy": true},
+                {"url": "http://gpu:8003", "healthy": true}
+            ]}]}),
+            json!({"backends": [
+                {"url": "http://gpu:8001", "gpu_load": 1},
+                {"url": "http://gpu:8002", "gpu_load": 5},
+                {"url": "http://gpu:8003", "gpu_load": 3}
+            ]}),
+        );
+        let mut state = AppState { top_backends: Some(2), ..AppState::default() };
+        state.backend_expanded.insert("m1".to_string());
+
+        let text = panel_tsv(&proxy, &proxy, &state);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[2].starts_with("http://gpu:8002\t"));
+        assert!(lines[3].starts_with("http://gpu:8003\t"));
+        assert!(lines[4].starts_with("(\u{2026} 1 more)\t"));
+    }
+
+    #[test]
+    fn metrics_prometheus_exposes_gauges_and_diagnosis() {
+        let proxy = snapshot(
+            json!({"requests_per_minute": 12.5, "error_rate": 2.0}),
+            json!({"summary": {"pending": 3}, "backends": [{"url": "http://gpu:8001", "healthy": true}]}),
+        );
+        let metrics = MetricsSummary::new(&proxy, &ThroughputSnapshot::default(), 0.0);
+        let text = metrics.prometheus();
+        assert!(text.contains("dashboard_pending_requests 3\n"));
+        assert!(text.contains("dashboard_requests_per_minute 12.5\n"));
+        assert!(text.contains("dashboard_healthy_backends 1\n"));
+        assert!(text.contains(&format!("dashboard_diagnosis{{label=\"{}\"}} 1", metrics.diagnosis)));
+        assert!(!text.contains("completions_per_minute"));
+        assert_eq!(serde_json::to_value(&metrics).unwrap()["pending"], 3);
+    }
+
+    #[test]
+    fn dashboard_state_serializes_pools_queue_and_timing() {
+        let proxy = snapshot(
+            json!({"pools": [{"model": "m1", "backends": [{"url": "http://gpu:8001", "healthy": true, "requests": 3}]}]}),
+            json!({
+                "summary": {"pending": 2, "in_flight": 1},
+                "backends": [{"url": "http://gpu:8001", "healthy": true, "gpu_load": 1}],
+                "sessions": [{
+                    "session_id": "s1",
+                    "completed_turns": [{"submitted_at": 100.0, "completed_at": 101.0, "processing_time_ms": 1000.0}]
+                }]
+            }),
+        );
+        let state = DashboardState::new(&proxy, &ThroughputSnapshot::default(), "http://proxy:5800", 0.0);
+        let value = serde_json::to_value(&state).unwrap();
+        assert_eq!(value["proxy"], "http://proxy:5800");
+        assert_eq!(value["queue"]["pending"], 2);
+        assert_eq!(value["pools"][0]["model"], "m1");
+        assert_eq!(value["pools"][0]["backends"][0]["gpu_load"], 1);
+        assert_eq!(value["timing"]["turns"], 1);
+        assert_eq!(value["timing"]["avg_inference_ms"], 1000.0);
+        assert!(value["throughput"].is_null());
+    }
+
+    #[test]
+    fn tsv_fields_are_flattened() {
+        assert_eq!(tsv_row(&["a\tb", "c\nd"]), "a b\tc d");
+    }
+}
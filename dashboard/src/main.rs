@@ -1,21 +1,43 @@
+mod clipboard;
 mod collector;
+mod credentials;
+mod export;
 mod model;
+mod serve;
+mod syslog;
+#[cfg(test)]
+mod test_support;
 mod ui;
 
-use std::io;
+use std::collections::VecDeque;
+use std::io::{self, Write};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
-use clap::Parser;
-use crossterm::event::{self, Event, KeyCode};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use collector::proxy::{PollConfig, PollSinks, ProxyAuth, ProxySource};
+use collector::replay::{Recorder, Replay, ReplayControl};
+use collector::shared::SharedWriter;
+use collector::throughput::RunStartSource;
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::execute;
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 
-use model::{AppState, FocusedPanel, ProxySnapshot, SelectableItem, ThroughputSnapshot};
-use ui::theme::ColorScheme;
+use model::{AppState, FocusedPanel, HealthWeights, IdleWatch, LayoutMode, Pricing, ProxySnapshot, SelectableCache, SelectableItem, SessionSort, SortDir, ThroughputSnapshot, TimestampFormat, Toast, TruncationLimits};
+use ui::format::{Precision, UptimeStyle};
+use ui::theme::{ColorScheme, SelectionStyle};
+
+/// How long a backend health toast stays on screen.
+const TOAST_TTL: Duration = Duration::from_secs(5);
+const DEFAULT_PROXY_URL: &str = "http://localhost:5800";
+
+/// How long `--capture` and `--once` wait for the first proxy poll before
+/// reporting anyway.
+const FIRST_POLL_WAIT: Duration = Duration::from_secs(10);
 
 #[derive(Clone, Copy, Debug, clap::ValueEnum)]
 enum ThemeChoice {
@@ -23,39 +45,437 @@ enum ThemeChoice {
     Light,
 }
 
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum SelectionChoice {
+    Marker,
+    Reverse,
+    Background,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum LayoutChoice {
+    Standard,
+    Wall,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum SessionSortChoice {
+    Proxy,
+    Recent,
+    Turns,
+    Status,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum TimestampChoice {
+    Clock,
+    Iso,
+    Epoch,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum UptimeChoice {
+    Clock,
+    Human,
+    Iso,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum RunStartChoice {
+    Fields,
+    Marker,
+    Off,
+}
+
 #[derive(Parser)]
-#[command(name = "dashboard", about = "Real-time TUI monitoring dashboard for Agent Infra")]
+#[command(
+    name = "dashboard",
+    version,
+    about = "Real-time TUI monitoring dashboard for Agent Infra",
+    after_help = credentials::PRECEDENCE_HELP
+)]
 struct Cli {
-    /// Proxy server URL
-    #[arg(long, default_value = "http://localhost:5800")]
-    proxy: String,
+    /// Proxy server URL [default: http://localhost:5800]; see below for
+    /// reading it from the environment or a systemd credential
+    #[arg(long)]
+    proxy: Option<String>,
+
+    /// Bearer token sent to the proxy; prefer a credential or the
+    /// environment so it stays out of the process list
+    #[arg(long)]
+    proxy_token: Option<String>,
 
-    /// Artifact directory for throughput tracking
+    /// PEM CA certificate to trust for an https proxy
     #[arg(long)]
-    artifacts: Option<PathBuf>,
+    proxy_ca_cert: Option<PathBuf>,
+
+    /// Read `stats.json` and `queue_status.json` from this directory instead
+    /// of polling the proxy; files are re-read every poll
+    #[arg(long, conflicts_with = "test_connection")]
+    fixtures: Option<PathBuf>,
+
+    /// Display polls another dashboard publishes instead of polling the
+    /// proxy; `mmap:<path>` (or `file:<path>`) names the file it writes
+    /// with --share-snapshot
+    #[arg(long, value_parser = parse_source, conflicts_with_all = ["test_connection", "fixtures", "share_snapshot"])]
+    source: Option<PathBuf>,
+
+    /// Publish every poll to this file (e.g. under /dev/shm) for other
+    /// dashboards on the host to display with `--source mmap:<path>`
+    #[arg(long)]
+    share_snapshot: Option<PathBuf>,
+
+    /// Append every poll to this file, one JSON object per line, to play
+    /// back later with --replay
+    #[arg(long, conflicts_with = "test_connection")]
+    record: Option<PathBuf>,
+
+    /// Play back a --record file instead of polling the proxy. Space
+    /// pauses, +/- change the speed and `.` steps one frame while paused;
+    /// the last frame is held
+    #[arg(long, conflicts_with_all = ["test_connection", "fixtures", "source", "share_snapshot", "record"])]
+    replay: Option<PathBuf>,
+
+    /// Starting playback speed for --replay (2 plays twice as fast),
+    /// from 0.0625 to 64
+    #[arg(long, default_value_t = 1.0, value_parser = parse_speed, requires = "replay")]
+    replay_speed: f64,
 
-    /// Screen refresh interval in seconds
+    /// Artifact directory for throughput tracking (repeatable or comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    artifacts: Vec<PathBuf>,
+
+    /// Screen refresh interval in seconds; the proxy is never polled more
+    /// than once per 250ms, even at 0
     #[arg(long, default_value_t = 2)]
     interval: u64,
 
-    /// Throughput sliding window in seconds
+    /// Back off the proxy poll interval while nothing is happening
+    #[arg(long)]
+    adaptive: bool,
+
+    /// Upper bound in seconds for the adaptive poll interval
+    #[arg(long, default_value_t = 30)]
+    max_interval: u64,
+
+    /// Testing only: lower the floor between proxy polls (normally 250ms)
+    #[arg(long, hide = true)]
+    min_poll_interval_ms: Option<u64>,
+
+    /// Where run start times come from: artifact JSON fields, a marker file's mtime, or off
+    #[arg(long, default_value = "fields", value_enum)]
+    run_start: RunStartChoice,
+
+    /// Marker file name used with `--run-start marker`
+    #[arg(long, default_value = "start")]
+    run_start_marker: String,
+
+    /// Throughput sliding window in seconds (artifacts and per-client turn rate)
     #[arg(long, default_value_t = 300)]
     window: u64,
 
+    /// Window in seconds that delta and rate metrics (recent per-backend
+    /// latency, turns/min) are smoothed over. The collector keeps about
+    /// window / --interval polls; a window at or below --interval falls
+    /// back to comparing consecutive polls. 0 hides recent latency, as
+    /// `--latency-window 0` did. turns/min used a fixed 300s window before
+    /// this flag; pass 300 to keep that
+    #[arg(long, alias = "latency-window", default_value_t = 60)]
+    metric_window_secs: u64,
+
     /// Number of recent completions to display
     #[arg(long, default_value_t = 10)]
     recent: usize,
 
+    /// Highlight completions newer than this many seconds (0 disables)
+    #[arg(long, default_value_t = 30)]
+    recent_highlight_secs: u64,
+
+    /// A client with no queued work counts as idle only after this many
+    /// seconds without a completed turn (0 = as soon as its queue drains)
+    #[arg(long, default_value_t = 60)]
+    idle_quiet_secs: u64,
+
+    /// Turn/completion timestamp style (toggle at runtime with `t`)
+    #[arg(long, default_value = "clock", value_enum)]
+    timestamps: TimestampChoice,
+
+    /// Cap on client command length (default: fit the column)
+    #[arg(long)]
+    max_command_len: Option<usize>,
+
+    /// Cap on session task ID length (default: fit the column)
+    #[arg(long)]
+    max_task_len: Option<usize>,
+
+    /// Cap on turn response snippet length (default: fit the column)
+    #[arg(long)]
+    max_response_len: Option<usize>,
+
     /// Color theme
     #[arg(long, default_value = "dark", value_enum)]
     theme: ThemeChoice,
+
+    /// How the selected row is marked (`marker` adds a left-edge bar for
+    /// terminals where the selection background is hard to see)
+    #[arg(long, default_value = "marker", value_enum)]
+    selection: SelectionChoice,
+
+    /// Screen layout (`wall` is a glanceable grid for large displays)
+    #[arg(long, default_value = "standard", value_enum)]
+    layout: LayoutChoice,
+
+    /// Order of sessions under each client: as the proxy lists them, most
+    /// recent activity, most turns, or failed/active first
+    #[arg(long, default_value = "proxy", value_enum)]
+    sort_sessions: SessionSortChoice,
+
+    /// Error rate percent at which a model is highlighted in the errors panel
+    #[arg(long, default_value_t = 5.0)]
+    model_error_threshold: f64,
+
+    /// Warn when more than this fraction of active requests have no session_id
+    #[arg(long, default_value_t = 0.5)]
+    ungrouped_warn_ratio: f64,
+
+    /// Serve aggregate metrics at this address (e.g. 127.0.0.1:9184) as
+    /// Prometheus text on /metrics and JSON on /metrics.json
+    #[arg(long)]
+    serve: Option<String>,
+
+    /// Only show this session (and its client), expanded
+    #[arg(long)]
+    session: Option<String>,
+
+    /// With --session, quit once the session finishes (exit 1 if any of
+    /// its requests failed, or if it disappears from the proxy)
+    #[arg(long, requires = "session")]
+    exit_on_complete: bool,
+
+    /// Quit once the proxy has had nothing pending or in flight and no new
+    /// completions for this many seconds (exit 1 if any request failed)
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    exit_when_idle_secs: Option<u64>,
+
+    /// With --session, exit with an error if the session hasn't appeared
+    /// after this many seconds (0 waits forever)
+    #[arg(long, default_value_t = 300)]
+    session_timeout: u64,
+
+    /// Flag in-flight requests running longer than this many seconds
+    #[arg(long, default_value_t = 60)]
+    slow_request_secs: u64,
+
+    /// List raw sessions without client grouping, even when the proxy
+    /// reports clients (for debugging suspect grouping)
+    #[arg(long)]
+    flat_sessions: bool,
+
+    /// Fraction of the longest agent gaps to ignore when diagnosing
+    /// AGENT-BOUND, so one stuck turn doesn't dominate (0 uses the plain mean)
+    #[arg(long, default_value_t = 0.05)]
+    gap_trim: f64,
+
+    /// GPU utilization percent to aim for when suggesting a higher
+    /// --num-parallel for an IDLE fleet
+    #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u8).range(1..=100))]
+    idle_target_util: u8,
+
+    /// Concurrent requests each healthy backend can serve, for the
+    /// per-model capacity gauge
+    #[arg(long, default_value_t = 8)]
+    capacity_per_backend: u64,
+
+    /// Show each backend as one dense line instead of the table's columns;
+    /// this happens automatically when the backends panel is under 100 columns
+    #[arg(long)]
+    narrow: bool,
+
+    /// Add a 0-100 health score column to the backends panel, combining the
+    /// health flag, error rate, latency vs the fleet median and headroom
+    #[arg(long)]
+    health_score: bool,
+
+    /// Weights of the health score signals, as HEALTHY,ERRORS,LATENCY,HEADROOM
+    #[arg(long, default_value = "40,25,20,15", requires = "health_score")]
+    health_weights: HealthWeights,
+
+    /// In each expanded pool, list only the N backends with the highest
+    /// gpu_load / inflight; pool totals still cover every backend
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    top_backends: Option<u64>,
+
+    /// Expect sticky routing: highlight sessions whose turns were served
+    /// by more than one backend
+    #[arg(long)]
+    expect_affinity: bool,
+
+    /// Smallest burst of near-simultaneous pending requests reported as a
+    /// thundering herd; lower is more sensitive (0 disables)
+    #[arg(long, default_value_t = 8)]
+    herd_min_burst: usize,
+
+    /// Decimals shown for durations, latencies and percentages (0-3);
+    /// 0 shows latencies in whole milliseconds. Defaults vary per metric
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=3))]
+    precision: Option<u8>,
+
+    /// Show counts as plain digits (1234567) instead of with thousands
+    /// separators (1,234,567)
+    #[arg(long)]
+    raw_counts: bool,
+
+    /// Show every wall-clock time (header clock, turn timestamps, report
+    /// and file names) in UTC instead of the local timezone
+    #[arg(long)]
+    utc: bool,
+
+    /// How the proxy uptime is shown: `clock` (250:13:07), `human`
+    /// (10d 10h 13m) or an ISO 8601 duration (P10DT10H13M7S)
+    #[arg(long, default_value = "human", value_enum)]
+    uptime_style: UptimeChoice,
+
+    /// Only count backends in this SLURM partition in the header, the
+    /// backends, GPU performance and bottleneck panels, and `--serve` metrics
+    #[arg(long)]
+    partition: Option<String>,
+
+    /// Log proxy connect/disconnect, backend health flips, backends joining
+    /// or leaving a pool, diagnosis changes and completion stalls to the
+    /// system log (syslog or journald via /dev/log)
+    #[arg(long)]
+    log_syslog: bool,
+
+    /// Expand sessions (and their clients) as they record failures, so the
+    /// failing turns show without expanding by hand; toggle with `e`
+    #[arg(long)]
+    expand_failed: bool,
+
+    /// Also toast when a backend joins or leaves a model pool
+    #[arg(long)]
+    fleet_toasts: bool,
+
+    /// Alert when the artifacts scan sees no new completion for this many
+    /// seconds while the proxy has requests in flight
+    #[arg(long, requires = "artifacts", value_parser = clap::value_parser!(u64).range(1..))]
+    stall_alert_secs: Option<u64>,
+
+    /// Ring the terminal bell when a completion stall starts
+    #[arg(long, requires = "stall_alert_secs")]
+    stall_bell: bool,
+
+    /// Minimum seconds between terminal bells, so a flapping alert can't
+    /// beep continuously
+    #[arg(long, default_value_t = 30, requires = "stall_bell")]
+    bell_interval_secs: u64,
+
+    /// Price per 1k prompt tokens, for session cost estimates
+    #[arg(long)]
+    price_prompt: Option<f64>,
+
+    /// Price per 1k completion tokens, for session cost estimates
+    #[arg(long)]
+    price_completion: Option<f64>,
+
+    /// Query the proxy endpoints once, print diagnostics, and exit
+    #[arg(long)]
+    test_connection: bool,
+
+    /// Upper bound on redraws per second; frames are also skipped entirely
+    /// when nothing on screen has changed
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..=60))]
+    max_fps: u32,
+
+    /// Ask "Quit? (y/n)" on q/Esc instead of exiting immediately
+    #[arg(long)]
+    confirm_quit: bool,
+
+    /// Number of past proxy snapshots kept in memory for scrubbing with [ and ]
+    #[arg(long, default_value_t = 150)]
+    history: usize,
+
+    /// Directory where `m` writes Markdown bottleneck reports
+    #[arg(long, default_value = ".")]
+    report_dir: PathBuf,
+
+    /// Render one frame of the dashboard off-screen to this file once the
+    /// first poll lands, then exit (for docs and bug reports)
+    #[arg(long, conflicts_with = "test_connection")]
+    capture: Option<PathBuf>,
+
+    /// Size of the `--capture` render, as WIDTHxHEIGHT
+    #[arg(long, default_value = "160x50", value_parser = parse_size, requires = "capture")]
+    capture_size: (u16, u16),
+
+    /// Keep colors in the `--capture` file as ANSI escapes (view with `cat`
+    /// or `less -R`) instead of plain text
+    #[arg(long, requires = "capture")]
+    capture_ansi: bool,
+
+    /// Print the bottleneck analysis once the first poll lands, then exit
+    /// (exit 1 if the proxy is unreachable)
+    #[arg(long, conflicts_with_all = ["test_connection", "capture"])]
+    once: bool,
+
+    /// With --once, print the aggregated dashboard state as a JSON object
+    #[arg(long, requires = "once")]
+    json: bool,
+}
+
+/// Parse a shared snapshot source like "mmap:/dev/shm/dashboard";
+/// "file:" is accepted as an alias.
+fn parse_source(s: &str) -> Result<PathBuf, String> {
+    match s.strip_prefix("mmap:").or_else(|| s.strip_prefix("file:")) {
+        Some(path) if !path.is_empty() => Ok(PathBuf::from(path)),
+        _ => Err(format!("expected mmap:<path>, got {:?}", s)),
+    }
+}
+
+/// Parse a replay speed within the range the live controls allow.
+fn parse_speed(s: &str) -> Result<f64, String> {
+    let (min, max) = (collector::replay::MIN_SPEED, collector::replay::MAX_SPEED);
+    match s.parse::<f64>() {
+        Ok(speed) if (min..=max).contains(&speed) => Ok(speed),
+        _ => Err(format!("expected a speed from {} to {}, got {:?}", min, max, s)),
+    }
+}
+
+/// Parse a terminal size like "160x50".
+fn parse_size(s: &str) -> Result<(u16, u16), String> {
+    let (w, h) = s.split_once('x').ok_or_else(|| format!("expected WIDTHxHEIGHT, got {:?}", s))?;
+    let dim = |v: &str| v.trim().parse::<u16>().ok().filter(|n| *n > 0).ok_or_else(|| format!("invalid size {:?}", s));
+    Ok((dim(w)?, dim(h)?))
 }
 
 fn main() -> io::Result<()> {
-    let cli = Cli::parse();
-    let colors = match cli.theme {
-        ThemeChoice::Dark => ColorScheme::dark(),
-        ThemeChoice::Light => ColorScheme::light(),
+    // `--version` also lists the proxy schema this build understands
+    let long_version: &'static str = Box::leak(model::compatibility_info(env!("CARGO_PKG_VERSION")).into_boxed_str());
+    let matches = Cli::command().long_version(long_version).get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    // Flags override systemd credentials, which override the environment
+    let proxy_url = credentials::resolve(&credentials::PROXY_URL, cli.proxy.clone())
+        .unwrap_or_else(|| DEFAULT_PROXY_URL.to_string());
+    let auth = ProxyAuth {
+        token: credentials::resolve(&credentials::PROXY_TOKEN, cli.proxy_token.clone()),
+        ca_cert: credentials::resolve_path(&credentials::PROXY_CA_CERT, cli.proxy_ca_cert.clone()),
+    };
+
+    if cli.test_connection {
+        let ok = collector::proxy::test_connection(&proxy_url, &auth);
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
+    let colors = ColorScheme {
+        selection: match cli.selection {
+            SelectionChoice::Marker => SelectionStyle::Marker,
+            SelectionChoice::Reverse => SelectionStyle::Reverse,
+            SelectionChoice::Background => SelectionStyle::Background,
+        },
+        ..match cli.theme {
+            ThemeChoice::Dark => ColorScheme::dark(),
+            ThemeChoice::Light => ColorScheme::light(),
+        }
     };
 
     // Shared state
@@ -63,25 +483,171 @@ fn main() -> io::Result<()> {
     let throughput_snapshot = Arc::new(Mutex::new(ThroughputSnapshot::default()));
 
     // Spawn proxy collector
+    let mut replay_handle = None;
+    let (source, proxy_label) = match (&cli.replay, &cli.fixtures, &cli.source) {
+        (Some(path), _, _) => {
+            let (replay, handle) = Replay::new(collector::replay::load(path)?, cli.replay_speed);
+            replay_handle = Some(handle);
+            (ProxySource::Replay(replay), format!("replay {}", path.display()))
+        }
+        (None, Some(dir), _) => (ProxySource::Fixtures(dir.clone()), format!("fixtures {}", dir.display())),
+        (None, None, Some(path)) => (ProxySource::Shared(path.clone()), format!("shared {}", path.display())),
+        (None, None, None) => (ProxySource::Http(proxy_url.clone()), credentials::redact_userinfo(&proxy_url)),
+    };
+    let http_client = auth.client(Duration::from_secs(2)).map_err(io::Error::other)?;
+    let sinks = PollSinks {
+        share: cli.share_snapshot.as_deref().map(SharedWriter::create).transpose()?,
+        record: cli.record.as_deref().map(Recorder::create).transpose()?,
+    };
     let _proxy_handle = collector::proxy::spawn_proxy_collector(
-        cli.proxy.clone(),
-        cli.interval,
+        source,
+        PollConfig {
+            interval_secs: cli.interval,
+            adaptive: cli.adaptive,
+            max_interval_secs: cli.max_interval,
+            metric_window_secs: cli.metric_window_secs,
+            min_poll: cli.min_poll_interval_ms.map_or(collector::proxy::MIN_POLL_INTERVAL, Duration::from_millis),
+        },
+        http_client,
+        sinks,
         Arc::clone(&proxy_snapshot),
     );
 
-    // Spawn throughput collector if artifacts dir specified
-    if let Some(ref artifacts_dir) = cli.artifacts {
+    // Spawn metrics server if requested
+    if let Some(addr) = &cli.serve {
+        let _serve_handle = serve::spawn_metrics_server(
+            addr,
+            Arc::clone(&proxy_snapshot),
+            Arc::clone(&throughput_snapshot),
+            cli.gap_trim,
+            cli.partition.clone(),
+        )?;
+    }
+
+    let syslog_sink = if cli.log_syslog { Some(syslog::SyslogSink::connect()?) } else { None };
+    let mut event_detector = syslog::EventDetector::default();
+
+    // Spawn throughput collector if artifacts dirs specified
+    if !cli.artifacts.is_empty() {
         let _tp_handle = collector::throughput::spawn_throughput_collector(
-            artifacts_dir.clone(),
+            cli.artifacts.clone(),
             cli.interval,
             cli.window,
             cli.recent,
+            match cli.run_start {
+                RunStartChoice::Fields => RunStartSource::Fields,
+                RunStartChoice::Marker => RunStartSource::Marker(cli.run_start_marker.clone()),
+                RunStartChoice::Off => RunStartSource::Off,
+            },
             Arc::clone(&throughput_snapshot),
         );
     }
 
+    ui::format::set_grouping(!cli.raw_counts);
+    ui::timefmt::set_utc(cli.utc);
+    ui::format::set_uptime_style(match cli.uptime_style {
+        UptimeChoice::Clock => UptimeStyle::Clock,
+        UptimeChoice::Human => UptimeStyle::Human,
+        UptimeChoice::Iso => UptimeStyle::Iso,
+    });
+
     // Dashboard UI state
-    let mut app_state = AppState::default();
+    let mut app_state = AppState {
+        layout: match cli.layout {
+            LayoutChoice::Standard => LayoutMode::Standard,
+            LayoutChoice::Wall => LayoutMode::Wall,
+        },
+        session_sort: (match cli.sort_sessions {
+            SessionSortChoice::Proxy => SessionSort::Proxy,
+            SessionSortChoice::Recent => SessionSort::Recent,
+            SessionSortChoice::Turns => SessionSort::Turns,
+            SessionSortChoice::Status => SessionSort::Status,
+        }, SortDir::Descending),
+        model_error_threshold: cli.model_error_threshold,
+        rate_window_secs: cli.window,
+        recent_highlight_secs: cli.recent_highlight_secs,
+        idle_quiet_secs: cli.idle_quiet_secs,
+        gap_trim: cli.gap_trim,
+        precision: Precision::new(cli.precision),
+        idle_target_util: cli.idle_target_util,
+        confirm_quit: cli.confirm_quit,
+        capacity_per_backend: cli.capacity_per_backend,
+        top_backends: cli.top_backends.map(|n| n as usize),
+        health_weights: cli.health_score.then_some(cli.health_weights),
+        narrow_backends: cli.narrow,
+        expect_affinity: cli.expect_affinity,
+        herd_min_burst: cli.herd_min_burst,
+        partition: cli.partition.clone(),
+        stall_alert: cli.stall_alert_secs.map(Duration::from_secs),
+        bell_interval: Duration::from_secs(cli.bell_interval_secs),
+        expand_failed: cli.expand_failed,
+        flat_sessions: cli.flat_sessions,
+        slow_request_secs: cli.slow_request_secs,
+        watch_session: cli.session.clone(),
+        timestamp_format: match cli.timestamps {
+            TimestampChoice::Clock => TimestampFormat::Clock,
+            TimestampChoice::Iso => TimestampFormat::Iso,
+            TimestampChoice::Epoch => TimestampFormat::Epoch,
+        },
+        truncation: TruncationLimits {
+            command: cli.max_command_len,
+            task: cli.max_task_len,
+            response: cli.max_response_len,
+        },
+        ungrouped_warn_ratio: cli.ungrouped_warn_ratio,
+        pricing: (cli.price_prompt.is_some() || cli.price_completion.is_some()).then(|| Pricing {
+            prompt_per_1k: cli.price_prompt.unwrap_or(0.0),
+            completion_per_1k: cli.price_completion.unwrap_or(0.0),
+        }),
+        ..AppState::default()
+    };
+    if let Some(id) = &cli.session {
+        app_state.session_expanded.insert(id.clone());
+    }
+
+    if cli.capture.is_some() || cli.once {
+        let deadline = Instant::now() + FIRST_POLL_WAIT;
+        while proxy_snapshot.lock().unwrap().fetched_at.is_none() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    if cli.once {
+        let proxy_snap = proxy_snapshot.lock().unwrap().clone();
+        let tp_snap = throughput_snapshot.lock().unwrap().clone();
+        let scoped = app_state.scoped(&proxy_snap);
+        if cli.json {
+            let state = export::DashboardState::new(&scoped, &tp_snap, &proxy_label, app_state.gap_trim);
+            println!("{}", serde_json::to_string_pretty(&state).map_err(io::Error::other)?);
+        } else {
+            let report = ui::analysis::compute_bottleneck(&scoped, app_state.gap_trim);
+            print!("{}", export::bottleneck_text(&report, app_state.idle_target_util as f64, app_state.precision));
+        }
+        std::process::exit(if proxy_snap.connected.is_up() { 0 } else { 1 });
+    }
+
+    if let Some(path) = &cli.capture {
+        let proxy_snap = proxy_snapshot.lock().unwrap().clone();
+        let tp_snap = throughput_snapshot.lock().unwrap().clone();
+        app_state.replay = replay_handle.as_ref().map(|r| r.status());
+        let buffer = export::render_capture(&proxy_snap, &tp_snap, &proxy_label, &mut app_state, &colors, cli.capture_size)?;
+        std::fs::write(path, export::capture_text(&buffer, cli.capture_ansi))?;
+        eprintln!("Wrote {}x{} capture to {}", cli.capture_size.0, cli.capture_size.1, path.display());
+        return Ok(());
+    }
+
+    // SIGTERM/SIGINT request a clean shutdown; the loop notices within one poll
+    let shutdown = Arc::new(AtomicBool::new(false));
+    for sig in [signal_hook::consts::SIGTERM, signal_hook::consts::SIGINT] {
+        signal_hook::flag::register(sig, Arc::clone(&shutdown))?;
+    }
+
+    // Leave the terminal usable if we panic mid-draw
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+    }));
 
     // Setup terminal
     enable_raw_mode()?;
@@ -92,38 +658,392 @@ fn main() -> io::Result<()> {
 
     // Main event loop
     let poll_timeout = Duration::from_millis(200);
+    let frame_interval = Duration::from_secs_f64(1.0 / cli.max_fps as f64);
+    let mut last_frame: Option<(ui::FrameKey, Instant)> = None;
+    // Set by any terminal event; snapshot and clock changes are caught by the frame key
+    let mut input_dirty = true;
+    let mut history: VecDeque<ProxySnapshot> = VecDeque::new();
+    let mut selectable_cache = SelectableCache::default();
+    let started = Instant::now();
+    let mut idle_watch = IdleWatch::default();
+    let started_at = SystemTime::now();
+    let mut watched_seen = false;
+    // Set to (exit code, message) to quit with a non-interactive result
+    let mut exit_with: Option<(i32, String)> = None;
 
-    loop {
+    while !shutdown.load(Ordering::Relaxed) {
         // Draw
-        let proxy_snap = proxy_snapshot.lock().unwrap().clone();
+        let live_snap = proxy_snapshot.lock().unwrap().clone();
+        // The `--partition` view, built once per frame for everything below
+        let live_scoped = app_state.scoped(&live_snap);
+
+        // Record each new poll; keep a scrubbed view pinned to the same snapshot
+        if live_snap.fetched_at.is_some()
+            && history.back().map(|s: &ProxySnapshot| s.fetched_at) != Some(live_snap.fetched_at)
+        {
+            if let Some(sink) = &syslog_sink {
+                let diagnosis = ui::analysis::compute_bottleneck(&live_scoped, app_state.gap_trim)
+                    .diagnosis
+                    .label();
+                for event in event_detector.observe(&live_snap, diagnosis) {
+                    sink.send(&event);
+                }
+            }
+            for t in &live_snap.health_transitions {
+                let port = t.url.rsplit(':').next().unwrap_or(&t.url);
+                app_state.toasts.push(Toast {
+                    text: format!(":{} {}", port, if t.healthy { "UP" } else { "DOWN" }),
+                    good: t.healthy,
+                    expires: Instant::now() + TOAST_TTL,
+                });
+            }
+            if cli.fleet_toasts {
+                for c in &live_snap.fleet_changes {
+                    let port = c.url.rsplit(':').next().unwrap_or(&c.url);
+                    app_state.toasts.push(Toast {
+                        text: format!(
+                            ":{} {} {} {}",
+                            port,
+                            if c.added { "joined" } else { "left" },
+                            c.model,
+                            ui::timefmt::format_epoch(c.at, app_state.timestamp_format),
+                        ),
+                        good: c.added,
+                        expires: Instant::now() + TOAST_TTL,
+                    });
+                }
+            }
+            if live_snap.connected.is_up() {
+                app_state.record_model_errors(&live_scoped.stats);
+            }
+            history.push_back(live_snap.clone());
+            if history.len() > cli.history.max(1) {
+                history.pop_front();
+            }
+            if let Some(offset) = app_state.history_offset.as_mut() {
+                *offset = (*offset + 1).min(history.len() - 1);
+            }
+        }
+
+        app_state.toasts.retain(|t| t.expires > Instant::now());
+        app_state.expand_new_failures(&live_snap.queue);
+
+        // `--stall-alert-secs`: completions gone quiet while the proxy is still busy
+        if let Some(threshold) = app_state.stall_alert {
+            let in_flight = live_snap.queue.summary.in_flight;
+            let stall = throughput_snapshot.lock().unwrap().completion_stall(
+                in_flight,
+                started_at,
+                SystemTime::now(),
+                threshold,
+            );
+            let stall_started = stall.is_some() && app_state.completion_stall.is_none();
+            if stall_started && cli.stall_bell && app_state.take_bell(Instant::now()) {
+                print!("\x07");
+                io::stdout().flush()?;
+            }
+            if let Some(sink) = &syslog_sink {
+                if let Some(event) = event_detector.observe_stall(stall, in_flight) {
+                    sink.send(&event);
+                }
+            }
+            app_state.completion_stall = stall;
+        }
+        app_state.update_alerts(&live_scoped);
+
+        // `--session`: expand the owning client on first sight, then watch for completion
+        if let Some(id) = &cli.session {
+            match live_snap.queue.find_session(id) {
+                Some(sess) => {
+                    if !watched_seen {
+                        watched_seen = true;
+                        if let Some(client) = live_snap
+                            .queue
+                            .clients
+                            .iter()
+                            .find(|c| c.sessions.iter().any(|s| s.session_id == *id))
+                        {
+                            app_state.client_expanded.insert(client.client_id.clone());
+                        }
+                    }
+                    if cli.exit_on_complete && sess.is_finished() {
+                        exit_with = Some((0, format!("session {} completed", id)));
+                    } else if cli.exit_on_complete && sess.is_settled() {
+                        exit_with = Some((
+                            1,
+                            format!("session {} finished with {} failed requests", id, sess.failed_requests),
+                        ));
+                    }
+                }
+                // Gone after being seen: it will never be reported finished
+                None if watched_seen && cli.exit_on_complete => {
+                    exit_with = Some((1, format!("session {} disappeared from the proxy", id)));
+                }
+                None if !watched_seen
+                    && cli.session_timeout > 0
+                    && started.elapsed() > Duration::from_secs(cli.session_timeout) =>
+                {
+                    exit_with = Some((
+                        1,
+                        format!("session {} did not appear within {}s", id, cli.session_timeout),
+                    ));
+                }
+                None => {}
+            }
+            if exit_with.is_some() {
+                break;
+            }
+        }
+
+        // `--exit-when-idle-secs`: the batch has drained
+        if let Some(limit) = cli.exit_when_idle_secs.map(Duration::from_secs) {
+            if idle_watch.observe(&live_snap, Instant::now()).is_some_and(|idle| idle >= limit) {
+                let failed = idle_watch.failed_requests();
+                exit_with = Some(match failed {
+                    0 => (0, format!("proxy idle for {}s", limit.as_secs())),
+                    n => (1, format!("proxy idle for {}s; {} requests failed", limit.as_secs(), n)),
+                });
+                break;
+            }
+        }
+
+        let (proxy_snap, scoped) = match app_state.history_offset {
+            Some(offset) if offset < history.len() => {
+                let snap = &history[history.len() - 1 - offset];
+                (snap, app_state.scoped(snap))
+            }
+            _ => (&live_snap, live_scoped),
+        };
+        app_state.replay = replay_handle.as_ref().map(|r| r.status());
+        app_state.history_age = app_state
+            .history_offset
+            .and(proxy_snap.fetched_at)
+            .map(|t| t.elapsed());
         let tp_snap = throughput_snapshot.lock().unwrap().clone();
 
         // Clamp selection indices
-        let model_count = proxy_snap.stats.pools.len();
+        let pool_models: Vec<String> = scoped
+            .stats
+            .pools
+            .iter()
+            .map(|p| p.model.clone())
+            .collect();
+        let model_count = pool_models.len();
         if model_count > 0 && app_state.backend_selected >= model_count {
             app_state.backend_selected = model_count - 1;
         }
-        let selectable_items = app_state.build_selectable_items(&proxy_snap.queue);
+        let selectable_items = selectable_cache.items(&app_state, proxy_snap);
         let selectable_count = selectable_items.len();
-        if selectable_count > 0 && app_state.session_selected >= selectable_count {
-            app_state.session_selected = selectable_count - 1;
-        }
+        app_state.clamp_session_selected(selectable_count);
 
-        terminal.draw(|frame| {
-            ui::draw(frame, &proxy_snap, &tp_snap, &cli.proxy, &mut app_state, &colors);
-        })?;
+        let frame_key = ui::FrameKey::new(proxy_snap, &tp_snap, &app_state, chrono::Local::now().timestamp());
+        let changed = input_dirty || last_frame.as_ref().is_none_or(|(k, _)| *k != frame_key);
+        let since_frame = last_frame.as_ref().map_or(frame_interval, |(_, at)| at.elapsed());
+        if changed && since_frame >= frame_interval {
+            terminal.draw(|frame| {
+                ui::draw(frame, proxy_snap, &scoped, &tp_snap, &proxy_label, &mut app_state, &colors);
+            })?;
+            last_frame = Some((frame_key, Instant::now()));
+            input_dirty = false;
+        }
 
-        // Handle input
-        if event::poll(poll_timeout)? {
+        // Handle input; a deferred redraw only waits out the rest of the frame budget
+        let timeout = if changed && since_frame < frame_interval {
+            (frame_interval - since_frame).min(poll_timeout)
+        } else {
+            poll_timeout
+        };
+        if event::poll(timeout)? {
+            input_dirty = true;
             if let Event::Key(key) = event::read()? {
+                // The quit prompt takes the next key: only `y` quits, so a
+                // reflexive Esc cancels rather than confirms
+                if app_state.quit_prompt {
+                    if key.code == KeyCode::Char('y') {
+                        break;
+                    }
+                    app_state.quit_prompt = false;
+                    continue;
+                }
+
+                // Detail modals capture input until closed
+                if app_state.modal_open() {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('d') | KeyCode::Char('?') | KeyCode::Char('M') => {
+                            app_state.turn_detail = None;
+                            app_state.session_detail = None;
+                            app_state.show_legend = false;
+                            app_state.show_models = false;
+                        }
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app_state.modal_scroll = app_state.modal_scroll.saturating_sub(1);
+                        }
+                        KeyCode::Down | KeyCode::Char('j') => {
+                            app_state.modal_scroll = app_state.modal_scroll.saturating_add(1);
+                        }
+                        KeyCode::PageUp => {
+                            app_state.modal_scroll = app_state.modal_scroll.saturating_sub(10);
+                        }
+                        KeyCode::PageDown => {
+                            app_state.modal_scroll = app_state.modal_scroll.saturating_add(10);
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // `--replay` playback controls go to the replay thread
+                if let Some(replay) = &replay_handle {
+                    let control = match key.code {
+                        KeyCode::Char(' ') => Some(ReplayControl::TogglePause),
+                        KeyCode::Char('+') | KeyCode::Char('=') => Some(ReplayControl::Faster),
+                        KeyCode::Char('-') => Some(ReplayControl::Slower),
+                        KeyCode::Char('.') => Some(ReplayControl::Step),
+                        _ => None,
+                    };
+                    if let Some(control) = control {
+                        replay.send(control);
+                        continue;
+                    }
+                }
+
                 match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc if app_state.confirm_quit => {
+                        app_state.quit_prompt = true;
+                    }
                     KeyCode::Char('q') | KeyCode::Esc => break,
-                    KeyCode::Tab | KeyCode::BackTab => {
-                        app_state.focused_panel = match app_state.focused_panel {
-                            FocusedPanel::Backends => FocusedPanel::Sessions,
-                            FocusedPanel::Sessions => FocusedPanel::Backends,
+                    // Raw mode swallows SIGINT, so treat Ctrl-C as the same request
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                    KeyCode::Char('[') if !history.is_empty() => {
+                        // Step back in history (offset 0 is the newest snapshot)
+                        let next = app_state.history_offset.map_or(1, |o| o + 1);
+                        app_state.history_offset = Some(next.min(history.len() - 1));
+                    }
+                    KeyCode::Char(']') => {
+                        app_state.history_offset = match app_state.history_offset {
+                            Some(o) if o > 1 => Some(o - 1),
+                            _ => None,
                         };
                     }
+                    KeyCode::End => {
+                        app_state.history_offset = None;
+                    }
+                    KeyCode::Char('t') => {
+                        app_state.timestamp_format = app_state.timestamp_format.next();
+                    }
+                    KeyCode::Char('d') if app_state.focused_panel == FocusedPanel::Sessions => {
+                        let session = match selectable_items.get(app_state.session_selected) {
+                            Some(SelectableItem::Session(sid)) | Some(SelectableItem::Turn(sid, _)) => Some(sid.clone()),
+                            _ => None,
+                        };
+                        if session.is_some() {
+                            app_state.session_detail = session;
+                            app_state.modal_scroll = 0;
+                        }
+                    }
+                    KeyCode::Char('i') => {
+                        app_state.hide_idle_clients = !app_state.hide_idle_clients;
+                    }
+                    KeyCode::Char('x') if app_state.focused_panel == FocusedPanel::Sessions => {
+                        if let Some(SelectableItem::Session(sid) | SelectableItem::Turn(sid, _)) =
+                            selectable_items.get(app_state.session_selected)
+                        {
+                            if !app_state.session_full_text.remove(sid) {
+                                app_state.session_full_text.insert(sid.clone());
+                                app_state.session_expanded.insert(sid.clone());
+                            }
+                        }
+                    }
+                    KeyCode::Char('f') => {
+                        app_state.hide_finished = !app_state.hide_finished;
+                    }
+                    KeyCode::Char('*') if app_state.focused_panel == FocusedPanel::Sessions => {
+                        if let Some(SelectableItem::Session(sid) | SelectableItem::Turn(sid, _)) =
+                            selectable_items.get(app_state.session_selected)
+                        {
+                            app_state.toggle_star(sid);
+                        }
+                    }
+                    KeyCode::Char('s') => {
+                        app_state.starred_only = !app_state.starred_only;
+                    }
+                    KeyCode::Char('e') => {
+                        app_state.expand_failed = !app_state.expand_failed;
+                    }
+                    KeyCode::Char('r') => {
+                        app_state.show_requests = !app_state.show_requests;
+                    }
+                    KeyCode::Char('a') if !app_state.alerts.is_empty() => {
+                        let acked = app_state.ack_alerts();
+                        app_state.status_message = Some((format!("Acknowledged {} alert(s)", acked), Instant::now()));
+                    }
+                    KeyCode::Char('c') => {
+                        app_state.clients_only = !app_state.clients_only;
+                    }
+                    KeyCode::Char('o') | KeyCode::Char('O') => {
+                        // o cycles the key, O flips the direction; both survive panel switches
+                        let (sort, dir) = &mut app_state.session_sort;
+                        match key.code {
+                            KeyCode::Char('o') => *sort = sort.next(),
+                            _ => *dir = dir.flip(),
+                        }
+                        let msg = format!("Sort sessions: {} {}", sort.label(), dir.arrow());
+                        app_state.status_message = Some((msg, Instant::now()));
+                    }
+                    KeyCode::Char('b') => {
+                        app_state.baseline = Some(proxy_snap.clone());
+                        app_state.status_message = Some(("Baseline captured".to_string(), Instant::now()));
+                    }
+                    KeyCode::Char('B') if app_state.baseline.is_some() => {
+                        app_state.baseline = None;
+                        app_state.status_message = Some(("Baseline cleared".to_string(), Instant::now()));
+                    }
+                    KeyCode::Char('?') => {
+                        app_state.show_legend = true;
+                        app_state.modal_scroll = 0;
+                    }
+                    KeyCode::Char('M') => {
+                        app_state.show_models = true;
+                        app_state.modal_scroll = 0;
+                    }
+                    KeyCode::Char('v') => {
+                        app_state.bottleneck_verbosity = app_state.bottleneck_verbosity.next();
+                    }
+                    KeyCode::Char('p') => {
+                        app_state.latency_stat = app_state.latency_stat.toggle();
+                    }
+                    KeyCode::Char('y') => {
+                        let text = export::panel_tsv(proxy_snap, &scoped, &app_state);
+                        let msg = match clipboard::copy_text(&text, "panel") {
+                            Ok(clipboard::CopyTarget::Clipboard(tool)) => format!("Copied panel via {}", tool),
+                            Ok(clipboard::CopyTarget::File(path)) => format!("No clipboard; saved to {}", path.display()),
+                            Err(e) => format!("Copy failed: {}", e),
+                        };
+                        app_state.status_message = Some((msg, Instant::now()));
+                    }
+                    KeyCode::Char('Y') => {
+                        let report = ui::analysis::compute_bottleneck(&scoped, app_state.gap_trim);
+                        let text = export::bottleneck_text(&report, app_state.idle_target_util as f64, app_state.precision);
+                        let msg = match clipboard::copy_text(&text, "analysis") {
+                            Ok(clipboard::CopyTarget::Clipboard(tool)) => format!("Copied analysis via {}", tool),
+                            Ok(clipboard::CopyTarget::File(path)) => format!("No clipboard; saved to {}", path.display()),
+                            Err(e) => format!("Copy failed: {}", e),
+                        };
+                        app_state.status_message = Some((msg, Instant::now()));
+                    }
+                    KeyCode::Char('m') => {
+                        let msg = match export::write_bottleneck_report(&cli.report_dir, &scoped, &proxy_label, app_state.gap_trim, app_state.precision) {
+                            Ok(path) => format!("Report written to {}", path.display()),
+                            Err(e) => format!("Report export failed: {}", e),
+                        };
+                        app_state.status_message = Some((msg, Instant::now()));
+                    }
+                    KeyCode::Tab => {
+                        app_state.focused_panel = app_state.focused_panel.next();
+                    }
+                    KeyCode::BackTab => {
+                        app_state.focused_panel = app_state.focused_panel.prev();
+                    }
                     KeyCode::Up | KeyCode::Char('k') => {
                         match app_state.focused_panel {
                             FocusedPanel::Backends => {
@@ -155,14 +1075,9 @@ fn main() -> io::Result<()> {
                     KeyCode::Enter => {
                         match app_state.focused_panel {
                             FocusedPanel::Backends => {
-                                if model_count > 0 {
-                                    if let Some(pool) = proxy_snap.stats.pools.get(app_state.backend_selected) {
-                                        let name = pool.model.clone();
-                                        if app_state.backend_expanded.contains(&name) {
-                                            app_state.backend_expanded.remove(&name);
-                                        } else {
-                                            app_state.backend_expanded.insert(name);
-                                        }
+                                if let Some(name) = pool_models.get(app_state.backend_selected) {
+                                    if !app_state.backend_expanded.remove(name) {
+                                        app_state.backend_expanded.insert(name.clone());
                                     }
                                 }
                             }
@@ -183,6 +1098,10 @@ fn main() -> io::Result<()> {
                                                 app_state.session_expanded.insert(sid.clone());
                                             }
                                         }
+                                        SelectableItem::Turn(sid, idx) => {
+                                            app_state.turn_detail = Some((sid.clone(), *idx));
+                                            app_state.modal_scroll = 0;
+                                        }
                                     }
                                 }
                             }
@@ -199,5 +1118,16 @@ fn main() -> io::Result<()> {
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
+    if let Some((code, msg)) = exit_with {
+        eprintln!("{}", msg);
+        std::process::exit(code);
+    }
+
     Ok(())
 }
+
+/// Best-effort terminal restore for paths that can't propagate errors.
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, crossterm::cursor::Show);
+}
@@ -1,22 +1,75 @@
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::{Duration, Instant, SystemTime};
 
 use ratatui::widgets::TableState;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::ui::format::Precision;
 
 // --- /stats response ---
 
+/// `/stats` schema versions this dashboard knows how to render.
+pub const KNOWN_SCHEMA_VERSIONS: &[&str] = &["1"];
+
+/// Legacy field names accepted in proxy responses, as
+/// (record, field, alias). Keep in sync with the `serde(alias)` attributes below.
+pub const SCHEMA_ALIASES: &[(&str, &str, &str)] = &[
+    ("queue", "sessions", "episodes"),
+    ("queue", "clients", "processes"),
+    ("queue", "orphan_sessions", "orphan_episodes"),
+    ("request", "session_id", "episode_id"),
+    ("request", "task_id", "instruction_id"),
+    ("client", "client_id", "process_id"),
+    ("client", "client_command", "process_command"),
+    ("client", "sessions", "episodes"),
+    ("session", "session_id", "episode_id"),
+    ("session", "task_id", "instruction_id"),
+    ("turn", "agent_obs_ms", "agent_pre_ms"),
+    ("turn", "agent_act_ms", "agent_post_ms"),
+    ("turn", "prompt_tokens", "input_tokens"),
+    ("turn", "completion_tokens", "output_tokens"),
+    ("health", "healthy_backends", "healthy"),
+    ("health", "total_backends", "backends"),
+];
+
+/// Version text plus the proxy schema this build understands, for `--version`.
+pub fn compatibility_info(version: &str) -> String {
+    let mut out = format!(
+        "{}\nproxy schema versions: {}\nendpoints: /stats, /queue/status, /health (fallback)\naccepted aliases:",
+        version,
+        KNOWN_SCHEMA_VERSIONS.join(", "),
+    );
+    for (record, field, alias) in SCHEMA_ALIASES {
+        out.push_str(&format!("\n  {}.{} <- {}", record, field, alias));
+    }
+    out
+}
+
+/// Timing value that treats `null` and non-finite numbers as zero, so a
+/// misbehaving proxy can't poison averages with NaN.
+fn finite_or_zero<'de, D: Deserializer<'de>>(d: D) -> Result<f64, D::Error> {
+    Ok(finite_opt(d)?.unwrap_or(0.0))
+}
+
+/// Optional timing value; non-finite numbers count as not reported.
+fn finite_opt<'de, D: Deserializer<'de>>(d: D) -> Result<Option<f64>, D::Error> {
+    Ok(Option::<f64>::deserialize(d)?.filter(|v| v.is_finite()))
+}
+
 #[allow(dead_code)]
-#[derive(Deserialize, Clone, Default, Debug)]
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
 pub struct StatsResponse {
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_or_zero")]
     pub uptime_seconds: f64,
     #[serde(default)]
     pub total_requests: u64,
     #[serde(default)]
     pub total_errors: u64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_or_zero")]
     pub error_rate: f64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_or_zero")]
     pub requests_per_minute: f64,
     #[serde(default)]
     pub strategy: String,
@@ -24,9 +77,33 @@ pub struct StatsResponse {
     pub models: Vec<String>,
     #[serde(default)]
     pub pools: Vec<PoolStats>,
+    /// Absent on older proxies, which are assumed compatible.
+    #[serde(default)]
+    pub schema_version: Option<String>,
+    /// Proxy wall clock (epoch seconds) when the response was built.
+    #[serde(default, deserialize_with = "finite_opt")]
+    pub server_time: Option<f64>,
+}
+
+impl StatsResponse {
+    /// Models the proxy knows about that have no backends registered.
+    pub fn empty_pools(&self) -> Vec<&str> {
+        self.pools
+            .iter()
+            .filter(|p| p.backends.is_empty())
+            .map(|p| p.model.as_str())
+            .collect()
+    }
+
+    /// The proxy's schema version, if it reported one we don't recognize.
+    pub fn unknown_schema_version(&self) -> Option<&str> {
+        self.schema_version
+            .as_deref()
+            .filter(|v| !KNOWN_SCHEMA_VERSIONS.contains(v))
+    }
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct PoolStats {
     pub model: String,
     #[serde(default)]
@@ -34,7 +111,7 @@ pub struct PoolStats {
 }
 
 #[allow(dead_code)]
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct BackendStats {
     pub url: String,
     #[serde(default)]
@@ -43,7 +120,7 @@ pub struct BackendStats {
     pub requests: u64,
     #[serde(default)]
     pub errors: u64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_or_zero")]
     pub avg_latency_ms: f64,
     #[serde(default)]
     pub inflight: u64,
@@ -53,7 +130,7 @@ pub struct BackendStats {
 
 // --- /queue/status response ---
 
-#[derive(Deserialize, Clone, Default, Debug)]
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
 pub struct QueueResponse {
     #[serde(default)]
     pub summary: QueueSummary,
@@ -74,10 +151,74 @@ pub struct QueueResponse {
 
     #[serde(default, alias = "orphan_episodes")]
     pub orphan_sessions: Vec<SessionGroup>,
+
+    /// Set by paginating proxies when more sessions follow.
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+impl QueueResponse {
+    /// Append a later page. The summary and backends are queue-wide and
+    /// repeated on every page, so the first page's summary is kept and only
+    /// backends not already listed are added; a client split across pages
+    /// is merged into one group.
+    pub fn merge_page(&mut self, page: QueueResponse) {
+        for backend in page.backends {
+            if !self.backends.iter().any(|b| b.url == backend.url) {
+                self.backends.push(backend);
+            }
+        }
+        self.pending.extend(page.pending);
+        self.in_flight.extend(page.in_flight);
+        self.sessions.extend(page.sessions);
+        self.orphan_sessions.extend(page.orphan_sessions);
+        for client in page.clients {
+            match self.clients.iter_mut().find(|c| c.client_id == client.client_id) {
+                Some(existing) => existing.sessions.extend(client.sessions),
+                None => self.clients.push(client),
+            }
+        }
+        self.next_cursor = page.next_cursor;
+    }
+
+    /// Running cost estimate across every tracked session.
+    pub fn estimated_cost(&self, pricing: &Pricing) -> Option<f64> {
+        let sessions = if self.clients.is_empty() && self.orphan_sessions.is_empty() {
+            &self.sessions[..]
+        } else {
+            &self.orphan_sessions[..]
+        };
+        sum_costs(
+            self.clients
+                .iter()
+                .map(|c| c.estimated_cost(pricing))
+                .chain(sessions.iter().map(|s| s.estimated_cost(pricing))),
+        )
+    }
+
+    /// Find a session by id across clients, orphans, and the flat list.
+    pub fn find_session(&self, session_id: &str) -> Option<&SessionGroup> {
+        self.all_sessions().find(|s| s.session_id == session_id)
+    }
+
+    /// Every session in the response: under clients, orphaned, and flat.
+    /// A proxy may list a session in more than one place.
+    pub fn all_sessions(&self) -> impl Iterator<Item = &SessionGroup> {
+        self.clients
+            .iter()
+            .flat_map(|c| c.sessions.iter())
+            .chain(self.orphan_sessions.iter())
+            .chain(self.sessions.iter())
+    }
+
+    /// Payload sizes summed over every tracked session's turns.
+    pub fn payload_bytes(&self) -> Option<PayloadBytes> {
+        PayloadBytes::sum(self.all_sessions().flat_map(|s| s.completed_turns.iter()))
+    }
 }
 
 #[allow(dead_code)]
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct QueueRequest {
     #[serde(default)]
     pub request_id: String,
@@ -89,10 +230,13 @@ pub struct QueueRequest {
     pub status: String,
     #[serde(default)]
     pub backend: Option<String>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_or_zero")]
     pub wait_time_ms: f64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_opt")]
     pub processing_time_ms: Option<f64>,
+    /// Epoch seconds when a backend picked the request up, if reported.
+    #[serde(default, deserialize_with = "finite_opt")]
+    pub started_at: Option<f64>,
 
     // Dual terminology support
     #[serde(default, alias = "episode_id")]
@@ -101,7 +245,20 @@ pub struct QueueRequest {
     pub task_id: Option<String>,
 }
 
-#[derive(Deserialize, Clone, Default, Debug)]
+impl QueueRequest {
+    /// How long this in-flight request has been running as of `as_of`
+    /// (epoch seconds). `processing_time_ms` was measured at fetch time, so
+    /// `since_fetch_ms` is added to keep it growing between polls.
+    pub fn running_ms(&self, as_of: f64, since_fetch_ms: f64) -> Option<f64> {
+        match (self.processing_time_ms, self.started_at) {
+            (Some(ms), _) => Some(ms + since_fetch_ms),
+            (None, Some(start)) if start > 0.0 => Some(((as_of - start) * 1000.0).max(0.0)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
 pub struct QueueSummary {
     #[serde(default)]
     pub pending: u64,
@@ -114,7 +271,7 @@ pub struct QueueSummary {
 }
 
 #[allow(dead_code)]
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct QueueBackend {
     pub url: String,
     #[serde(default)]
@@ -123,7 +280,7 @@ pub struct QueueBackend {
     pub gpu_load: u64,
     #[serde(default)]
     pub inflight: u64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_or_zero")]
     pub avg_latency_ms: f64,
     #[serde(default)]
     pub partition: String,
@@ -133,7 +290,7 @@ pub struct QueueBackend {
 
 /// Client group - represents a process running multiple sessions.
 /// Aliases: process_id, process_command, episodes (for backward compat)
-#[derive(Deserialize, Clone, Default, Debug)]
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
 pub struct ClientGroup {
     #[serde(default, alias = "process_id")]
     pub client_id: String,
@@ -146,7 +303,7 @@ pub struct ClientGroup {
 /// Session group - represents a multi-turn conversation.
 /// Aliases: episode_id, instruction_id (for backward compat)
 #[allow(dead_code)]
-#[derive(Deserialize, Clone, Default, Debug)]
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
 pub struct SessionGroup {
     #[serde(default, alias = "episode_id")]
     pub session_id: String,
@@ -174,7 +331,7 @@ pub struct SessionGroup {
 }
 
 #[allow(dead_code)]
-#[derive(Deserialize, Clone, Default, Debug)]
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
 pub struct CompletedTurn {
     #[serde(default)]
     pub request_id: String,
@@ -184,25 +341,304 @@ pub struct CompletedTurn {
     pub request_summary: Option<String>,
     #[serde(default)]
     pub response_summary: Option<String>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_or_zero")]
     pub submitted_at: f64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_or_zero")]
     pub completed_at: f64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_or_zero")]
     pub total_time_ms: f64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_or_zero")]
     pub wait_time_ms: f64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_or_zero")]
     pub processing_time_ms: f64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "finite_opt")]
     pub backend_time_ms: Option<f64>,
-    #[serde(default, alias = "agent_pre_ms")]
+    #[serde(default, alias = "agent_pre_ms", deserialize_with = "finite_opt")]
     pub agent_obs_ms: Option<f64>,
-    #[serde(default, alias = "agent_post_ms")]
+    #[serde(default, alias = "agent_post_ms", deserialize_with = "finite_opt")]
     pub agent_act_ms: Option<f64>,
     /// Sequential turn number within session (1-indexed, assigned by proxy).
     #[serde(default)]
     pub turn_number: Option<u64>,
+    #[serde(default, alias = "input_tokens")]
+    pub prompt_tokens: Option<u64>,
+    #[serde(default, alias = "output_tokens")]
+    pub completion_tokens: Option<u64>,
+    /// Request body size as sent to the backend.
+    #[serde(default)]
+    pub request_bytes: Option<u64>,
+    #[serde(default)]
+    pub response_bytes: Option<u64>,
+}
+
+/// Request/response payload sizes summed over turns that report them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PayloadBytes {
+    pub request: u64,
+    pub response: u64,
+    /// Turns carrying at least one size.
+    pub turns: usize,
+}
+
+impl PayloadBytes {
+    /// `None` when no turn reports a size, so displays can stay hidden.
+    pub fn sum<'a>(turns: impl Iterator<Item = &'a CompletedTurn>) -> Option<PayloadBytes> {
+        turns
+            .filter(|t| t.request_bytes.is_some() || t.response_bytes.is_some())
+            .fold(None, |acc, t| {
+                let acc: PayloadBytes = acc.unwrap_or_default();
+                Some(PayloadBytes {
+                    request: acc.request + t.request_bytes.unwrap_or(0),
+                    response: acc.response + t.response_bytes.unwrap_or(0),
+                    turns: acc.turns + 1,
+                })
+            })
+    }
+}
+
+/// Per-1k-token prices used for cost estimates.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Pricing {
+    pub prompt_per_1k: f64,
+    pub completion_per_1k: f64,
+}
+
+/// Relative weights of the signals in a backend health score. Only the
+/// ratios matter; the score normalizes by their sum.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HealthWeights {
+    pub healthy: f64,
+    pub errors: f64,
+    pub latency: f64,
+    pub headroom: f64,
+}
+
+impl Default for HealthWeights {
+    fn default() -> Self {
+        Self { healthy: 40.0, errors: 25.0, latency: 20.0, headroom: 15.0 }
+    }
+}
+
+impl std::str::FromStr for HealthWeights {
+    type Err = String;
+
+    /// Parse "HEALTHY,ERRORS,LATENCY,HEADROOM", e.g. "40,25,20,15".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values: Vec<f64> = s
+            .split(',')
+            .map(|v| v.trim().parse::<f64>().ok().filter(|w| w.is_finite() && *w >= 0.0))
+            .collect::<Option<_>>()
+            .ok_or_else(|| format!("weights must be non-negative numbers, got {:?}", s))?;
+        let [healthy, errors, latency, headroom] = values[..] else {
+            return Err(format!("expected HEALTHY,ERRORS,LATENCY,HEADROOM, got {:?}", s));
+        };
+        if healthy + errors + latency + headroom <= 0.0 {
+            return Err("at least one weight must be positive".to_string());
+        }
+        Ok(Self { healthy, errors, latency, headroom })
+    }
+}
+
+impl CompletedTurn {
+    /// Estimated cost of this turn, or `None` if the proxy reported no token counts.
+    pub fn estimated_cost(&self, pricing: &Pricing) -> Option<f64> {
+        if self.prompt_tokens.is_none() && self.completion_tokens.is_none() {
+            return None;
+        }
+        let prompt = self.prompt_tokens.unwrap_or(0) as f64 / 1000.0 * pricing.prompt_per_1k;
+        let completion = self.completion_tokens.unwrap_or(0) as f64 / 1000.0 * pricing.completion_per_1k;
+        Some(prompt + completion)
+    }
+}
+
+/// Wait-to-inference ratio above which a session counts as queue-starved.
+pub const QUEUE_STARVED_RATIO: f64 = 1.0;
+
+impl SessionGroup {
+    /// Nothing queued and every request accounted for, successfully or not.
+    pub fn is_settled(&self) -> bool {
+        let total = if self.total_turns > 0 { self.total_turns } else { self.total_requests };
+        total > 0
+            && self.turns_done() + self.failed_requests >= total
+            && self.pending_requests == 0
+            && self.in_flight_requests == 0
+    }
+
+    /// Every turn done and nothing left queued: the session is done. Uses
+    /// `total_turns` when the proxy reports it, like the turns column, and
+    /// `turns_done` so turns the proxy has cleaned up still count.
+    pub fn is_finished(&self) -> bool {
+        let total = if self.total_turns > 0 { self.total_turns } else { self.total_requests };
+        total > 0 && self.turns_done() >= total && self.pending_requests == 0 && self.in_flight_requests == 0
+    }
+
+    /// Turns finished so far. `completed_requests` can shrink when the proxy
+    /// cleans up old requests, but `total_turns` survives cleanup, so turns
+    /// no longer queued, running or failed count as finished too.
+    pub fn turns_done(&self) -> u64 {
+        let settled = self
+            .total_turns
+            .saturating_sub(self.pending_requests + self.in_flight_requests + self.failed_requests);
+        settled.max(self.completed_requests)
+    }
+
+    /// Sum of turn costs, or `None` if no turn carries token counts.
+    pub fn estimated_cost(&self, pricing: &Pricing) -> Option<f64> {
+        sum_costs(self.completed_turns.iter().map(|t| t.estimated_cost(pricing)))
+    }
+
+    /// Latest turn completion (epoch seconds), or 0 if none completed.
+    pub fn last_completed_at(&self) -> f64 {
+        self.completed_turns.iter().map(|t| t.completed_at).fold(0.0, f64::max)
+    }
+
+    /// Payload sizes summed over this session's completed turns.
+    pub fn payload_bytes(&self) -> Option<PayloadBytes> {
+        PayloadBytes::sum(self.completed_turns.iter())
+    }
+
+    /// Summed queue wait over summed inference time (backend time where
+    /// reported, else processing time) across completed turns. `None` when
+    /// there is no inference time to divide by.
+    pub fn wait_ratio(&self) -> Option<f64> {
+        let wait: f64 = self.completed_turns.iter().map(|t| t.wait_time_ms).sum();
+        let inference: f64 = self
+            .completed_turns
+            .iter()
+            .map(|t| t.backend_time_ms.unwrap_or(t.processing_time_ms))
+            .sum();
+        (inference > 0.0).then(|| wait / inference)
+    }
+
+    /// The session spends more time queued than inferring.
+    pub fn is_queue_starved(&self) -> bool {
+        self.wait_ratio().is_some_and(|r| r > QUEUE_STARVED_RATIO)
+    }
+
+    /// Number of distinct backends that served this session's completed turns.
+    /// Turns without a reported backend are not counted.
+    pub fn distinct_backends(&self) -> usize {
+        self.completed_turns
+            .iter()
+            .filter_map(|t| t.backend.as_deref())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+}
+
+/// Aggregate counts over a client's sessions, for the client summary view.
+#[derive(Debug, Default, PartialEq)]
+pub struct ClientTotals {
+    pub sessions: usize,
+    pub turns_done: u64,
+    pub turns_total: u64,
+    /// Sessions with pending or in-flight requests.
+    pub active: usize,
+    /// Sessions with at least one failed request.
+    pub failed: usize,
+}
+
+impl ClientGroup {
+    /// Session and turn totals across this client. Turn totals prefer each
+    /// session's `total_turns`, like the session turns column, and finished
+    /// turns come from `turns_done` so proxy cleanup doesn't shrink them.
+    pub fn totals(&self) -> ClientTotals {
+        let mut totals = ClientTotals { sessions: self.sessions.len(), ..ClientTotals::default() };
+        for sess in &self.sessions {
+            totals.turns_done += sess.turns_done();
+            totals.turns_total += if sess.total_turns > 0 { sess.total_turns } else { sess.total_requests };
+            if sess.pending_requests > 0 || sess.in_flight_requests > 0 {
+                totals.active += 1;
+            }
+            if sess.failed_requests > 0 {
+                totals.failed += 1;
+            }
+        }
+        totals
+    }
+
+    /// Turn completions per minute across this client's sessions, counting
+    /// turns completed within `window_secs` of `now` (epoch seconds).
+    /// `None` with fewer than two turns, since a single point has no rate.
+    pub fn turns_per_minute(&self, now: f64, window_secs: f64) -> Option<f64> {
+        let mut times: Vec<f64> = self
+            .sessions
+            .iter()
+            .flat_map(|s| s.completed_turns.iter())
+            .map(|t| t.completed_at)
+            .filter(|&t| t > 0.0 && now - t <= window_secs)
+            .collect();
+        if times.len() < 2 {
+            return None;
+        }
+        times.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let span_min = (times[times.len() - 1] - times[0]) / 60.0;
+        if span_min <= 0.0 {
+            return None;
+        }
+        Some((times.len() - 1) as f64 / span_min)
+    }
+
+    pub fn estimated_cost(&self, pricing: &Pricing) -> Option<f64> {
+        sum_costs(self.sessions.iter().map(|s| s.estimated_cost(pricing)))
+    }
+
+    /// No pending or in-flight requests, and (when `quiet_secs` > 0) no turn
+    /// completed within `quiet_secs` of `now` (epoch seconds).
+    pub fn is_idle(&self, now: f64, quiet_secs: f64) -> bool {
+        let busy = self.sessions.iter().any(|s| s.pending_requests > 0 || s.in_flight_requests > 0);
+        let recent = quiet_secs > 0.0
+            && self
+                .sessions
+                .iter()
+                .flat_map(|s| s.completed_turns.iter())
+                .any(|t| t.completed_at > 0.0 && now - t.completed_at <= quiet_secs);
+        !busy && !recent
+    }
+}
+
+/// Tracks how long the proxy has been drained, for `--exit-when-idle-secs`,
+/// plus every session's failures seen along the way, since the proxy may
+/// clean a session up before the batch drains.
+#[derive(Default)]
+pub struct IdleWatch {
+    /// Last poll with queued work or a new completion, and the turn count then.
+    last_activity: Option<(Instant, u64)>,
+    failed: HashMap<String, u64>,
+}
+
+impl IdleWatch {
+    /// How long the proxy has had nothing pending or in flight and completed
+    /// no turns, as of `now`. `None` while busy or not connected; the clock
+    /// starts at the first connected poll.
+    pub fn observe(&mut self, proxy: &ProxySnapshot, now: Instant) -> Option<Duration> {
+        for sess in proxy.queue.all_sessions() {
+            let seen = self.failed.entry(sess.session_id.clone()).or_insert(0);
+            *seen = (*seen).max(sess.failed_requests);
+        }
+        if !proxy.connected.is_up() {
+            return None;
+        }
+        let summary = &proxy.queue.summary;
+        let busy = summary.pending > 0 || summary.in_flight > 0;
+        match self.last_activity {
+            Some((_, turns)) if !busy && turns == proxy.turns_completed => {}
+            _ => self.last_activity = Some((now, proxy.turns_completed)),
+        }
+        self.last_activity
+            .filter(|_| !busy)
+            .map(|(at, _)| now.duration_since(at))
+    }
+
+    /// Failed requests across every session observed.
+    pub fn failed_requests(&self) -> u64 {
+        self.failed.values().sum()
+    }
+}
+
+/// Sum the known costs; `None` only if every input is `None`.
+fn sum_costs(costs: impl Iterator<Item = Option<f64>>) -> Option<f64> {
+    costs.flatten().fold(None, |acc, c| Some(acc.unwrap_or(0.0) + c))
 }
 
 // --- Dashboard UI state ---
@@ -213,11 +649,219 @@ pub enum FocusedPanel {
     Sessions,
 }
 
+impl FocusedPanel {
+    /// Focus order for Tab; BackTab walks it in reverse.
+    const ORDER: [FocusedPanel; 2] = [FocusedPanel::Backends, FocusedPanel::Sessions];
+
+    pub fn next(self) -> Self {
+        let idx = Self::ORDER.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ORDER[(idx + 1) % Self::ORDER.len()]
+    }
+
+    pub fn prev(self) -> Self {
+        let idx = Self::ORDER.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ORDER[(idx + Self::ORDER.len() - 1) % Self::ORDER.len()]
+    }
+}
+
+/// Optional caps on free-text cells; unset fields use the full column width.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TruncationLimits {
+    pub command: Option<usize>,
+    pub task: Option<usize>,
+    pub response: Option<usize>,
+}
+
+/// How much the Bottleneck Analysis panel shows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Diagnosis line only.
+    Compact,
+    #[default]
+    Normal,
+    /// Normal plus latency percentiles.
+    Detailed,
+}
+
+impl Verbosity {
+    pub fn next(self) -> Self {
+        match self {
+            Verbosity::Compact => Verbosity::Normal,
+            Verbosity::Normal => Verbosity::Detailed,
+            Verbosity::Detailed => Verbosity::Compact,
+        }
+    }
+}
+
+/// Which statistic the latency displays show.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LatencyStat {
+    #[default]
+    Mean,
+    /// 95th percentile, for chasing stragglers.
+    P95,
+}
+
+impl LatencyStat {
+    pub fn toggle(self) -> Self {
+        match self {
+            LatencyStat::Mean => LatencyStat::P95,
+            LatencyStat::P95 => LatencyStat::Mean,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LatencyStat::Mean => "avg",
+            LatencyStat::P95 => "p95",
+        }
+    }
+}
+
+/// Polls of per-model error increments kept for the errors panel sparkline.
+pub const ERROR_TREND_LEN: usize = 20;
+
+/// Recent per-poll error increments for one model.
+#[derive(Clone, Debug, Default)]
+pub struct ErrorTrend {
+    last: Option<u64>,
+    pub deltas: VecDeque<u64>,
+}
+
+/// Order of sessions under each client.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SessionSort {
+    /// As the proxy lists them.
+    #[default]
+    Proxy,
+    /// Most recently completed turn first.
+    Recent,
+    /// Most turns done first.
+    Turns,
+    /// Failed, then active, then waiting, then finished.
+    Status,
+}
+
+/// Direction of a panel sort. Descending is each key's natural
+/// worst/most-first order; ascending flips it to best-first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SortDir {
+    #[default]
+    Descending,
+    Ascending,
+}
+
+impl SortDir {
+    pub fn flip(self) -> Self {
+        match self {
+            SortDir::Descending => SortDir::Ascending,
+            SortDir::Ascending => SortDir::Descending,
+        }
+    }
+
+    pub fn arrow(self) -> &'static str {
+        match self {
+            SortDir::Descending => "\u{2193}",
+            SortDir::Ascending => "\u{2191}",
+        }
+    }
+}
+
+impl SessionSort {
+    /// The next key in the `o` cycle.
+    pub fn next(self) -> Self {
+        match self {
+            SessionSort::Proxy => SessionSort::Recent,
+            SessionSort::Recent => SessionSort::Turns,
+            SessionSort::Turns => SessionSort::Status,
+            SessionSort::Status => SessionSort::Proxy,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SessionSort::Proxy => "proxy",
+            SessionSort::Recent => "recent",
+            SessionSort::Turns => "turns",
+            SessionSort::Status => "status",
+        }
+    }
+
+    /// `sessions` in this order. The sort is stable, so ties keep proxy
+    /// order; ascending reverses the whole list, ties included.
+    pub fn apply(self, dir: SortDir, sessions: &[SessionGroup]) -> Vec<&SessionGroup> {
+        let mut sorted: Vec<&SessionGroup> = sessions.iter().collect();
+        match self {
+            SessionSort::Proxy => {}
+            SessionSort::Recent => sorted.sort_by(|a, b| b.last_completed_at().total_cmp(&a.last_completed_at())),
+            SessionSort::Turns => sorted.sort_by_key(|s| std::cmp::Reverse(s.turns_done())),
+            SessionSort::Status => sorted.sort_by_key(|s| {
+                if s.failed_requests > 0 {
+                    0
+                } else if s.pending_requests > 0 || s.in_flight_requests > 0 {
+                    1
+                } else if !s.is_finished() {
+                    2
+                } else {
+                    3
+                }
+            }),
+        }
+        if dir == SortDir::Ascending {
+            sorted.reverse();
+        }
+        sorted
+    }
+}
+
+/// A condition the header flashes for until acknowledged. Acks are kept
+/// per identity, so a different backend going down still fires.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Alert {
+    /// No completions while requests are in flight (`--stall-alert-secs`).
+    CompletionStall,
+    /// The proxy reports this backend unhealthy.
+    BackendDown(String),
+}
+
+/// Top-level screen arrangement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LayoutMode {
+    /// All panels stacked, tuned for information density.
+    #[default]
+    Standard,
+    /// Big-screen grid: backends, GPU load chart, and diagnosis only.
+    Wall,
+}
+
+/// How turn and completion timestamps are rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimestampFormat {
+    /// Local `HH:MM:SS`.
+    #[default]
+    Clock,
+    /// Full ISO-8601 with date and UTC offset.
+    Iso,
+    /// Raw epoch seconds, as the proxy reports them.
+    Epoch,
+}
+
+impl TimestampFormat {
+    pub fn next(self) -> Self {
+        match self {
+            TimestampFormat::Clock => TimestampFormat::Iso,
+            TimestampFormat::Iso => TimestampFormat::Epoch,
+            TimestampFormat::Epoch => TimestampFormat::Clock,
+        }
+    }
+}
+
 /// Selectable item in the hierarchical sessions panel.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SelectableItem {
-    Client(String),   // client_id
-    Session(String),  // session_id
+    Client(String),       // client_id
+    Session(String),      // session_id
+    Turn(String, usize),  // session_id, index into completed_turns
 }
 
 #[derive(Debug)]
@@ -227,10 +871,105 @@ pub struct AppState {
     pub session_selected: usize,              // index into flattened selectable items
     pub client_expanded: HashSet<String>,     // expanded client_ids
     pub session_expanded: HashSet<String>,    // expanded session_ids
+    pub session_full_text: HashSet<String>,   // sessions showing wrapped full turn text
     pub session_table_state: TableState,
     // Backends panel
     pub backend_selected: usize,
     pub backend_expanded: HashSet<String>,  // expanded model names
+    // Turn detail modal -- (session_id, turn index) when open
+    pub turn_detail: Option<(String, usize)>,
+    // Session detail modal -- session_id when open
+    pub session_detail: Option<String>,
+    // Timing-terms legend modal (`?`)
+    pub show_legend: bool,
+    // Configured-models modal (`M`)
+    pub show_models: bool,
+    // Scroll offset of whichever modal is open
+    pub modal_scroll: u16,
+    // Transient feedback for one-shot actions (export, copy), with when it was set
+    pub status_message: Option<(String, Instant)>,
+    // Display settings (from CLI)
+    pub ungrouped_warn_ratio: f64,
+    pub pricing: Option<Pricing>,
+    pub layout: LayoutMode,
+    pub rate_window_secs: u64,
+    pub recent_highlight_secs: u64,
+    pub timestamp_format: TimestampFormat,
+    // History scrubber -- steps back from the newest snapshot; None = live
+    pub history_offset: Option<usize>,
+    pub history_age: Option<std::time::Duration>,
+    // `--replay` playback position and controls; None when running live
+    pub replay: Option<ReplayStatus>,
+    pub truncation: TruncationLimits,
+    pub toasts: Vec<Toast>,
+    pub bottleneck_verbosity: Verbosity,
+    pub latency_stat: LatencyStat,
+    // Session order under each client, with its direction
+    pub session_sort: (SessionSort, SortDir),
+    // Error rate percent at which a model is highlighted in the errors panel
+    pub model_error_threshold: f64,
+    pub model_error_trend: HashMap<String, ErrorTrend>,
+    // Ask before quitting on q/Esc (`--confirm-quit`)
+    pub confirm_quit: bool,
+    // The quit prompt is showing and captures the next keypress
+    pub quit_prompt: bool,
+    // Hide idle clients from the sessions panel (toggle)
+    pub hide_idle_clients: bool,
+    pub idle_quiet_secs: u64,
+    // Hide finished sessions from the sessions panel (toggle)
+    pub hide_finished: bool,
+    // Sessions marked with `*`, by session_id so marks survive reordering
+    pub starred: HashSet<String>,
+    // Show only starred sessions and the clients that own them (toggle)
+    pub starred_only: bool,
+    // Turn rows show the request snippet instead of the response (toggle)
+    pub show_requests: bool,
+    // Expand sessions as they record new failures (`--expand-failed`, toggle)
+    pub expand_failed: bool,
+    // Failure count each session was last auto-expanded at, so a manual
+    // collapse sticks until the session fails again
+    pub failures_seen: HashMap<String, u64>,
+    // Only show this session (and its client), from `--session`
+    pub watch_session: Option<String>,
+    // In-flight requests running longer than this are flagged
+    pub slow_request_secs: u64,
+    // Always render the flat session list, ignoring client grouping
+    pub flat_sessions: bool,
+    // Fraction of largest agent gaps ignored by the diagnosis
+    pub gap_trim: f64,
+    // Decimals for durations, percentages and ratios (--precision)
+    pub precision: Precision,
+    // Utilization percent the IDLE headroom suggestion aims for
+    pub idle_target_util: u8,
+    // Concurrent requests one healthy backend is expected to serve
+    pub capacity_per_backend: u64,
+    // List only this many of the busiest backends per pool, from `--top-backends`
+    pub top_backends: Option<usize>,
+    // One line per backend regardless of width, from `--narrow`
+    pub narrow_backends: bool,
+    // Show the backend health score column with these weights, from `--health-score`
+    pub health_weights: Option<HealthWeights>,
+    // Sessions should stick to one backend; flag ones that moved
+    pub expect_affinity: bool,
+    // Show only client rows with aggregate columns (toggle)
+    pub clients_only: bool,
+    // Smallest pending burst reported as a thundering herd (0 disables)
+    pub herd_min_burst: usize,
+    // Snapshot captured with `b`; backends show deltas against it
+    pub baseline: Option<ProxySnapshot>,
+    // Only count backends in this partition, from `--partition`
+    pub partition: Option<String>,
+    // No completions for this long with work in flight is a stall, from `--stall-alert-secs`
+    pub stall_alert: Option<Duration>,
+    // How long completions have been silent, while stalled
+    pub completion_stall: Option<Duration>,
+    // Alerts firing as of the last tick, and those acknowledged with `a`
+    pub alerts: Vec<Alert>,
+    pub acked_alerts: HashSet<Alert>,
+    // Shortest gap between two terminal bells, from `--bell-interval-secs`
+    pub bell_interval: Duration,
+    // When the bell last rang
+    pub last_bell: Option<Instant>,
 }
 
 impl Default for AppState {
@@ -240,14 +979,89 @@ impl Default for AppState {
             session_selected: 0,
             client_expanded: HashSet::new(),
             session_expanded: HashSet::new(),
+            session_full_text: HashSet::new(),
             session_table_state: TableState::default(),
             backend_selected: 0,
             backend_expanded: HashSet::new(),
+            turn_detail: None,
+            session_detail: None,
+            show_legend: false,
+            show_models: false,
+            modal_scroll: 0,
+            status_message: None,
+            ungrouped_warn_ratio: 0.5,
+            pricing: None,
+            layout: LayoutMode::Standard,
+            rate_window_secs: 300,
+            recent_highlight_secs: 30,
+            timestamp_format: TimestampFormat::Clock,
+            history_offset: None,
+            history_age: None,
+            replay: None,
+            truncation: TruncationLimits::default(),
+            toasts: Vec::new(),
+            bottleneck_verbosity: Verbosity::Normal,
+            latency_stat: LatencyStat::Mean,
+            session_sort: (SessionSort::Proxy, SortDir::Descending),
+            model_error_threshold: 5.0,
+            model_error_trend: HashMap::new(),
+            confirm_quit: false,
+            quit_prompt: false,
+            hide_idle_clients: false,
+            idle_quiet_secs: 60,
+            hide_finished: false,
+            starred: HashSet::new(),
+            starred_only: false,
+            show_requests: false,
+            expand_failed: false,
+            failures_seen: HashMap::new(),
+            watch_session: None,
+            slow_request_secs: 60,
+            flat_sessions: false,
+            gap_trim: 0.05,
+            precision: Precision::default(),
+            idle_target_util: 80,
+            capacity_per_backend: 8,
+            top_backends: None,
+            health_weights: None,
+            narrow_backends: false,
+            expect_affinity: false,
+            clients_only: false,
+            herd_min_burst: 8,
+            baseline: None,
+            partition: None,
+            stall_alert: None,
+            bell_interval: Duration::from_secs(30),
+            last_bell: None,
+            completion_stall: None,
+            alerts: Vec::new(),
+            acked_alerts: HashSet::new(),
         }
     }
 }
 
 impl AppState {
+    /// Whether a modal is open and capturing input.
+    pub fn modal_open(&self) -> bool {
+        self.turn_detail.is_some() || self.session_detail.is_some() || self.show_legend || self.show_models
+    }
+
+    /// Whether `client` is currently hidden by the idle-client or starred-only toggle.
+    pub fn client_hidden(&self, client: &ClientGroup, now: f64) -> bool {
+        let unwatched = self
+            .watch_session
+            .as_ref()
+            .is_some_and(|id| !client.sessions.iter().any(|s| s.session_id == *id));
+        let unstarred = self.starred_only && !client.sessions.iter().any(|s| self.starred.contains(&s.session_id));
+        unwatched || unstarred || (self.hide_idle_clients && client.is_idle(now, self.idle_quiet_secs as f64))
+    }
+
+    /// Whether the sessions panel groups by client: the proxy reports
+    /// grouping and `--flat-sessions` isn't forcing the flat list.
+    pub fn uses_hierarchy(&self, queue: &QueueResponse) -> bool {
+        !self.flat_sessions && (!queue.clients.is_empty() || !queue.orphan_sessions.is_empty())
+    }
+
     /// Build the flat list of selectable items from the current queue data.
     pub fn build_selectable_items(&self, queue: &QueueResponse) -> Vec<SelectableItem> {
         let mut items = Vec::new();
@@ -255,45 +1069,445 @@ impl AppState {
         let clients = &queue.clients;
         let orphans = &queue.orphan_sessions;
 
-        if !clients.is_empty() || !orphans.is_empty() {
+        if self.uses_hierarchy(queue) {
             // Hierarchical mode
-            for client in clients {
+            let now = epoch_now();
+            for client in clients.iter().filter(|c| !self.client_hidden(c, now)) {
                 items.push(SelectableItem::Client(client.client_id.clone()));
-                if self.client_expanded.contains(&client.client_id) {
-                    for sess in &client.sessions {
-                        items.push(SelectableItem::Session(sess.session_id.clone()));
+                if !self.clients_only && self.client_expanded.contains(&client.client_id) {
+                    for sess in self.session_sort.0.apply(self.session_sort.1, &client.sessions) {
+                        self.push_session_items(sess, &mut items);
                     }
                 }
             }
             // Orphan sessions (no client)
-            for sess in orphans {
-                items.push(SelectableItem::Session(sess.session_id.clone()));
+            for sess in orphans.iter().filter(|_| !self.clients_only) {
+                self.push_session_items(sess, &mut items);
             }
         } else {
             // Flat fallback (old proxy without client grouping)
             for sess in &queue.sessions {
-                items.push(SelectableItem::Session(sess.session_id.clone()));
+                self.push_session_items(sess, &mut items);
             }
         }
 
         items
     }
 
+    /// The snapshot as the backend-facing panels should see it: scoped to
+    /// `--partition` when one is set.
+    pub fn scoped<'a>(&self, proxy: &'a ProxySnapshot) -> Cow<'a, ProxySnapshot> {
+        match &self.partition {
+            Some(partition) => Cow::Owned(proxy.in_partition(partition)),
+            None => Cow::Borrowed(proxy),
+        }
+    }
+
+    /// Whether `sess` is currently hidden by `--session`, the finished-session
+    /// toggle or the starred-only toggle.
+    pub fn session_hidden(&self, sess: &SessionGroup) -> bool {
+        let unwatched = self.watch_session.as_ref().is_some_and(|id| *id != sess.session_id);
+        let unstarred = self.starred_only && !self.starred.contains(&sess.session_id);
+        unwatched || unstarred || (self.hide_finished && sess.is_finished())
+    }
+
+    /// Star `session_id`, or unstar it if already starred.
+    pub fn toggle_star(&mut self, session_id: &str) {
+        if !self.starred.remove(session_id) {
+            self.starred.insert(session_id.to_string());
+        }
+    }
+
+    /// Refresh the firing alerts from `proxy` and the stall state. Acks of
+    /// alerts that have cleared are dropped, so a recurrence fires again.
+    pub fn update_alerts(&mut self, proxy: &ProxySnapshot) {
+        let mut alerts = Vec::new();
+        if self.completion_stall.is_some() {
+            alerts.push(Alert::CompletionStall);
+        }
+        // Health flags from a dead connection are stale
+        if proxy.connected.is_up() {
+            alerts.extend(
+                proxy
+                    .stats
+                    .pools
+                    .iter()
+                    .flat_map(|p| p.backends.iter())
+                    .filter(|b| !b.healthy)
+                    .map(|b| Alert::BackendDown(b.url.clone())),
+            );
+        }
+        self.acked_alerts.retain(|a| alerts.contains(a));
+        self.alerts = alerts;
+    }
+
+    /// Acknowledge every firing alert, returning how many were new acks.
+    pub fn ack_alerts(&mut self) -> usize {
+        let before = self.acked_alerts.len();
+        self.acked_alerts.extend(self.alerts.iter().cloned());
+        self.acked_alerts.len() - before
+    }
+
+    /// Whether `alert` is firing and not yet acknowledged.
+    pub fn alert_unacked(&self, alert: &Alert) -> bool {
+        self.alerts.contains(alert) && !self.acked_alerts.contains(alert)
+    }
+
+    /// Whether a bell may ring at `now`, recording it if so. Bells closer
+    /// together than `bell_interval` are swallowed so a flapping alert
+    /// doesn't beep continuously.
+    pub fn take_bell(&mut self, now: Instant) -> bool {
+        if self.last_bell.is_some_and(|last| now.duration_since(last) < self.bell_interval) {
+            return false;
+        }
+        self.last_bell = Some(now);
+        true
+    }
+
+    /// With `expand_failed`, expand every session whose failure count grew
+    /// since it was last seen, along with its client.
+    pub fn expand_new_failures(&mut self, queue: &QueueResponse) {
+        if !self.expand_failed {
+            return;
+        }
+        for client in &queue.clients {
+            for sess in &client.sessions {
+                if self.expand_if_failed(sess) {
+                    self.client_expanded.insert(client.client_id.clone());
+                }
+            }
+        }
+        for sess in queue.orphan_sessions.iter().chain(&queue.sessions) {
+            self.expand_if_failed(sess);
+        }
+    }
+
+    fn expand_if_failed(&mut self, sess: &SessionGroup) -> bool {
+        let seen = self.failures_seen.entry(sess.session_id.clone()).or_insert(0);
+        if sess.failed_requests <= *seen {
+            return false;
+        }
+        *seen = sess.failed_requests;
+        self.session_expanded.insert(sess.session_id.clone());
+        true
+    }
+
+    /// Push a session row, followed by its turn rows when expanded.
+    fn push_session_items(&self, sess: &SessionGroup, items: &mut Vec<SelectableItem>) {
+        if self.session_hidden(sess) {
+            return;
+        }
+        items.push(SelectableItem::Session(sess.session_id.clone()));
+        if self.session_expanded.contains(&sess.session_id) {
+            for i in 0..sess.completed_turns.len() {
+                items.push(SelectableItem::Turn(sess.session_id.clone(), i));
+            }
+        }
+    }
+
+    /// Record each model's error increment since the previous poll. Counters
+    /// that reset (proxy restart) count from zero rather than going negative.
+    pub fn record_model_errors(&mut self, stats: &StatsResponse) {
+        for pool in &stats.pools {
+            let errors: u64 = pool.backends.iter().map(|b| b.errors).sum();
+            let trend = self.model_error_trend.entry(pool.model.clone()).or_default();
+            if let Some(last) = trend.last {
+                trend.deltas.push_back(errors.checked_sub(last).unwrap_or(errors));
+                if trend.deltas.len() > ERROR_TREND_LEN {
+                    trend.deltas.pop_front();
+                }
+            }
+            trend.last = Some(errors);
+        }
+    }
+
+    /// Keep the session selection within `count` items.
+    pub fn clamp_session_selected(&mut self, count: usize) {
+        if count > 0 && self.session_selected >= count {
+            self.session_selected = count - 1;
+        }
+    }
+
+    /// Fingerprint of everything `build_selectable_items` reads.
+    fn selectable_key(&self, proxy: &ProxySnapshot) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        proxy.fetched_at.hash(&mut hasher);
+        for set in [&self.client_expanded, &self.session_expanded, &self.starred] {
+            let mut ids: Vec<&String> = set.iter().collect();
+            ids.sort();
+            ids.hash(&mut hasher);
+        }
+        (
+            self.hide_idle_clients,
+            self.hide_finished,
+            self.starred_only,
+            self.clients_only,
+            self.flat_sessions,
+            self.session_sort,
+            &self.watch_session,
+        )
+            .hash(&mut hasher);
+        // Idle hiding depends on the clock as well
+        if self.hide_idle_clients {
+            (epoch_now() as u64).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
     /// Resolve the currently selected item.
-    #[allow(dead_code)]
     pub fn resolve_selected(&self, queue: &QueueResponse) -> Option<SelectableItem> {
         let items = self.build_selectable_items(queue);
         items.get(self.session_selected).cloned()
     }
 }
 
+/// `build_selectable_items` memoized on the displayed snapshot and the
+/// expansion/filter state, so large client lists aren't rebuilt every frame.
+#[derive(Default)]
+pub struct SelectableCache {
+    key: Option<u64>,
+    items: Vec<SelectableItem>,
+}
+
+impl SelectableCache {
+    pub fn items(&mut self, state: &AppState, proxy: &ProxySnapshot) -> &[SelectableItem] {
+        let key = state.selectable_key(proxy);
+        if self.key != Some(key) {
+            self.items = state.build_selectable_items(&proxy.queue);
+            self.key = Some(key);
+        }
+        &self.items
+    }
+}
+
+/// Current wall-clock time as epoch seconds, matching proxy timestamps.
+pub fn epoch_now() -> f64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
 // --- Internal snapshots ---
 
+/// A backend changing health between two polls.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthTransition {
+    pub url: String,
+    pub healthy: bool,
+}
+
+/// A backend joining or leaving a model pool between two polls.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FleetChange {
+    pub model: String,
+    pub url: String,
+    pub added: bool,
+    /// Epoch seconds of the poll that saw the change.
+    pub at: f64,
+}
+
+/// A transient notification shown until `expires`.
+#[derive(Clone, Debug)]
+pub struct Toast {
+    pub text: String,
+    pub good: bool,
+    pub expires: Instant,
+}
+
+/// `/health` body from proxies that lack the richer endpoints. Every field
+/// is optional since implementations vary.
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
+pub struct HealthResponse {
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default, alias = "healthy")]
+    pub healthy_backends: Option<u64>,
+    #[serde(default, alias = "backends")]
+    pub total_backends: Option<u64>,
+    #[serde(default)]
+    pub pending: Option<u64>,
+    #[serde(default)]
+    pub in_flight: Option<u64>,
+}
+
+impl HealthResponse {
+    /// Short summary of whatever counts the endpoint reported.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(status) = &self.status {
+            parts.push(status.clone());
+        }
+        match (self.healthy_backends, self.total_backends) {
+            (Some(h), Some(t)) => parts.push(format!("{}/{} backends healthy", h, t)),
+            (Some(h), None) => parts.push(format!("{} backends healthy", h)),
+            (None, Some(t)) => parts.push(format!("{} backends", t)),
+            (None, None) => {}
+        }
+        if let Some(p) = self.pending {
+            parts.push(format!("{} pending", p));
+        }
+        if let Some(f) = self.in_flight {
+            parts.push(format!("{} in flight", f));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Proxy reachability as seen by the collector.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum Connection {
+    /// No poll has completed yet, e.g. right after startup.
+    #[default]
+    Never,
+    Up,
+    /// The latest poll failed, whether or not the proxy ever answered.
+    Down,
+}
+
+impl Connection {
+    pub fn is_up(self) -> bool {
+        self == Connection::Up
+    }
+
+    /// Placeholder for panels without data: neutral until the first poll
+    /// completes, so startup doesn't look like an outage.
+    pub fn placeholder(self) -> &'static str {
+        match self {
+            Connection::Never => "Connecting\u{2026}",
+            _ => "Proxy offline",
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct ProxySnapshot {
-    pub connected: bool,
+    /// When the collector last completed a poll; `None` before the first.
+    pub fetched_at: Option<Instant>,
+    pub connected: Connection,
+    /// Set when `/stats` and `/queue/status` both failed but `/health`
+    /// answered; `connected` isn't `Up` and the other data is stale.
+    pub limited: Option<HealthResponse>,
     pub stats: StatsResponse,
     pub queue: QueueResponse,
+    /// Health changes detected by the most recent poll.
+    pub health_transitions: Vec<HealthTransition>,
+    /// Backends added to or removed from pools by the most recent poll.
+    pub fleet_changes: Vec<FleetChange>,
+    /// Per-backend mean latency over the recent window, keyed by URL.
+    pub recent_latency_ms: HashMap<String, f64>,
+    /// Turns completed since the dashboard started.
+    pub turns_completed: u64,
+    /// Recent rate of `turns_completed`, once there are two polls to compare.
+    pub turns_per_min: Option<f64>,
+    /// Local clock minus the proxy's, measured at the last `/stats` poll;
+    /// positive when the dashboard runs ahead. `None` if the proxy doesn't
+    /// report `server_time`.
+    pub clock_skew_secs: Option<f64>,
+    /// In-flight requests with no backend yet, tracked across polls.
+    pub unrouted: UnroutedRequests,
+    /// Time between the last two successful polls, once there are two.
+    pub update_cadence: Option<Duration>,
+    /// Successful polls so far; the header pulse advances with it, so a
+    /// collector that stops updating visibly freezes.
+    pub polls_succeeded: u64,
+    /// The poll interval the collector was aiming for (grows while the
+    /// adaptive interval backs off).
+    pub target_interval: Duration,
+}
+
+/// Cadence over target at which the collector counts as falling behind.
+const CADENCE_LAG_RATIO: f64 = 1.5;
+/// Slack below which a slower cadence isn't worth mentioning.
+const CADENCE_LAG_MIN: Duration = Duration::from_secs(1);
+
+/// In-flight requests the proxy has accepted but not yet routed.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct UnroutedRequests {
+    pub count: usize,
+    /// How long the oldest of them has gone without a backend.
+    pub oldest: Duration,
+}
+
+/// Where `--replay` playback stands, shared by the replay thread for the header.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct ReplayStatus {
+    /// 1-based index of the frame on screen.
+    pub frame: usize,
+    pub frames: usize,
+    /// Wall clock (epoch seconds) when the frame was recorded.
+    pub recorded_at: f64,
+    /// Playback rate relative to the recording.
+    pub speed: f64,
+    pub paused: bool,
+    /// Holding the last frame.
+    pub ended: bool,
+}
+
+/// How long an in-flight request may lack a backend before it is a routing stall.
+pub const UNROUTED_STALL: Duration = Duration::from_secs(5);
+
+/// Clock skew beyond which relative times and "ago" windows are unreliable.
+pub const CLOCK_SKEW_WARN_SECS: f64 = 2.0;
+
+impl ProxySnapshot {
+    /// The measured clock skew, if it is large enough to warn about.
+    pub fn significant_clock_skew(&self) -> Option<f64> {
+        self.clock_skew_secs.filter(|s| s.abs() > CLOCK_SKEW_WARN_SECS)
+    }
+
+    /// The actual update cadence when it lags well behind the target
+    /// interval, i.e. polls themselves are slow rather than the proxy idle.
+    pub fn lagging_cadence(&self) -> Option<Duration> {
+        self.update_cadence.filter(|c| {
+            !self.target_interval.is_zero()
+                && c.as_secs_f64() > self.target_interval.as_secs_f64() * CADENCE_LAG_RATIO
+                && *c > self.target_interval + CADENCE_LAG_MIN
+        })
+    }
+
+    /// Unrouted requests, once the oldest has waited past `UNROUTED_STALL`.
+    pub fn routing_stall(&self) -> Option<UnroutedRequests> {
+        Some(self.unrouted).filter(|u| u.count > 0 && u.oldest >= UNROUTED_STALL)
+    }
+
+    /// Copy of this snapshot restricted to backends in `partition`: other
+    /// backends, pools left without backends, and turns those backends
+    /// served are dropped, so aggregates and the diagnosis ignore them.
+    pub fn in_partition(&self, partition: &str) -> ProxySnapshot {
+        let mut scoped = self.clone();
+        let mut excluded: HashSet<String> = HashSet::new();
+        for pool in &mut scoped.stats.pools {
+            pool.backends.retain(|b| {
+                let keep = b.partition == partition;
+                if !keep {
+                    excluded.insert(b.url.clone());
+                }
+                keep
+            });
+        }
+        scoped.stats.pools.retain(|p| !p.backends.is_empty());
+        scoped.queue.backends.retain(|b| {
+            let keep = b.partition == partition;
+            if !keep {
+                excluded.insert(b.url.clone());
+            }
+            keep
+        });
+
+        let queue = &mut scoped.queue;
+        let sessions = queue
+            .clients
+            .iter_mut()
+            .flat_map(|c| c.sessions.iter_mut())
+            .chain(queue.orphan_sessions.iter_mut())
+            .chain(queue.sessions.iter_mut());
+        for sess in sessions {
+            sess.completed_turns
+                .retain(|t| t.backend.as_ref().is_none_or(|b| !excluded.contains(b)));
+        }
+        scoped
+    }
 }
 
 #[derive(Clone, Default, Debug)]
@@ -303,12 +1517,573 @@ pub struct ThroughputSnapshot {
     pub success: usize,
     pub failure: usize,
     pub rate_per_min: f64,
+    /// Mean run duration over completions with a known start time.
+    pub avg_run_secs: Option<f64>,
     pub recent: Vec<CompletionEntry>,
+    /// Modification time of the newest artifact seen.
+    pub last_completion: Option<SystemTime>,
+}
+
+impl ThroughputSnapshot {
+    /// How long nothing has completed, once that reaches `threshold` while
+    /// the proxy still has requests in flight; a legitimately idle system
+    /// never stalls. Silence is counted from `since` at the earliest, so
+    /// artifacts from before the dashboard started don't alarm at once.
+    pub fn completion_stall(&self, in_flight: u64, since: SystemTime, now: SystemTime, threshold: Duration) -> Option<Duration> {
+        if !self.enabled || in_flight == 0 {
+            return None;
+        }
+        let last = self.last_completion.map_or(since, |t| t.max(since));
+        now.duration_since(last).ok().filter(|silence| *silence >= threshold)
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct CompletionEntry {
-    pub time: String,
+    pub modified: SystemTime,
     pub spec_name: String,
     pub success: bool,
+    pub duration: Option<std::time::Duration>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const PRICING: Pricing = Pricing { prompt_per_1k: 0.5, completion_per_1k: 2.0 };
+
+    #[test]
+    fn running_time_grows_from_processing_or_start() {
+        let req: QueueRequest = serde_json::from_value(json!({"processing_time_ms": 1500.0})).unwrap();
+        assert_eq!(req.running_ms(0.0, 500.0), Some(2000.0));
+        let req: QueueRequest = serde_json::from_value(json!({"started_at": 100.0})).unwrap();
+        assert_eq!(req.running_ms(103.0, 500.0), Some(3000.0));
+        let req: QueueRequest = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(req.running_ms(103.0, 0.0), None);
+    }
+
+    #[test]
+    fn starred_only_keeps_starred_sessions_and_their_clients() {
+        let queue: QueueResponse = serde_json::from_value(json!({
+            "clients": [
+                {"client_id": "c1", "sessions": [{"session_id": "s1"}, {"session_id": "s2"}]},
+                {"client_id": "c2", "sessions": [{"session_id": "s3"}]}
+            ]
+        }))
+        .unwrap();
+        let mut state = AppState::default();
+        state.client_expanded.extend(["c1".to_string(), "c2".to_string()]);
+        state.toggle_star("s2");
+        state.toggle_star("s3");
+        state.toggle_star("s3");
+        assert_eq!(state.starred, HashSet::from(["s2".to_string()]));
+        assert_eq!(state.build_selectable_items(&queue).len(), 5);
+
+        state.starred_only = true;
+        assert_eq!(
+            state.build_selectable_items(&queue),
+            vec![SelectableItem::Client("c1".into()), SelectableItem::Session("s2".into())]
+        );
+    }
+
+    #[test]
+    fn watch_session_hides_other_sessions_and_clients() {
+        let queue: QueueResponse = serde_json::from_value(json!({
+            "clients": [
+                {"client_id": "c1", "sessions": [{"session_id": "s1"}, {"session_id": "s2"}]},
+                {"client_id": "c2", "sessions": [{"session_id": "s3"}]}
+            ]
+        }))
+        .unwrap();
+        let mut state = AppState { watch_session: Some("s2".into()), ..AppState::default() };
+        state.client_expanded.insert("c1".into());
+        assert_eq!(
+            state.build_selectable_items(&queue),
+            vec![SelectableItem::Client("c1".into()), SelectableItem::Session("s2".into())]
+        );
+    }
+
+    #[test]
+    fn session_sort_orders_selectable_items() {
+        let queue: QueueResponse = serde_json::from_value(json!({
+            "clients": [{"client_id": "c1", "sessions": [
+                {"session_id": "done", "total_turns": 2, "completed_requests": 2,
+                 "completed_turns": [{"completed_at": 300.0}]},
+                {"session_id": "busy", "total_turns": 9, "completed_requests": 5, "in_flight_requests": 1,
+                 "completed_turns": [{"completed_at": 100.0}]},
+                {"session_id": "failed", "total_turns": 2, "completed_requests": 1, "failed_requests": 1,
+                 "completed_turns": [{"completed_at": 200.0}]}
+            ]}]
+        }))
+        .unwrap();
+        let order = |sort: SessionSort| -> Vec<SelectableItem> {
+            let mut state = AppState { session_sort: (sort, SortDir::Descending), ..AppState::default() };
+            state.client_expanded.insert("c1".into());
+            state.build_selectable_items(&queue).into_iter().skip(1).collect()
+        };
+        let sessions = |ids: &[&str]| -> Vec<SelectableItem> {
+            ids.iter().map(|id| SelectableItem::Session(id.to_string())).collect()
+        };
+        assert_eq!(order(SessionSort::Proxy), sessions(&["done", "busy", "failed"]));
+        assert_eq!(order(SessionSort::Recent), sessions(&["done", "failed", "busy"]));
+        assert_eq!(order(SessionSort::Turns), sessions(&["busy", "done", "failed"]));
+        assert_eq!(order(SessionSort::Status), sessions(&["failed", "busy", "done"]));
+
+        let mut state = AppState { session_sort: (SessionSort::Turns, SortDir::Ascending), ..AppState::default() };
+        state.client_expanded.insert("c1".into());
+        let items: Vec<SelectableItem> = state.build_selectable_items(&queue).into_iter().skip(1).collect();
+        assert_eq!(items, sessions(&["failed", "done", "busy"]));
+    }
+
+    #[test]
+    fn selectable_cache_follows_expansion_and_clamps() {
+        let proxy = ProxySnapshot {
+            fetched_at: Some(Instant::now()),
+            queue: serde_json::from_value(json!({
+                "clients": [{"client_id": "c1", "sessions": [{"session_id": "s1"}, {"session_id": "s2"}]}]
+            }))
+            .unwrap(),
+            ..ProxySnapshot::default()
+        };
+        let mut cache = SelectableCache::default();
+        let mut state = AppState::default();
+        assert_eq!(cache.items(&state, &proxy).len(), 1);
+
+        // Expanding rebuilds immediately, so navigation reaches the new rows
+        state.client_expanded.insert("c1".into());
+        assert_eq!(cache.items(&state, &proxy).len(), 3);
+        state.session_selected = 2;
+
+        state.client_expanded.remove("c1");
+        let count = cache.items(&state, &proxy).len();
+        state.clamp_session_selected(count);
+        assert_eq!((count, state.session_selected), (1, 0));
+    }
+
+    #[test]
+    fn model_error_trend_records_increments() {
+        let stats = |errors: u64| -> StatsResponse {
+            serde_json::from_value(json!({
+                "pools": [{"model": "m", "backends": [{"url": "http://a:8001", "errors": errors}]}]
+            }))
+            .unwrap()
+        };
+        let mut state = AppState::default();
+        for errors in [3, 5, 5, 1] {
+            state.record_model_errors(&stats(errors));
+        }
+        assert_eq!(state.model_error_trend["m"].deltas, [2, 0, 1]);
+    }
+
+    #[test]
+    fn clients_only_yields_client_rows_and_totals() {
+        let queue: QueueResponse = serde_json::from_value(json!({
+            "clients": [{"client_id": "c1", "sessions": [
+                {"session_id": "s1", "total_turns": 5, "completed_requests": 3, "in_flight_requests": 1},
+                {"session_id": "s2", "total_requests": 2, "completed_requests": 1, "failed_requests": 1}
+            ]}],
+            "orphan_sessions": [{"session_id": "s3"}]
+        }))
+        .unwrap();
+        let mut state = AppState { clients_only: true, ..AppState::default() };
+        state.client_expanded.insert("c1".into());
+        assert_eq!(state.build_selectable_items(&queue), vec![SelectableItem::Client("c1".into())]);
+        // s1 has 4 of 5 turns settled although only 3 completions remain listed
+        assert_eq!(
+            queue.clients[0].totals(),
+            ClientTotals { sessions: 2, turns_done: 5, turns_total: 7, active: 1, failed: 1 }
+        );
+    }
+
+    #[test]
+    fn snapshot_starts_neutral_before_first_poll() {
+        let snap = ProxySnapshot::default();
+        assert_eq!(snap.connected, Connection::Never);
+        assert!(!snap.connected.is_up());
+        assert_eq!(snap.connected.placeholder(), "Connecting\u{2026}");
+        assert_eq!(Connection::Down.placeholder(), "Proxy offline");
+    }
+
+    #[test]
+    fn partition_scope_drops_other_backends_and_their_turns() {
+        let proxy = crate::test_support::snapshot(
+            json!({"pools": [
+                {"model": "a", "backends": [
+                    {"url": "http://n1:8001", "partition": "gpu"},
+                    {"url": "http://n2:8001", "partition": "cpu"}
+                ]},
+                {"model": "b", "backends": [{"url": "http://n3:8001", "partition": "cpu"}]}
+            ]}),
+            json!({
+                "backends": [
+                    {"url": "http://n1:8001", "partition": "gpu"},
+                    {"url": "http://n2:8001", "partition": "cpu"}
+                ],
+                "sessions": [{"session_id": "s1", "completed_turns": [
+                    {"backend": "http://n1:8001"}, {"backend": "http://n2:8001"}, {}
+                ]}]
+            }),
+        );
+        let scoped = proxy.in_partition("gpu");
+        assert_eq!(scoped.stats.pools.len(), 1);
+        assert_eq!(scoped.stats.pools[0].backends.len(), 1);
+        assert_eq!(scoped.queue.backends.len(), 1);
+        assert_eq!(scoped.queue.sessions[0].completed_turns.len(), 2);
+        assert!(proxy.in_partition("tpu").stats.pools.is_empty());
+
+        let state = AppState::default();
+        assert!(matches!(state.scoped(&proxy), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn merge_page_joins_clients_split_across_pages() {
+        let mut queue: QueueResponse = serde_json::from_value(json!({
+            "summary": {"pending": 2, "in_flight": 1},
+            "backends": [{"url": "http://a:8001"}],
+            "clients": [{"client_id": "c1", "sessions": [{"session_id": "s1"}]}],
+            "next_cursor": "p2"
+        }))
+        .unwrap();
+        let page: QueueResponse = serde_json::from_value(json!({
+            "summary": {"pending": 3},
+            "backends": [{"url": "http://a:8001"}, {"url": "http://b:8001"}],
+            "clients": [
+                {"client_id": "c1", "sessions": [{"session_id": "s2"}]},
+                {"client_id": "c2", "sessions": [{"session_id": "s3"}]}
+            ]
+        }))
+        .unwrap();
+        queue.merge_page(page);
+        assert_eq!(queue.summary.pending, 2);
+        assert_eq!(queue.summary.in_flight, 1);
+        assert_eq!(queue.backends.len(), 2);
+        assert_eq!(queue.clients.len(), 2);
+        assert_eq!(queue.clients[0].sessions.len(), 2);
+        assert!(queue.next_cursor.is_none());
+    }
+
+    #[test]
+    fn flat_sessions_ignores_client_grouping() {
+        let queue: QueueResponse = serde_json::from_value(json!({
+            "clients": [{"client_id": "c1", "sessions": [{"session_id": "s1"}]}],
+            "sessions": [{"session_id": "s1"}, {"session_id": "s2"}]
+        }))
+        .unwrap();
+        let mut state = AppState::default();
+        assert_eq!(state.build_selectable_items(&queue), vec![SelectableItem::Client("c1".into())]);
+        state.flat_sessions = true;
+        assert_eq!(
+            state.build_selectable_items(&queue),
+            vec![SelectableItem::Session("s1".into()), SelectableItem::Session("s2".into())]
+        );
+    }
+
+    #[test]
+    fn session_finished_when_all_requests_complete() {
+        let mut sess: SessionGroup = serde_json::from_value(json!({
+            "session_id": "s1", "total_requests": 3, "completed_requests": 3
+        }))
+        .unwrap();
+        assert!(sess.is_finished());
+        // Cleanup shrank completed_requests; total_turns survives it
+        sess.total_turns = 4;
+        sess.completed_requests = 1;
+        assert!(sess.is_finished());
+        sess.failed_requests = 1;
+        assert!(!sess.is_finished());
+        assert!(sess.is_settled());
+        sess.completed_requests = 4;
+        sess.pending_requests = 1;
+        assert!(!sess.is_finished());
+        assert!(!sess.is_settled());
+        assert!(!SessionGroup::default().is_finished());
+    }
+
+    #[test]
+    fn distinct_backends_ignores_unreported_turns() {
+        let sess: SessionGroup = serde_json::from_value(json!({
+            "session_id": "s1",
+            "completed_turns": [
+                {"backend": "http://gpu:8001"},
+                {"backend": "http://gpu:8002"},
+                {"backend": "http://gpu:8001"},
+                {}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(sess.distinct_backends(), 2);
+        assert_eq!(SessionGroup::default().distinct_backends(), 0);
+    }
+
+    #[test]
+    fn wait_ratio_prefers_backend_time_and_guards_zero() {
+        let sess: SessionGroup = serde_json::from_value(json!({
+            "completed_turns": [
+                {"wait_time_ms": 300.0, "processing_time_ms": 500.0, "backend_time_ms": 100.0},
+                {"wait_time_ms": 100.0, "processing_time_ms": 100.0}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(sess.wait_ratio(), Some(2.0));
+        assert!(sess.is_queue_starved());
+
+        let idle: SessionGroup = serde_json::from_value(json!({
+            "completed_turns": [{"wait_time_ms": 50.0}]
+        }))
+        .unwrap();
+        assert_eq!(idle.wait_ratio(), None);
+        assert!(!idle.is_queue_starved());
+        assert_eq!(SessionGroup::default().wait_ratio(), None);
+    }
+
+    #[test]
+    fn idle_watch_waits_for_a_drained_quiet_proxy() {
+        let busy = crate::test_support::snapshot(
+            json!({}),
+            json!({
+                "summary": {"pending": 0, "in_flight": 1},
+                "sessions": [{"session_id": "s1", "failed_requests": 1}]
+            }),
+        );
+        let mut drained = crate::test_support::snapshot(json!({}), json!({"summary": {"pending": 0, "in_flight": 0}}));
+        let t0 = Instant::now();
+        let secs = Duration::from_secs;
+        let mut watch = IdleWatch::default();
+
+        assert_eq!(watch.observe(&busy, t0), None);
+        // Idle time counts from the last poll that saw work
+        assert_eq!(watch.observe(&drained, t0 + secs(5)), Some(secs(5)));
+        assert_eq!(watch.observe(&drained, t0 + secs(20)), Some(secs(20)));
+        // A completion restarts the clock even with nothing queued
+        drained.turns_completed = 1;
+        assert_eq!(watch.observe(&drained, t0 + secs(25)), Some(secs(0)));
+        // A dropped connection neither counts as idle nor resets the clock
+        drained.connected = Connection::Down;
+        assert_eq!(watch.observe(&drained, t0 + secs(30)), None);
+        drained.connected = Connection::Up;
+        assert_eq!(watch.observe(&drained, t0 + secs(35)), Some(secs(10)));
+        // The session that failed has since been cleaned up
+        assert_eq!(watch.failed_requests(), 1);
+    }
+
+    #[test]
+    fn bells_are_rate_limited() {
+        let mut state = AppState { bell_interval: Duration::from_secs(30), ..AppState::default() };
+        let t0 = Instant::now();
+        assert!(state.take_bell(t0));
+        assert!(!state.take_bell(t0 + Duration::from_secs(5)));
+        assert!(!state.take_bell(t0 + Duration::from_secs(29)));
+        assert!(state.take_bell(t0 + Duration::from_secs(30)));
+        // A swallowed bell doesn't restart the interval
+        assert!(!state.take_bell(t0 + Duration::from_secs(45)));
+        assert!(state.take_bell(t0 + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn acks_hold_per_alert_until_the_condition_clears() {
+        let proxy = |down: &[&str]| ProxySnapshot {
+            connected: Connection::Up,
+            stats: serde_json::from_value(json!({"pools": [{"model": "m", "backends": [
+                {"url": "http://gpu:8001", "healthy": !down.contains(&"8001")},
+                {"url": "http://gpu:8002", "healthy": !down.contains(&"8002")}
+            ]}]}))
+            .unwrap(),
+            ..ProxySnapshot::default()
+        };
+        let down = |port: &str| Alert::BackendDown(format!("http://gpu:{}", port));
+        let mut state = AppState::default();
+        state.update_alerts(&proxy(&["8001"]));
+        assert!(state.alert_unacked(&down("8001")));
+        assert_eq!(state.ack_alerts(), 1);
+        assert!(!state.alert_unacked(&down("8001")));
+
+        // A different backend still fires; the acked one stays quiet
+        state.update_alerts(&proxy(&["8001", "8002"]));
+        assert!(state.alert_unacked(&down("8002")));
+        assert!(!state.alert_unacked(&down("8001")));
+
+        // Recovering clears the ack, so going down again fires
+        state.update_alerts(&proxy(&[]));
+        assert!(state.alerts.is_empty() && state.acked_alerts.is_empty());
+        state.update_alerts(&proxy(&["8001"]));
+        assert!(state.alert_unacked(&down("8001")));
+    }
+
+    #[test]
+    fn new_failures_expand_until_collapsed_by_hand() {
+        let queue = |failed: u64| -> QueueResponse {
+            serde_json::from_value(json!({
+                "clients": [{"client_id": "c1", "sessions": [
+                    {"session_id": "ok"},
+                    {"session_id": "bad", "failed_requests": failed}
+                ]}]
+            }))
+            .unwrap()
+        };
+        let mut state = AppState { expand_failed: true, ..AppState::default() };
+        state.expand_new_failures(&queue(1));
+        assert!(state.session_expanded.contains("bad") && state.client_expanded.contains("c1"));
+        assert!(!state.session_expanded.contains("ok"));
+
+        // A manual collapse holds while the failure count is unchanged
+        state.session_expanded.remove("bad");
+        state.expand_new_failures(&queue(1));
+        assert!(!state.session_expanded.contains("bad"));
+        state.expand_new_failures(&queue(2));
+        assert!(state.session_expanded.contains("bad"));
+
+        let mut off = AppState::default();
+        off.expand_new_failures(&queue(1));
+        assert!(off.session_expanded.is_empty());
+    }
+
+    #[test]
+    fn health_weights_parse_four_non_negative_numbers() {
+        assert_eq!("40,25,20,15".parse::<HealthWeights>(), Ok(HealthWeights::default()));
+        assert_eq!(
+            "1, 0, 0, 0".parse::<HealthWeights>(),
+            Ok(HealthWeights { healthy: 1.0, errors: 0.0, latency: 0.0, headroom: 0.0 })
+        );
+        assert!("40,25,20".parse::<HealthWeights>().is_err());
+        assert!("40,-1,20,15".parse::<HealthWeights>().is_err());
+        assert!("0,0,0,0".parse::<HealthWeights>().is_err());
+    }
+
+    #[test]
+    fn completion_stall_needs_silence_and_work_in_flight() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let threshold = Duration::from_secs(60);
+        let tp = ThroughputSnapshot {
+            enabled: true,
+            last_completion: Some(start + Duration::from_secs(10)),
+            ..ThroughputSnapshot::default()
+        };
+        let at = |secs| start + Duration::from_secs(secs);
+        assert_eq!(tp.completion_stall(3, start, at(100), threshold), Some(Duration::from_secs(90)));
+        assert_eq!(tp.completion_stall(3, start, at(50), threshold), None);
+        // Idle proxy: silence is expected
+        assert_eq!(tp.completion_stall(0, start, at(100), threshold), None);
+        // Old artifacts count from when the dashboard started
+        assert_eq!(tp.completion_stall(3, at(80), at(100), threshold), None);
+        let never = ThroughputSnapshot { enabled: true, ..ThroughputSnapshot::default() };
+        assert_eq!(never.completion_stall(3, start, at(60), threshold), Some(threshold));
+        assert_eq!(ThroughputSnapshot::default().completion_stall(3, start, at(100), threshold), None);
+    }
+
+    #[test]
+    fn cadence_lags_only_well_past_target() {
+        let snap = |cadence: Option<u64>, target: u64| ProxySnapshot {
+            update_cadence: cadence.map(Duration::from_millis),
+            target_interval: Duration::from_millis(target),
+            ..ProxySnapshot::default()
+        };
+        assert_eq!(snap(Some(6_000), 2_000).lagging_cadence(), Some(Duration::from_secs(6)));
+        assert_eq!(snap(Some(2_400), 2_000).lagging_cadence(), None);
+        // Proportionally slow but under a second behind
+        assert_eq!(snap(Some(1_200), 500).lagging_cadence(), None);
+        assert_eq!(snap(None, 2_000).lagging_cadence(), None);
+        assert_eq!(snap(Some(6_000), 0).lagging_cadence(), None);
+    }
+
+    #[test]
+    fn clock_skew_warns_past_threshold_either_way() {
+        let skewed = |secs: Option<f64>| ProxySnapshot { clock_skew_secs: secs, ..ProxySnapshot::default() };
+        assert_eq!(skewed(None).significant_clock_skew(), None);
+        assert_eq!(skewed(Some(0.4)).significant_clock_skew(), None);
+        assert_eq!(skewed(Some(-3.5)).significant_clock_skew(), Some(-3.5));
+        assert_eq!(skewed(Some(12.0)).significant_clock_skew(), Some(12.0));
+    }
+
+    #[test]
+    fn payload_bytes_sum_reporting_turns_only() {
+        let sess: SessionGroup = serde_json::from_value(json!({
+            "session_id": "s1",
+            "completed_turns": [
+                {"request_bytes": 2048, "response_bytes": 300},
+                {"request_bytes": 4096},
+                {}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            sess.payload_bytes(),
+            Some(PayloadBytes { request: 6144, response: 300, turns: 2 })
+        );
+        assert_eq!(SessionGroup::default().payload_bytes(), None);
+    }
+
+    #[test]
+    fn compatibility_info_lists_aliases() {
+        let info = compatibility_info("0.1.0");
+        assert!(info.starts_with("0.1.0\nproxy schema versions: 1"));
+        assert!(info.contains("  queue.sessions <- episodes"));
+        assert_eq!(info.lines().filter(|l| l.contains(" <- ")).count(), SCHEMA_ALIASES.len());
+    }
+
+    #[test]
+    fn client_idle_requires_no_work_and_no_recent_turns() {
+        let client: ClientGroup = serde_json::from_value(json!({
+            "client_id": "c1",
+            "sessions": [{"session_id": "s1", "completed_turns": [{"completed_at": 1000.0}]}]
+        }))
+        .unwrap();
+        assert!(!client.is_idle(1030.0, 60.0));
+        assert!(client.is_idle(1100.0, 60.0));
+        assert!(client.is_idle(1030.0, 0.0));
+
+        let mut busy = client.clone();
+        busy.sessions[0].pending_requests = 1;
+        assert!(!busy.is_idle(1100.0, 60.0));
+    }
+
+    #[test]
+    fn health_summary_lists_reported_counts() {
+        let health: HealthResponse = serde_json::from_value(json!({
+            "status": "ok", "healthy": 3, "backends": 4, "pending": 2
+        }))
+        .unwrap();
+        assert_eq!(health.summary(), "ok, 3/4 backends healthy, 2 pending");
+        assert_eq!(HealthResponse::default().summary(), "");
+    }
+
+    #[test]
+    fn session_cost_sums_turns_with_tokens() {
+        let sess: SessionGroup = serde_json::from_value(json!({
+            "session_id": "s1",
+            "completed_turns": [
+                {"prompt_tokens": 2000, "completion_tokens": 500},
+                {"input_tokens": 1000},
+                {}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(sess.estimated_cost(&PRICING), Some(1.0 + 1.0 + 0.5));
+    }
+
+    #[test]
+    fn client_turn_rate_over_window() {
+        let client: ClientGroup = serde_json::from_value(json!({
+            "client_id": "c1",
+            "sessions": [
+                {"completed_turns": [{"completed_at": 1000.0}, {"completed_at": 1030.0}]},
+                {"completed_turns": [{"completed_at": 1060.0}, {"completed_at": 100.0}]}
+            ]
+        }))
+        .unwrap();
+        // 3 turns in window spanning 60s -> 2 intervals per minute
+        assert_eq!(client.turns_per_minute(1060.0, 300.0), Some(2.0));
+        // Only one turn left in a narrow window
+        assert_eq!(client.turns_per_minute(1060.0, 10.0), None);
+    }
+
+    #[test]
+    fn cost_is_none_without_token_data() {
+        let queue: QueueResponse = serde_json::from_value(json!({
+            "clients": [{"client_id": "c1", "sessions": [{"completed_turns": [{}]}]}]
+        }))
+        .unwrap();
+        assert_eq!(queue.estimated_cost(&PRICING), None);
+    }
 }
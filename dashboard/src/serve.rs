@@ -0,0 +1,105 @@
+//! Minimal HTTP endpoint exposing the collected metrics (`--serve`).
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::export::MetricsSummary;
+use crate::model::{ProxySnapshot, ThroughputSnapshot};
+
+/// How long a client may take to send its request line or accept the response.
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Bind `addr` and serve `/metrics` (Prometheus text) and `/metrics.json`
+/// from a background thread. Binding happens up front so a bad address
+/// fails before the TUI takes over the terminal. With a `partition`, backend
+/// figures cover only that partition, as in the TUI.
+pub fn spawn_metrics_server(
+    addr: &str,
+    proxy: Arc<Mutex<ProxySnapshot>>,
+    throughput: Arc<Mutex<ThroughputSnapshot>>,
+    gap_trim: f64,
+    partition: Option<String>,
+) -> io::Result<thread::JoinHandle<()>> {
+    let listener = TcpListener::bind(addr)?;
+    let partition: Option<Arc<str>> = partition.map(Arc::from);
+    Ok(thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // A connection per thread, so a slow client can't hold up the next scrape
+            let (proxy, throughput, partition) = (Arc::clone(&proxy), Arc::clone(&throughput), partition.clone());
+            thread::spawn(move || {
+                // A broken client only loses its own response
+                let _ = handle(stream, &proxy, &throughput, gap_trim, partition.as_deref());
+            });
+        }
+    }))
+}
+
+fn handle(
+    mut stream: TcpStream,
+    proxy: &Mutex<ProxySnapshot>,
+    throughput: &Mutex<ThroughputSnapshot>,
+    gap_trim: f64,
+    partition: Option<&str>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let (method, path) = parse_request_line(&request_line);
+
+    let metrics = || {
+        let proxy = match partition {
+            Some(partition) => proxy.lock().unwrap().in_partition(partition),
+            None => proxy.lock().unwrap().clone(),
+        };
+        let throughput = throughput.lock().unwrap().clone();
+        MetricsSummary::new(&proxy, &throughput, gap_trim)
+    };
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => ("200 OK", "text/plain; version=0.0.4", metrics().prometheus()),
+        ("GET", "/metrics.json") => (
+            "200 OK",
+            "application/json",
+            serde_json::to_string(&metrics()).unwrap_or_default(),
+        ),
+        ("GET", _) => ("404 Not Found", "text/plain", "try /metrics or /metrics.json\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain", "only GET is supported\n".to_string()),
+    };
+    let allow = if status.starts_with("405") { "Allow: GET\r\n" } else { "" };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        allow,
+        body,
+    )
+}
+
+/// Method and path of a request line like "GET /metrics?x=1 HTTP/1.1", with
+/// any query string or fragment dropped so scrapers may add parameters.
+fn parse_request_line(line: &str) -> (&str, &str) {
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let target = parts.next().unwrap_or("/");
+    let path = target.split(['?', '#']).next().unwrap_or(target);
+    (method, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_line_drops_the_query_string() {
+        assert_eq!(parse_request_line("GET /metrics HTTP/1.1\r\n"), ("GET", "/metrics"));
+        assert_eq!(parse_request_line("GET /metrics?foo=1 HTTP/1.1\r\n"), ("GET", "/metrics"));
+        assert_eq!(parse_request_line("POST /metrics.json#x HTTP/1.1\r\n"), ("POST", "/metrics.json"));
+        assert_eq!(parse_request_line(""), ("", "/"));
+    }
+}
@@ -0,0 +1,216 @@
+//! State-change logging to the local system log (`--log-syslog`).
+//!
+//! Messages go to the `/dev/log` datagram socket in RFC 3164 form, which
+//! both syslog daemons and journald accept, so no extra dependency is needed.
+//! The socket is Unix-only; elsewhere `--log-syslog` fails at startup.
+
+use std::io;
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+use std::time::Duration;
+
+use crate::model::{Connection, ProxySnapshot};
+
+/// Socket the local syslog daemon (or journald) listens on.
+#[cfg(unix)]
+const SYSLOG_SOCKET: &str = "/dev/log";
+/// Facility `user`, per RFC 3164.
+const FACILITY_USER: u8 = 1;
+const TAG: &str = "agent-dashboard";
+
+/// Syslog severities used for state changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error = 3,
+    Warning = 4,
+    Notice = 5,
+    Info = 6,
+}
+
+/// A state change worth recording.
+#[derive(Debug, PartialEq)]
+pub struct StateEvent {
+    pub severity: Severity,
+    pub text: String,
+}
+
+impl StateEvent {
+    fn new(severity: Severity, text: impl Into<String>) -> Self {
+        Self { severity, text: text.into() }
+    }
+}
+
+/// Tracks proxy connectivity and the diagnosis between polls and reports
+/// what changed, alongside the backend health flips the collector detects.
+#[derive(Default)]
+pub struct EventDetector {
+    connection: Connection,
+    diagnosis: Option<&'static str>,
+    stalled: bool,
+}
+
+impl EventDetector {
+    /// Events for one new poll, given the diagnosis computed from it.
+    pub fn observe(&mut self, snap: &ProxySnapshot, diagnosis: &'static str) -> Vec<StateEvent> {
+        let mut events = Vec::new();
+
+        match (self.connection, snap.connected) {
+            (Connection::Never, Connection::Up) => events.push(StateEvent::new(Severity::Info, "proxy connected")),
+            (Connection::Down, Connection::Up) => events.push(StateEvent::new(Severity::Notice, "proxy reconnected")),
+            (Connection::Up | Connection::Never, Connection::Down) => {
+                events.push(StateEvent::new(Severity::Error, "proxy unreachable"))
+            }
+            _ => {}
+        }
+        self.connection = snap.connected;
+
+        for t in &snap.health_transitions {
+            events.push(if t.healthy {
+                StateEvent::new(Severity::Notice, format!("backend {} healthy", t.url))
+            } else {
+                StateEvent::new(Severity::Warning, format!("backend {} unhealthy", t.url))
+            });
+        }
+
+        for c in &snap.fleet_changes {
+            let text = if c.added {
+                format!("backend {} added to {}", c.url, c.model)
+            } else {
+                format!("backend {} removed from {}", c.url, c.model)
+            };
+            events.push(StateEvent::new(Severity::Notice, text));
+        }
+
+        // Diagnosis from stale data isn't meaningful
+        if snap.connected.is_up() {
+            if let Some(prev) = self.diagnosis.filter(|prev| *prev != diagnosis) {
+                let severity = if diagnosis == "UNHEALTHY" { Severity::Warning } else { Severity::Info };
+                events.push(StateEvent::new(severity, format!("diagnosis {} -> {}", prev, diagnosis)));
+            }
+            self.diagnosis = Some(diagnosis);
+        }
+        events
+    }
+
+    /// An event when completions stall (see `ThroughputSnapshot::completion_stall`)
+    /// or resume, checked every tick rather than per poll.
+    pub fn observe_stall(&mut self, stall: Option<Duration>, in_flight: u64) -> Option<StateEvent> {
+        let event = match (self.stalled, stall) {
+            (false, Some(silence)) => Some(StateEvent::new(
+                Severity::Warning,
+                format!("no completions for {}s with {} requests in flight", silence.as_secs(), in_flight),
+            )),
+            (true, None) => Some(StateEvent::new(Severity::Notice, "completions resumed")),
+            _ => None,
+        };
+        self.stalled = stall.is_some();
+        event
+    }
+}
+
+/// Connection to the local syslog socket.
+#[cfg(unix)]
+pub struct SyslogSink {
+    socket: UnixDatagram,
+}
+
+#[cfg(unix)]
+impl SyslogSink {
+    /// Connect up front so a missing syslog socket fails before the TUI starts.
+    pub fn connect() -> io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket
+            .connect(SYSLOG_SOCKET)
+            .map_err(|e| io::Error::new(e.kind(), format!("cannot open syslog socket {}: {}", SYSLOG_SOCKET, e)))?;
+        Ok(Self { socket })
+    }
+
+    /// Send one event. Logging is best-effort: a full or restarted daemon
+    /// must not disturb the dashboard.
+    pub fn send(&self, event: &StateEvent) {
+        let _ = self.socket.send(format_message(event, std::process::id()).as_bytes());
+    }
+}
+
+#[cfg(not(unix))]
+pub struct SyslogSink;
+
+#[cfg(not(unix))]
+impl SyslogSink {
+    pub fn connect() -> io::Result<Self> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "--log-syslog needs a Unix syslog socket"))
+    }
+
+    pub fn send(&self, _event: &StateEvent) {}
+}
+
+/// RFC 3164 message without a timestamp; the daemon stamps it on receipt.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn format_message(event: &StateEvent, pid: u32) -> String {
+    let priority = FACILITY_USER * 8 + event.severity as u8;
+    format!("<{}>{}[{}]: {}", priority, TAG, pid, event.text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{FleetChange, HealthTransition};
+
+    #[test]
+    fn detector_reports_connection_health_and_diagnosis_changes() {
+        let mut detector = EventDetector::default();
+        let mut snap = ProxySnapshot { connected: Connection::Up, ..ProxySnapshot::default() };
+        assert_eq!(detector.observe(&snap, "IDLE"), vec![StateEvent::new(Severity::Info, "proxy connected")]);
+        assert!(detector.observe(&snap, "IDLE").is_empty());
+
+        let unreachable = ProxySnapshot { connected: Connection::Down, ..ProxySnapshot::default() };
+        assert_eq!(
+            EventDetector::default().observe(&unreachable, "IDLE"),
+            vec![StateEvent::new(Severity::Error, "proxy unreachable")]
+        );
+
+        snap.health_transitions = vec![HealthTransition { url: "http://gpu:8001".into(), healthy: false }];
+        assert_eq!(
+            detector.observe(&snap, "UNHEALTHY"),
+            vec![
+                StateEvent::new(Severity::Warning, "backend http://gpu:8001 unhealthy"),
+                StateEvent::new(Severity::Warning, "diagnosis IDLE -> UNHEALTHY"),
+            ]
+        );
+
+        snap.health_transitions.clear();
+        snap.fleet_changes = vec![FleetChange { model: "m".into(), url: "http://gpu:8002".into(), added: true, at: 0.0 }];
+        assert_eq!(
+            detector.observe(&snap, "UNHEALTHY"),
+            vec![StateEvent::new(Severity::Notice, "backend http://gpu:8002 added to m")]
+        );
+
+        snap.fleet_changes.clear();
+        snap.connected = Connection::Down;
+        assert_eq!(detector.observe(&snap, "IDLE"), vec![StateEvent::new(Severity::Error, "proxy unreachable")]);
+        snap.connected = Connection::Up;
+        assert_eq!(
+            detector.observe(&snap, "UNHEALTHY"),
+            vec![StateEvent::new(Severity::Notice, "proxy reconnected")]
+        );
+    }
+
+    #[test]
+    fn stall_is_reported_once_until_completions_resume() {
+        let mut detector = EventDetector::default();
+        let stall = Some(Duration::from_secs(300));
+        assert_eq!(
+            detector.observe_stall(stall, 4),
+            Some(StateEvent::new(Severity::Warning, "no completions for 300s with 4 requests in flight"))
+        );
+        assert_eq!(detector.observe_stall(stall, 4), None);
+        assert_eq!(detector.observe_stall(None, 4), Some(StateEvent::new(Severity::Notice, "completions resumed")));
+        assert_eq!(detector.observe_stall(None, 0), None);
+    }
+
+    #[test]
+    fn messages_carry_user_facility_priority() {
+        let event = StateEvent::new(Severity::Warning, "backend x unhealthy");
+        assert_eq!(format_message(&event, 42), "<12>agent-dashboard[42]: backend x unhealthy");
+    }
+}
@@ -0,0 +1,73 @@
+//! Fixtures shared by unit tests.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::thread;
+
+use serde_json::Value;
+
+use crate::model::{Connection, ProxySnapshot, QueueResponse, StatsResponse};
+
+/// A connected snapshot built from raw `/stats` and `/queue/status` JSON,
+/// so fixtures exercise the same serde defaults the collector does.
+pub fn snapshot(stats: Value, queue: Value) -> ProxySnapshot {
+    ProxySnapshot {
+        connected: Connection::Up,
+        stats: serde_json::from_value::<StatsResponse>(stats).expect("valid stats fixture"),
+        queue: serde_json::from_value::<QueueResponse>(queue).expect("valid queue fixture"),
+        ..ProxySnapshot::default()
+    }
+}
+
+/// Raw contents of a file under `tests/fixtures/`.
+pub fn fixture(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("reading {}: {}", path.display(), e))
+}
+
+/// A local HTTP server standing in for the proxy: answers each path (query
+/// string ignored) with a fixed JSON body, and anything else with 404.
+pub struct MockProxy {
+    pub url: String,
+}
+
+impl MockProxy {
+    pub fn start(routes: &[(&str, String)]) -> MockProxy {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock proxy");
+        let url = format!("http://{}", listener.local_addr().expect("mock proxy address"));
+        let routes: HashMap<String, String> = routes.iter().map(|(p, b)| (p.to_string(), b.clone())).collect();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = respond(stream, &routes);
+            }
+        });
+        MockProxy { url }
+    }
+}
+
+fn respond(mut stream: TcpStream, routes: &HashMap<String, String>) -> io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain headers so the client sees a clean close
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let path = target.split('?').next().unwrap_or(target);
+    let (status, body) = match routes.get(path) {
+        Some(body) => ("200 OK", body.as_str()),
+        None => ("404 Not Found", ""),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body,
+    )
+}
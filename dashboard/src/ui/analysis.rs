@@ -1,46 +1,54 @@
+use std::collections::HashMap;
+
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph, Wrap};
 
-use crate::model::{SessionGroup, ProxySnapshot, QueueResponse};
+use crate::model::{AppState, BackendStats, CompletedTurn, LatencyStat, SessionGroup, ProxySnapshot, QueueBackend, QueueRequest, QueueResponse, StatsResponse, Verbosity};
+use super::backends::backend_mismatch;
+use super::format::{format_decimal, format_duration_ms, format_pct, Precision};
 use super::theme::ColorScheme;
 
 /// Diagnosis of the current system state.
-enum Diagnosis {
+pub enum Diagnosis {
     GpuBound,
     AgentBound,
+    ProxyBound,
     Balanced,
     Idle,
     Unhealthy,
 }
 
 impl Diagnosis {
-    fn label(&self) -> &'static str {
+    pub fn label(&self) -> &'static str {
         match self {
             Diagnosis::GpuBound => "GPU-BOUND",
             Diagnosis::AgentBound => "AGENT-BOUND",
+            Diagnosis::ProxyBound => "PROXY-BOUND",
             Diagnosis::Balanced => "BALANCED",
             Diagnosis::Idle => "IDLE",
             Diagnosis::Unhealthy => "UNHEALTHY",
         }
     }
 
-    fn color(&self, colors: &ColorScheme) -> Color {
+    pub fn color(&self, colors: &ColorScheme) -> Color {
         match self {
             Diagnosis::GpuBound => colors.status_error,
             Diagnosis::AgentBound => colors.status_warn,
+            Diagnosis::ProxyBound => colors.status_warn,
             Diagnosis::Balanced => colors.status_ok,
             Diagnosis::Idle => colors.accent,
             Diagnosis::Unhealthy => colors.status_warn,
         }
     }
 
-    fn advice(&self) -> &'static str {
+    pub fn advice(&self) -> &'static str {
         match self {
             Diagnosis::GpuBound => "All GPUs near capacity. Add replicas or reduce --num-parallel.",
             Diagnosis::AgentBound => "Agent overhead is high. Check obs/action timing in turn details.",
+            Diagnosis::ProxyBound => "Proxy overhead is high. Check proxy-side queuing and serialization.",
             Diagnosis::Balanced => "System running smoothly. Load well distributed.",
             Diagnosis::Idle => "GPUs underutilized. Can increase --num-parallel.",
             Diagnosis::Unhealthy => "Some GPUs offline. Check backend health.",
@@ -48,6 +56,31 @@ impl Diagnosis {
     }
 }
 
+/// A pipeline stage a turn's time is spent in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// Queued at the proxy.
+    Wait,
+    /// On the backend (processing time without backend timing).
+    Inference,
+    /// Proxy overhead around the backend call.
+    Proxy,
+    /// The agent between turns.
+    Agent,
+}
+
+impl Stage {
+    /// One-word label for the sessions column.
+    pub fn label(self) -> &'static str {
+        match self {
+            Stage::Wait => "wait",
+            Stage::Inference => "infer",
+            Stage::Proxy => "proxy",
+            Stage::Agent => "agent",
+        }
+    }
+}
+
 /// Aggregated pipeline timing stats across all turns.
 pub struct TimingStats {
     pub count: u64,
@@ -56,65 +89,230 @@ pub struct TimingStats {
     pub avg_wait_ms: f64,       // queue wait
     pub avg_proxy_ms: f64,      // processing - backend overhead
     pub avg_total_ms: f64,      // total_time_ms average
+    pub p95_inference_ms: f64,
+    pub p95_wait_ms: f64,
+    pub p95_proxy_ms: f64,
+    pub p95_total_ms: f64,
+    /// p95 of each turn's total plus the agent gap before it, where measured.
+    pub p95_turn_ms: f64,
     pub has_backend_time: bool,  // whether backend_time_ms data is available
     pub gap_count: u64,          // how many gap measurements
+    pub gap_samples: Vec<f64>,   // every gap, ascending
+}
+
+/// Share of turn time spent in the proxy at which the diagnosis is PROXY-BOUND.
+pub const PROXY_BOUND_RATIO: f64 = 0.3;
+
+/// Mean agent gap above the trimmed mean by this factor counts as skewed by outliers.
+pub const GAP_OUTLIER_RATIO: f64 = 1.5;
+
+impl TimingStats {
+    /// Mean agent gap after dropping the largest `trim` fraction of gaps
+    /// (at least one gap when `trim` > 0, always keeping one).
+    pub fn trimmed_gap_ms(&self, trim: f64) -> f64 {
+        let n = self.gap_samples.len();
+        if n == 0 {
+            return 0.0;
+        }
+        let drop = if trim > 0.0 { ((n as f64 * trim).ceil() as usize).min(n - 1) } else { 0 };
+        let kept = &self.gap_samples[..n - drop];
+        kept.iter().sum::<f64>() / kept.len() as f64
+    }
+
+    pub fn inference_ms(&self, stat: LatencyStat) -> f64 {
+        match stat {
+            LatencyStat::Mean => self.avg_inference_ms,
+            LatencyStat::P95 => self.p95_inference_ms,
+        }
+    }
+
+    pub fn wait_ms(&self, stat: LatencyStat) -> f64 {
+        match stat {
+            LatencyStat::Mean => self.avg_wait_ms,
+            LatencyStat::P95 => self.p95_wait_ms,
+        }
+    }
+
+    pub fn proxy_ms(&self, stat: LatencyStat) -> f64 {
+        match stat {
+            LatencyStat::Mean => self.avg_proxy_ms,
+            LatencyStat::P95 => self.p95_proxy_ms,
+        }
+    }
+
+    /// A whole turn cycle: total time plus the agent gap before it. The p95
+    /// is taken over per-turn cycles, since p95s don't add.
+    pub fn turn_ms(&self, stat: LatencyStat) -> f64 {
+        match stat {
+            LatencyStat::Mean => self.avg_total_ms + self.avg_gap_ms,
+            LatencyStat::P95 => self.p95_turn_ms,
+        }
+    }
+
+    /// Agent gap; the p95 reads straight off the sorted samples.
+    pub fn gap_ms(&self, stat: LatencyStat) -> f64 {
+        match stat {
+            LatencyStat::Mean => self.avg_gap_ms,
+            LatencyStat::P95 => p95(self.gap_samples.clone()),
+        }
+    }
+
+    /// The stage with the largest mean time per turn. The agent gap only
+    /// competes once one has been measured. `None` without turns.
+    pub fn dominant_stage(&self) -> Option<Stage> {
+        if self.count == 0 {
+            return None;
+        }
+        let mut stages = vec![
+            (Stage::Wait, self.avg_wait_ms),
+            (Stage::Inference, self.avg_inference_ms),
+            (Stage::Proxy, self.avg_proxy_ms),
+        ];
+        if self.gap_count > 0 {
+            stages.push((Stage::Agent, self.avg_gap_ms));
+        }
+        stages
+            .into_iter()
+            .filter(|(_, ms)| *ms > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(stage, _)| stage)
+    }
+
+    /// Mean proxy overhead as a fraction of mean turn time. `None` unless the
+    /// proxy reports backend time, since overhead is measured against it.
+    pub fn proxy_overhead(&self) -> Option<f64> {
+        (self.has_backend_time && self.avg_total_ms > 0.0).then(|| self.avg_proxy_ms / self.avg_total_ms)
+    }
+
+    /// Whether a few large gaps pull the mean well above the trimmed mean.
+    pub fn gap_outliers(&self, trim: f64) -> bool {
+        let trimmed = self.trimmed_gap_ms(trim);
+        trimmed > 0.0 && self.avg_gap_ms > trimmed * GAP_OUTLIER_RATIO
+    }
 }
 
 /// Collect timing stats from all sessions across the queue.
 pub fn collect_timing_stats(queue: &QueueResponse) -> TimingStats {
-    let all_sessions = collect_all_sessions(queue);
+    collect_timing_stats_where(queue, |_| true)
+}
 
+/// Collect timing stats from the turns matching `include` (e.g. one partition's backends).
+pub fn collect_timing_stats_where(queue: &QueueResponse, include: impl Fn(&CompletedTurn) -> bool) -> TimingStats {
+    timing_stats_over(&collect_all_sessions(queue), include)
+}
+
+/// Collect timing stats from the sessions running `model`.
+pub fn collect_timing_stats_for_model(queue: &QueueResponse, model: &str) -> TimingStats {
+    let sessions: Vec<&SessionGroup> = collect_all_sessions(queue).into_iter().filter(|s| s.model == model).collect();
+    timing_stats_over(&sessions, |_| true)
+}
+
+/// Timing stats over one session's turns, for its dominant stage.
+pub fn session_timing_stats(sess: &SessionGroup) -> TimingStats {
+    timing_stats_over(&[sess], |_| true)
+}
+
+/// Fewest completed turns before a session is given a dominant stage.
+pub const MIN_STAGE_TURNS: u64 = 3;
+
+/// The stage a session spends most of its turn time in, or `None` with
+/// fewer than `MIN_STAGE_TURNS` completed turns.
+pub fn session_stage(sess: &SessionGroup) -> Option<Stage> {
+    Some(session_timing_stats(sess))
+        .filter(|t| t.count >= MIN_STAGE_TURNS)
+        .and_then(|t| t.dominant_stage())
+}
+
+/// Timing stats from the turns of `sessions` matching `include`; gaps are
+/// measured within each session.
+fn timing_stats_over(all_sessions: &[&SessionGroup], include: impl Fn(&CompletedTurn) -> bool) -> TimingStats {
     let mut count = 0_u64;
-    let mut sum_inference = 0.0_f64;
-    let mut sum_wait = 0.0_f64;
-    let mut sum_proxy = 0.0_f64;
-    let mut sum_total = 0.0_f64;
-    let mut sum_gap = 0.0_f64;
-    let mut gap_count = 0_u64;
+    let mut inference: Vec<f64> = Vec::new();
+    let mut wait: Vec<f64> = Vec::new();
+    let mut proxy: Vec<f64> = Vec::new();
+    let mut total: Vec<f64> = Vec::new();
+    let mut gaps: Vec<f64> = Vec::new();
+    let mut cycles: Vec<f64> = Vec::new();
     let mut has_backend = false;
 
-    for sess in &all_sessions {
+    for sess in all_sessions {
         for (i, turn) in sess.completed_turns.iter().enumerate() {
+            if !include(turn) {
+                continue;
+            }
             count += 1;
-            sum_wait += turn.wait_time_ms;
-            sum_total += turn.total_time_ms;
+            wait.push(turn.wait_time_ms);
+            total.push(turn.total_time_ms);
 
             if let Some(backend_ms) = turn.backend_time_ms {
                 has_backend = true;
-                sum_inference += backend_ms;
-                sum_proxy += (turn.processing_time_ms - backend_ms).max(0.0);
+                inference.push(backend_ms);
+                proxy.push((turn.processing_time_ms - backend_ms).max(0.0));
             } else {
-                sum_inference += turn.processing_time_ms;
+                inference.push(turn.processing_time_ms);
+                proxy.push(0.0);
             }
 
             // Gap: time between prev turn completion and this turn submission (within same session)
+            let mut cycle = turn.total_time_ms;
             if i > 0 {
                 let prev = &sess.completed_turns[i - 1];
                 if prev.completed_at > 0.0 && turn.submitted_at > 0.0 {
                     let gap = (turn.submitted_at - prev.completed_at) * 1000.0;
                     if gap >= 0.0 {
-                        sum_gap += gap;
-                        gap_count += 1;
+                        gaps.push(gap);
+                        cycle += gap;
                     }
                 }
             }
+            cycles.push(cycle);
         }
     }
 
+    gaps.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let gap_count = gaps.len() as u64;
     let c = count.max(1) as f64;
-    let g = gap_count.max(1) as f64;
+    let mean = |v: &[f64]| v.iter().sum::<f64>() / c;
     TimingStats {
         count,
-        avg_inference_ms: sum_inference / c,
-        avg_gap_ms: if gap_count > 0 { sum_gap / g } else { 0.0 },
-        avg_wait_ms: sum_wait / c,
-        avg_proxy_ms: if has_backend { sum_proxy / c } else { 0.0 },
-        avg_total_ms: sum_total / c,
+        avg_inference_ms: mean(&inference),
+        avg_gap_ms: if gap_count > 0 { gaps.iter().sum::<f64>() / gap_count as f64 } else { 0.0 },
+        avg_wait_ms: mean(&wait),
+        avg_proxy_ms: if has_backend { mean(&proxy) } else { 0.0 },
+        avg_total_ms: mean(&total),
+        p95_proxy_ms: if has_backend { p95(proxy) } else { 0.0 },
+        p95_inference_ms: p95(inference),
+        p95_wait_ms: p95(wait),
+        p95_total_ms: p95(total),
+        p95_turn_ms: p95(cycles),
         has_backend_time: has_backend,
         gap_count,
+        gap_samples: gaps,
     }
 }
 
+/// 95th percentile of `samples`, or 0 with none.
+fn p95(samples: Vec<f64>) -> f64 {
+    Percentiles::from_samples(samples).map_or(0.0, |p| p.p95)
+}
+
+/// Per-backend p95 inference latency over the completed turns each backend
+/// served, keyed by backend URL. The proxy only reports per-backend means.
+pub fn backend_p95_ms(queue: &QueueResponse) -> HashMap<String, f64> {
+    let mut samples: HashMap<String, Vec<f64>> = HashMap::new();
+    for sess in collect_all_sessions(queue) {
+        for turn in &sess.completed_turns {
+            if let Some(url) = &turn.backend {
+                samples
+                    .entry(url.clone())
+                    .or_default()
+                    .push(turn.backend_time_ms.unwrap_or(turn.processing_time_ms));
+            }
+        }
+    }
+    samples.into_iter().map(|(url, v)| (url, p95(v))).collect()
+}
+
 fn collect_all_sessions(queue: &QueueResponse) -> Vec<&SessionGroup> {
     let mut sessions = Vec::new();
 
@@ -140,33 +338,70 @@ fn collect_all_sessions(queue: &QueueResponse) -> Vec<&SessionGroup> {
     sessions
 }
 
-/// Draw the GPU Performance panel.
-pub fn draw_gpu_performance(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, colors: &ColorScheme) {
-    if !proxy.connected {
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .title(" GPU Performance ")
-            .border_style(Style::default().fg(colors.border_normal));
-        let msg = Paragraph::new(Line::from(Span::styled(
-            "  Proxy offline",
-            Style::default().fg(colors.text_disabled),
-        )))
-        .block(block);
-        frame.render_widget(msg, area);
-        return;
+/// Fleet-wide latency reference points, from healthy backends that have served requests.
+pub struct LatencyThresholds {
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+    pub count: usize,
+}
+
+/// Compute latency thresholds across all pools, or `None` if no backend qualifies.
+pub fn fleet_latency_thresholds(stats: &StatsResponse) -> Option<LatencyThresholds> {
+    let mut latencies: Vec<f64> = stats
+        .pools
+        .iter()
+        .flat_map(|p| p.backends.iter())
+        .filter(|b| b.healthy && b.requests > 0)
+        .map(|b| b.avg_latency_ms)
+        .collect();
+    if latencies.is_empty() {
+        return None;
     }
+    latencies.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let n = latencies.len();
+    let median_ms = if n.is_multiple_of(2) {
+        (latencies[n / 2 - 1] + latencies[n / 2]) / 2.0
+    } else {
+        latencies[n / 2]
+    };
+    Some(LatencyThresholds {
+        min_ms: latencies[0],
+        median_ms,
+        max_ms: latencies[n - 1],
+        count: n,
+    })
+}
 
-    // Collect per-backend stats from /stats pools
-    struct BackendInfo {
-        port: String,
-        healthy: bool,
-        gpu_load: u64,
-        inflight: u64,
-        requests: u64,
-        avg_latency_ms: f64,
+/// Per-backend row for the GPU Performance panel.
+pub struct GpuBackendRow {
+    pub url: String,
+    pub port: String,
+    pub healthy: bool,
+    pub gpu_load: u64,
+    /// False when `/queue/status` doesn't list this backend and `gpu_load`
+    /// is a placeholder 0.
+    pub load_reported: bool,
+    pub inflight: u64,
+    pub requests: u64,
+    pub avg_latency_ms: f64,
+    /// From the queue's completed turns; `None` if none were served here.
+    pub p95_latency_ms: Option<f64>,
+}
+
+impl GpuBackendRow {
+    pub fn latency_ms(&self, stat: LatencyStat) -> Option<f64> {
+        match stat {
+            LatencyStat::Mean => Some(self.avg_latency_ms),
+            LatencyStat::P95 => self.p95_latency_ms,
+        }
     }
+}
 
-    let mut backends: Vec<BackendInfo> = Vec::new();
+/// Collect per-backend stats from /stats pools, joined with queue gpu_load.
+pub fn collect_gpu_backends(proxy: &ProxySnapshot) -> Vec<GpuBackendRow> {
+    let mut backends = Vec::new();
+    let p95_map = backend_p95_ms(&proxy.queue);
 
     // Use stats pools for requests/errors/latency
     for pool in &proxy.stats.pools {
@@ -182,17 +417,155 @@ pub fn draw_gpu_performance(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot
             let queue_b = proxy.queue.backends.iter().find(|qb| qb.url == b.url);
             let gpu_load = queue_b.map_or(0, |qb| qb.gpu_load);
 
-            backends.push(BackendInfo {
+            backends.push(GpuBackendRow {
+                url: b.url.clone(),
                 port,
                 healthy: b.healthy,
                 gpu_load,
+                load_reported: queue_b.is_some(),
                 inflight: b.inflight,
                 requests: b.requests,
                 avg_latency_ms: b.avg_latency_ms,
+                p95_latency_ms: p95_map.get(&b.url).copied(),
             });
         }
     }
 
+    backends
+}
+
+/// Latency and load spread across the fleet.
+pub struct LoadSpread {
+    pub latency_diff_pct: f64,
+    pub load_min: u64,
+    pub load_max: u64,
+    pub even: bool,
+}
+
+impl LoadSpread {
+    pub fn label(&self) -> &'static str {
+        if self.even { "even" } else { "uneven" }
+    }
+}
+
+/// Spread between the fastest and slowest backend, if there is more than one to compare.
+pub fn compute_load_spread(backends: &[GpuBackendRow], thresholds: Option<&LatencyThresholds>) -> Option<LoadSpread> {
+    let t = thresholds?;
+    if backends.len() <= 1 || t.min_ms <= 0.0 {
+        return None;
+    }
+    let latency_diff_pct = ((t.max_ms - t.min_ms) / t.min_ms * 100.0).round();
+    let gpu_loads: Vec<u64> = backends
+        .iter()
+        .filter(|b| b.healthy && b.load_reported)
+        .map(|b| b.gpu_load)
+        .collect();
+    let load_min = gpu_loads.iter().copied().min().unwrap_or(0);
+    let load_max = gpu_loads.iter().copied().max().unwrap_or(0);
+    Some(LoadSpread {
+        latency_diff_pct,
+        load_min,
+        load_max,
+        even: load_max - load_min <= 1,
+    })
+}
+
+/// Load ratio over the rest of the fleet at which a backend counts as skewed.
+const LOAD_SKEW_RATIO: f64 = 2.0;
+
+/// A backend carrying disproportionate load.
+pub struct LoadOutlier {
+    pub port: String,
+    /// Its load relative to the average of the other healthy backends.
+    pub ratio: f64,
+}
+
+/// Find the most-loaded healthy backend if it carries at least `min_ratio`
+/// times the average load of the others. Needs at least two healthy backends
+/// that have served requests, so a cold or single-GPU fleet never fires.
+pub fn find_load_outlier(backends: &[GpuBackendRow], min_ratio: f64) -> Option<LoadOutlier> {
+    let active: Vec<&GpuBackendRow> = backends
+        .iter()
+        .filter(|b| b.healthy && b.load_reported && b.requests > 0)
+        .collect();
+    if active.len() < 2 {
+        return None;
+    }
+    let top = active.iter().max_by_key(|b| b.gpu_load)?;
+    let others_total: u64 = active.iter().map(|b| b.gpu_load).sum::<u64>() - top.gpu_load;
+    let others_avg = others_total as f64 / (active.len() - 1) as f64;
+    // An idle rest-of-fleet counts as load 1 so the ratio stays finite
+    let ratio = top.gpu_load as f64 / others_avg.max(1.0);
+    (top.gpu_load > 1 && ratio >= min_ratio).then(|| LoadOutlier {
+        port: top.port.clone(),
+        ratio,
+    })
+}
+
+/// Gap between proxy inflight and backend-reported load at which a backend is flagged.
+const INFLIGHT_DRIFT_THRESHOLD: u64 = 2;
+
+/// A backend whose proxy-side inflight count disagrees with its reported load.
+#[derive(Debug, PartialEq)]
+pub struct InflightDrift {
+    pub url: String,
+    /// Requests the proxy believes are outstanding.
+    pub inflight: u64,
+    /// Requests the backend reports it is running.
+    pub gpu_load: u64,
+}
+
+impl InflightDrift {
+    /// Positive when the proxy counts more than the backend reports (leaked
+    /// accounting or stuck requests), negative when the backend is busier.
+    pub fn delta(&self) -> i64 {
+        self.inflight as i64 - self.gpu_load as i64
+    }
+}
+
+/// Join `/stats` backends with `/queue` backends by URL and return the healthy
+/// ones whose inflight and gpu_load differ by more than `threshold`, largest
+/// gap first. Backends missing from either list are skipped.
+pub fn find_inflight_drift(stats: &[BackendStats], queue: &[QueueBackend], threshold: u64) -> Vec<InflightDrift> {
+    let mut drifts: Vec<InflightDrift> = stats
+        .iter()
+        .filter(|b| b.healthy)
+        .filter_map(|b| {
+            let qb = queue.iter().find(|qb| qb.url == b.url)?;
+            (b.inflight.abs_diff(qb.gpu_load) > threshold).then(|| InflightDrift {
+                url: b.url.clone(),
+                inflight: b.inflight,
+                gpu_load: qb.gpu_load,
+            })
+        })
+        .collect();
+    drifts.sort_by(|a, b| b.delta().abs().cmp(&a.delta().abs()).then(a.url.cmp(&b.url)));
+    drifts
+}
+
+/// Draw the GPU Performance panel.
+pub fn draw_gpu_performance(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppState, colors: &ColorScheme) {
+    let precision = state.precision;
+    if !proxy.connected.is_up() {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" GPU Performance ")
+            .border_style(Style::default().fg(colors.border_normal));
+        let msg = Paragraph::new(Line::from(Span::styled(
+            format!("  {}", proxy.connected.placeholder()),
+            Style::default().fg(colors.text_disabled),
+        )))
+        .block(block);
+        frame.render_widget(msg, area);
+        return;
+    }
+
+    let backends = collect_gpu_backends(proxy);
+    let stats_backends: Vec<BackendStats> = proxy.stats.pools.iter().flat_map(|p| p.backends.iter().cloned()).collect();
+    let drifts = find_inflight_drift(&stats_backends, &proxy.queue.backends, INFLIGHT_DRIFT_THRESHOLD);
+    // By full URL: the same port on two hosts is two backends
+    let drift_urls: Vec<&str> = drifts.iter().map(|d| d.url.as_str()).collect();
+
     let mut lines: Vec<Line> = Vec::new();
 
     // Header
@@ -200,7 +573,15 @@ pub fn draw_gpu_performance(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot
         Span::styled(
             format!(
                 "  {:<10} {:>6} {:>9} {:>9} {:>8} {:>8}",
-                "Backend", "Health", "GPU Load", "Inflight", "Reqs", "Avg Lat"
+                "Backend",
+                "Health",
+                "GPU Load",
+                "Inflight",
+                "Reqs",
+                match state.latency_stat {
+                    LatencyStat::Mean => "Avg Lat",
+                    LatencyStat::P95 => "P95 Lat",
+                }
             ),
             Style::default()
                 .fg(colors.table_header)
@@ -208,30 +589,22 @@ pub fn draw_gpu_performance(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot
         ),
     ]));
 
-    // Find slowest for highlighting
-    let valid_latencies: Vec<f64> = backends
+    // Find slowest for highlighting, by whichever statistic is shown
+    let thresholds = fleet_latency_thresholds(&proxy.stats);
+    let max_latency = backends
         .iter()
         .filter(|b| b.healthy && b.requests > 0)
-        .map(|b| b.avg_latency_ms)
-        .collect();
-    let max_latency = valid_latencies.iter().cloned().fold(0.0_f64, f64::max);
-    let min_latency = valid_latencies
-        .iter()
-        .cloned()
-        .fold(f64::MAX, f64::min);
+        .filter_map(|b| b.latency_ms(state.latency_stat))
+        .fold(0.0, f64::max);
 
     for b in &backends {
         let health = if b.healthy { "\u{2713}" } else { "\u{2717}" };
         let health_color = if b.healthy { colors.status_ok } else { colors.status_error };
-        let is_slowest =
-            b.healthy && b.requests > 0 && b.avg_latency_ms == max_latency && backends.len() > 1;
+        let latency = b.latency_ms(state.latency_stat).filter(|_| b.healthy && b.requests > 0);
+        let is_slowest = latency == Some(max_latency) && backends.len() > 1;
 
-        let lat_str = if b.healthy && b.requests > 0 {
-            format_latency(b.avg_latency_ms)
-        } else {
-            "-".to_string()
-        };
-        let load_str = if b.healthy {
+        let lat_str = latency.map_or("-".to_string(), |ms| format_duration_ms(ms, precision));
+        let load_str = if b.healthy && b.load_reported {
             b.gpu_load.to_string()
         } else {
             "-".to_string()
@@ -264,37 +637,84 @@ pub fn draw_gpu_performance(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot
                 " \u{2190} slowest",
                 Style::default().fg(colors.status_error),
             ));
+        } else if drift_urls.contains(&b.url.as_str()) {
+            spans.push(Span::styled(
+                " \u{2190} drift",
+                Style::default().fg(colors.status_warn),
+            ));
         }
         lines.push(Line::from(spans));
     }
 
-    // Summary line
-    if !valid_latencies.is_empty() && backends.len() > 1 && min_latency > 0.0 {
-        let diff_pct = ((max_latency - min_latency) / min_latency * 100.0).round();
-        let gpu_loads: Vec<u64> = backends
-            .iter()
-            .filter(|b| b.healthy)
-            .map(|b| b.gpu_load)
-            .collect();
-        let load_min = gpu_loads.iter().copied().min().unwrap_or(0);
-        let load_max = gpu_loads.iter().copied().max().unwrap_or(0);
-        let spread_label = if load_max - load_min <= 1 {
-            "even"
-        } else {
-            "uneven"
-        };
+    if let Some(partition) = state.partition.as_ref().filter(|_| backends.is_empty()) {
+        lines.push(Line::from(Span::styled(
+            format!("  No backends in partition {}", partition),
+            Style::default().fg(colors.text_disabled),
+        )));
+    }
+
+    // Models with nothing behind them would otherwise vanish from this panel
+    for model in proxy.stats.empty_pools() {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {} ", model), Style::default().fg(colors.text_primary)),
+            Span::styled("no backends registered", Style::default().fg(colors.status_error)),
+        ]));
+    }
 
+    // Summary line
+    if let Some(spread) = compute_load_spread(&backends, thresholds.as_ref()) {
         lines.push(Line::from(""));
         lines.push(Line::from(vec![
             Span::styled("  Spread: ", Style::default().fg(colors.text_primary)),
             Span::styled(
                 format!(
-                    "{:.0}% latency diff, load {}-{} ({})",
-                    diff_pct, load_min, load_max, spread_label
+                    "{} latency diff, load {}-{} ({})",
+                    format_pct(spread.latency_diff_pct, 0, precision), spread.load_min, spread.load_max, spread.label()
                 ),
                 Style::default().fg(colors.text_secondary),
             ),
         ]));
+
+        if !spread.even {
+            if let Some(outlier) = find_load_outlier(&backends, LOAD_SKEW_RATIO) {
+                lines.push(Line::from(vec![
+                    Span::styled("  Skew:   ", Style::default().fg(colors.text_primary)),
+                    Span::styled(
+                        format!(
+                            "backend :{} is {}\u{00d7} loaded \u{2014} check strategy or health",
+                            outlier.port,
+                            format_decimal(outlier.ratio, 1, precision)
+                        ),
+                        Style::default().fg(colors.status_warn),
+                    ),
+                ]));
+            }
+        }
+    }
+
+    if let Some(note) = backend_mismatch(proxy).note().filter(|_| !proxy.queue.backends.is_empty()) {
+        lines.push(Line::from(vec![
+            Span::styled("  Mismatch: ", Style::default().fg(colors.text_primary)),
+            Span::styled(note, Style::default().fg(colors.status_warn)),
+        ]));
+    }
+
+    if let Some(worst) = drifts.first() {
+        let port = worst.url.rsplit(':').next().unwrap_or(&worst.url);
+        let more = match drifts.len() {
+            1 => String::new(),
+            n => format!(" (+{} more)", n - 1),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("  Drift:  ", Style::default().fg(colors.text_primary)),
+            Span::styled(
+                format!(
+                    "backend :{} inflight {} vs load {}{} \u{2014} check proxy accounting or stuck requests",
+                    port, worst.inflight, worst.gpu_load, more
+                ),
+                Style::default().fg(colors.status_warn),
+            ),
+        ]));
     }
 
     let paragraph = Paragraph::new(lines)
@@ -309,15 +729,15 @@ pub fn draw_gpu_performance(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot
     frame.render_widget(paragraph, area);
 }
 
-/// Draw the Bottleneck Analysis panel.
-pub fn draw_bottleneck(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, colors: &ColorScheme) {
-    if !proxy.connected {
+/// Draw a bar chart of GPU load per backend, for the wall layout.
+pub fn draw_gpu_load_chart(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, colors: &ColorScheme) {
+    if !proxy.connected.is_up() {
         let block = Block::default()
             .borders(Borders::ALL)
-            .title(" Bottleneck Analysis ")
+            .title(" GPU Load ")
             .border_style(Style::default().fg(colors.border_normal));
         let msg = Paragraph::new(Line::from(Span::styled(
-            "  Proxy offline",
+            format!("  {}", proxy.connected.placeholder()),
             Style::default().fg(colors.text_disabled),
         )))
         .block(block);
@@ -325,6 +745,254 @@ pub fn draw_bottleneck(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, col
         return;
     }
 
+    let backends = collect_gpu_backends(proxy);
+    let bars: Vec<Bar> = backends
+        .iter()
+        .map(|b| {
+            let color = if b.healthy { colors.accent } else { colors.status_error };
+            Bar::default()
+                .label(Line::from(format!(":{}", b.port)))
+                .value(b.gpu_load)
+                .style(Style::default().fg(color))
+                .value_style(Style::default().fg(colors.text_primary).bg(color).add_modifier(Modifier::BOLD))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(7)
+        .bar_gap(2)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" GPU Load ")
+                .border_style(Style::default().fg(colors.border_focused)),
+        );
+
+    frame.render_widget(chart, area);
+}
+
+/// Latency percentiles over a set of samples.
+pub struct Percentiles {
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+impl Percentiles {
+    /// Nearest-rank percentiles, or `None` for no samples.
+    pub fn from_samples(mut samples: Vec<f64>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let rank = |p: f64| {
+            let idx = ((p / 100.0) * samples.len() as f64).ceil() as usize;
+            samples[idx.clamp(1, samples.len()) - 1]
+        };
+        Some(Self { p50: rank(50.0), p95: rank(95.0), p99: rank(99.0) })
+    }
+}
+
+/// Share of a turn spent in each pipeline stage.
+pub struct Breakdown {
+    pub infer_label: &'static str,
+    pub infer_pct: u64,
+    pub agent_pct: u64,
+    pub proxy_pct: u64,
+}
+
+/// Suggested `--num-parallel` for the healthy fleet.
+pub struct ParallelSuggestion {
+    pub optimal: u64,
+    pub per_gpu: f64,
+    pub gpus: u64,
+}
+
+impl ParallelSuggestion {
+    /// Suggestion from average inference time and agent gap for `gpus` backends.
+    fn from_timing(timing: &TimingStats, gpus: u64) -> Option<Self> {
+        if timing.count == 0 || timing.avg_inference_ms <= 0.0 || gpus == 0 {
+            return None;
+        }
+        let per_gpu = (timing.avg_inference_ms + timing.avg_gap_ms) / timing.avg_inference_ms;
+        let optimal = (gpus as f64 * per_gpu).ceil() as u64;
+        Some(ParallelSuggestion { optimal, per_gpu, gpus })
+    }
+}
+
+/// Higher `--num-parallel` for an idle fleet: the running sessions scaled
+/// up until utilization reaches the target.
+pub struct IdleSuggestion {
+    pub optimal: u64,
+    pub running: u64,
+    pub util_pct: f64,
+    pub target_pct: f64,
+}
+
+/// Suggestion scoped to one backend partition.
+pub struct PartitionSuggestion {
+    pub partition: String,
+    pub suggestion: ParallelSuggestion,
+}
+
+/// Per-partition `--num-parallel`, using only turns served by each partition's
+/// healthy backends. Empty unless the fleet spans more than one partition.
+pub fn compute_partition_suggestions(proxy: &ProxySnapshot) -> Vec<PartitionSuggestion> {
+    let healthy: Vec<_> = proxy.queue.backends.iter().filter(|b| b.healthy).collect();
+    let mut partitions: Vec<&str> = healthy.iter().map(|b| b.partition.as_str()).collect();
+    partitions.sort();
+    partitions.dedup();
+    if partitions.len() <= 1 {
+        return Vec::new();
+    }
+
+    partitions
+        .into_iter()
+        .filter_map(|partition| {
+            let urls: Vec<&str> = healthy
+                .iter()
+                .filter(|b| b.partition == partition)
+                .map(|b| b.url.as_str())
+                .collect();
+            let timing = collect_timing_stats_where(&proxy.queue, |t| {
+                t.backend.as_deref().is_some_and(|b| urls.contains(&b))
+            });
+            ParallelSuggestion::from_timing(&timing, urls.len() as u64).map(|suggestion| PartitionSuggestion {
+                partition: if partition.is_empty() { "default".to_string() } else { partition.to_string() },
+                suggestion,
+            })
+        })
+        .collect()
+}
+
+/// Suggestion scoped to one model's pool.
+pub struct ModelSuggestion {
+    pub model: String,
+    pub suggestion: ParallelSuggestion,
+}
+
+/// Per-model `--num-parallel`, from each model's sessions and the healthy
+/// backends in its pool. Empty unless the proxy serves more than one model;
+/// models without completed turns are left out.
+pub fn compute_model_suggestions(proxy: &ProxySnapshot) -> Vec<ModelSuggestion> {
+    if proxy.stats.pools.len() <= 1 {
+        return Vec::new();
+    }
+    proxy
+        .stats
+        .pools
+        .iter()
+        .filter_map(|pool| {
+            let healthy = pool.backends.iter().filter(|b| b.healthy).count() as u64;
+            let timing = collect_timing_stats_for_model(&proxy.queue, &pool.model);
+            ParallelSuggestion::from_timing(&timing, healthy).map(|suggestion| ModelSuggestion {
+                model: pool.model.clone(),
+                suggestion,
+            })
+        })
+        .collect()
+}
+
+/// Everything the Bottleneck Analysis panel shows, independent of rendering.
+pub struct BottleneckReport {
+    pub active_sessions: usize,
+    /// Sessions not yet settled, the closest estimate of `--num-parallel`.
+    pub running_sessions: usize,
+    pub healthy_backends: u64,
+    pub total_backends: u64,
+    pub timing: TimingStats,
+    pub avg_gpu_load: f64,
+    pub gpu_util: f64,
+    pub diagnosis: Diagnosis,
+    pub breakdown: Option<Breakdown>,
+    pub suggestion: Option<ParallelSuggestion>,
+    /// Only populated on a fleet with more than one partition.
+    pub partition_suggestions: Vec<PartitionSuggestion>,
+    /// Only populated when the proxy serves more than one model.
+    pub model_suggestions: Vec<ModelSuggestion>,
+    pub spread: Option<LoadSpread>,
+    /// Per-turn total latency.
+    pub turn_percentiles: Option<Percentiles>,
+    /// Per-turn queue wait.
+    pub wait_percentiles: Option<Percentiles>,
+    /// Agent gap mean with the largest gaps trimmed; drives the diagnosis.
+    pub trimmed_gap_ms: f64,
+    pub gap_outliers: bool,
+}
+
+impl BottleneckReport {
+    /// Headroom suggestion when the diagnosis is IDLE, assuming utilization
+    /// scales linearly with running sessions. `None` without completed turns
+    /// or any current utilization to scale from.
+    pub fn idle_suggestion(&self, target_pct: f64) -> Option<IdleSuggestion> {
+        if !matches!(self.diagnosis, Diagnosis::Idle)
+            || self.timing.count == 0
+            || self.running_sessions == 0
+            || self.gpu_util <= 0.0
+            || self.gpu_util >= target_pct
+        {
+            return None;
+        }
+        let running = self.running_sessions as u64;
+        let optimal = (running as f64 * target_pct / self.gpu_util).ceil() as u64;
+        (optimal > running).then_some(IdleSuggestion {
+            optimal,
+            running,
+            util_pct: self.gpu_util,
+            target_pct,
+        })
+    }
+
+    /// Partition then per-model suggestions, labelled for listing under
+    /// the global one. Models drop their `org/` prefix unless that would
+    /// leave two with the same label.
+    pub fn scoped_suggestions(&self) -> impl Iterator<Item = (&str, &ParallelSuggestion)> {
+        fn short(model: &str) -> &str {
+            model.rsplit('/').next().unwrap_or(model)
+        }
+        let partitions = self.partition_suggestions.iter().map(|p| (p.partition.as_str(), &p.suggestion));
+        let models = self.model_suggestions.iter().map(move |m| {
+            let clash = self.model_suggestions.iter().filter(|o| short(&o.model) == short(&m.model)).count() > 1;
+            (if clash { m.model.as_str() } else { short(&m.model) }, &m.suggestion)
+        });
+        partitions.chain(models)
+    }
+
+    /// One-line pipeline timing summary, or `None` when no turns completed.
+    pub fn pipeline_summary(&self, stat: LatencyStat, precision: Precision) -> Option<String> {
+        let timing = &self.timing;
+        if timing.count == 0 {
+            return None;
+        }
+        Some(if timing.has_backend_time {
+            format!(
+                "agent={}  inference={}  proxy={}  wait={}",
+                format_duration_ms(timing.gap_ms(stat), precision),
+                format_duration_ms(timing.inference_ms(stat), precision),
+                format_duration_ms(timing.proxy_ms(stat), precision),
+                format_duration_ms(timing.wait_ms(stat), precision),
+            )
+        } else if timing.gap_count > 0 {
+            format!(
+                "agent={}  proc={}  wait={}",
+                format_duration_ms(timing.gap_ms(stat), precision),
+                format_duration_ms(timing.inference_ms(stat), precision),
+                format_duration_ms(timing.wait_ms(stat), precision),
+            )
+        } else {
+            format!(
+                "proc={}  wait={}",
+                format_duration_ms(timing.inference_ms(stat), precision),
+                format_duration_ms(timing.wait_ms(stat), precision),
+            )
+        })
+    }
+}
+
+/// Compute the bottleneck analysis from a proxy snapshot. `gap_trim` is the
+/// fraction of largest agent gaps ignored when diagnosing (0 uses the plain mean).
+pub fn compute_bottleneck(proxy: &ProxySnapshot, gap_trim: f64) -> BottleneckReport {
     let summary = &proxy.queue.summary;
     let qbackends = &proxy.queue.backends;
 
@@ -337,9 +1005,12 @@ pub fn draw_bottleneck(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, col
         .iter()
         .filter(|s| s.in_flight_requests > 0 || s.pending_requests > 0)
         .count();
+    let running_sessions = all_sessions.iter().filter(|s| !s.is_settled()).count();
 
     // Collect pipeline timing stats
     let timing = collect_timing_stats(&proxy.queue);
+    let trimmed_gap_ms = timing.trimmed_gap_ms(gap_trim);
+    let gap_outliers = timing.gap_outliers(gap_trim);
 
     // GPU metrics
     let healthy_loads: Vec<u64> = qbackends
@@ -366,8 +1037,10 @@ pub fn draw_bottleneck(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, col
     // Diagnosis — add AgentBound check
     let diagnosis = if healthy_backends < total_backends && total_backends > 0 {
         Diagnosis::Unhealthy
-    } else if timing.gap_count > 0 && timing.avg_gap_ms > timing.avg_inference_ms * 0.5 && timing.avg_inference_ms > 0.0 {
+    } else if timing.gap_count > 0 && trimmed_gap_ms > timing.avg_inference_ms * 0.5 && timing.avg_inference_ms > 0.0 {
         Diagnosis::AgentBound
+    } else if timing.proxy_overhead().is_some_and(|r| r >= PROXY_BOUND_RATIO) {
+        Diagnosis::ProxyBound
     } else if summary.pending > 0 && avg_gpu_load >= healthy_backends as f64 * 0.8 {
         Diagnosis::GpuBound
     } else if avg_gpu_load < 0.5 && summary.pending == 0 {
@@ -376,12 +1049,161 @@ pub fn draw_bottleneck(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, col
         Diagnosis::Balanced
     };
 
+    // Breakdown percentages
+    let mut breakdown = None;
+    if timing.count > 0 && timing.gap_count > 0 {
+        let total = timing.avg_gap_ms + timing.avg_inference_ms + timing.avg_proxy_ms;
+        if total > 0.0 {
+            let infer_pct = (timing.avg_inference_ms / total * 100.0).round() as u64;
+            let agent_pct = (timing.avg_gap_ms / total * 100.0).round() as u64;
+            let proxy_pct = 100_u64.saturating_sub(infer_pct).saturating_sub(agent_pct);
+            let infer_label = if timing.has_backend_time { "inference" } else { "proc" };
+            breakdown = Some(Breakdown { infer_label, infer_pct, agent_pct, proxy_pct });
+        }
+    }
+
+    // Golden point: suggested --num-parallel
+    let suggestion = ParallelSuggestion::from_timing(&timing, healthy_backends);
+    let partition_suggestions = compute_partition_suggestions(proxy);
+    let model_suggestions = compute_model_suggestions(proxy);
+
+    let turns: Vec<&CompletedTurn> = all_sessions.iter().flat_map(|s| s.completed_turns.iter()).collect();
+    let turn_percentiles = Percentiles::from_samples(turns.iter().map(|t| t.total_time_ms).collect());
+    let wait_percentiles = Percentiles::from_samples(turns.iter().map(|t| t.wait_time_ms).collect());
+
+    let gpu_backends = collect_gpu_backends(proxy);
+    let thresholds = fleet_latency_thresholds(&proxy.stats);
+    let spread = compute_load_spread(&gpu_backends, thresholds.as_ref());
+
+    BottleneckReport {
+        active_sessions,
+        running_sessions,
+        healthy_backends,
+        total_backends,
+        timing,
+        avg_gpu_load,
+        gpu_util,
+        diagnosis,
+        breakdown,
+        suggestion,
+        partition_suggestions,
+        model_suggestions,
+        spread,
+        turn_percentiles,
+        wait_percentiles,
+        trimmed_gap_ms,
+        gap_outliers,
+    }
+}
+
+/// Pending requests submitted within this span of each other form one burst.
+pub const HERD_WINDOW_MS: f64 = 2000.0;
+
+/// A burst of pending requests submitted at nearly the same moment, typical
+/// of many sessions starting together and then draining through the queue.
+#[derive(Debug, PartialEq)]
+pub struct HerdBurst {
+    pub size: usize,
+    pub pending: usize,
+    /// Submission spread within the burst.
+    pub spread_ms: f64,
+}
+
+/// Look for a thundering herd in the pending queue: at least `min_burst`
+/// requests, and a majority of everything pending, submitted within
+/// `HERD_WINDOW_MS`. Submission order is recovered from wait times, which
+/// share the same fetch instant.
+pub fn detect_herd(pending: &[QueueRequest], min_burst: usize) -> Option<HerdBurst> {
+    if min_burst == 0 || pending.len() < min_burst {
+        return None;
+    }
+    let mut waits: Vec<f64> = pending.iter().map(|r| r.wait_time_ms).collect();
+    waits.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Largest set of waits within the window (two-pointer sweep)
+    let (mut best, mut best_spread, mut lo) = (0, 0.0, 0);
+    for hi in 0..waits.len() {
+        while waits[hi] - waits[lo] > HERD_WINDOW_MS {
+            lo += 1;
+        }
+        if hi - lo + 1 > best {
+            best = hi - lo + 1;
+            best_spread = waits[hi] - waits[lo];
+        }
+    }
+    (best >= min_burst && best * 2 > waits.len()).then_some(HerdBurst {
+        size: best,
+        pending: waits.len(),
+        spread_ms: best_spread,
+    })
+}
+
+/// Panel height (including borders) for each verbosity level, plus a line
+/// per scoped suggestion outside compact mode.
+pub fn bottleneck_height(verbosity: Verbosity, scoped_suggestions: usize) -> u16 {
+    let scoped = scoped_suggestions as u16;
+    match verbosity {
+        Verbosity::Compact => 3,
+        Verbosity::Normal => 14 + scoped,
+        Verbosity::Detailed => 18 + scoped,
+    }
+}
+
+/// Draw the Bottleneck Analysis panel from `report`, computed from `proxy`
+/// by the caller (which also sizes the panel from it).
+pub fn draw_bottleneck(
+    frame: &mut Frame,
+    area: Rect,
+    proxy: &ProxySnapshot,
+    report: &BottleneckReport,
+    state: &AppState,
+    colors: &ColorScheme,
+) {
+    let precision = state.precision;
+    let verbosity = state.bottleneck_verbosity;
+    if !proxy.connected.is_up() {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Bottleneck Analysis ")
+            .border_style(Style::default().fg(colors.border_normal));
+        let msg = Paragraph::new(Line::from(Span::styled(
+            format!("  {}", proxy.connected.placeholder()),
+            Style::default().fg(colors.text_disabled),
+        )))
+        .block(block);
+        frame.render_widget(msg, area);
+        return;
+    }
+
     let mut lines: Vec<Line> = Vec::new();
 
+    if verbosity == Verbosity::Compact {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} ", report.diagnosis.label()),
+                Style::default()
+                    .fg(report.diagnosis.color(colors))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("\u{2192} {}", report.diagnosis.advice()),
+                Style::default().fg(colors.text_secondary),
+            ),
+        ]));
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Bottleneck Analysis ")
+                .border_style(Style::default().fg(colors.border_focused)),
+        );
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
     lines.push(Line::from(vec![
         Span::styled("  Sessions:  ", Style::default().fg(colors.text_primary)),
         Span::styled(
-            format!("{} active", active_sessions),
+            format!("{} active", report.active_sessions),
             Style::default()
                 .fg(colors.accent)
                 .add_modifier(Modifier::BOLD),
@@ -391,8 +1213,8 @@ pub fn draw_bottleneck(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, col
     lines.push(Line::from(vec![
         Span::styled("  GPUs:      ", Style::default().fg(colors.text_primary)),
         Span::styled(
-            format!("{} healthy / {} total", healthy_backends, total_backends),
-            Style::default().fg(if healthy_backends == total_backends {
+            format!("{} healthy / {} total", report.healthy_backends, report.total_backends),
+            Style::default().fg(if report.healthy_backends == report.total_backends {
                 colors.status_ok
             } else {
                 colors.status_warn
@@ -403,31 +1225,13 @@ pub fn draw_bottleneck(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, col
     lines.push(Line::from(""));
 
     // Pipeline timing line
-    if timing.count > 0 {
-        let pipeline_str = if timing.has_backend_time {
-            format!(
-                "agent={}  inference={}  proxy={}  wait={}",
-                format_latency(timing.avg_gap_ms),
-                format_latency(timing.avg_inference_ms),
-                format_latency(timing.avg_proxy_ms),
-                format_latency(timing.avg_wait_ms),
-            )
-        } else if timing.gap_count > 0 {
-            format!(
-                "agent={}  proc={}  wait={}",
-                format_latency(timing.avg_gap_ms),
-                format_latency(timing.avg_inference_ms),
-                format_latency(timing.avg_wait_ms),
-            )
-        } else {
-            format!(
-                "proc={}  wait={}",
-                format_latency(timing.avg_inference_ms),
-                format_latency(timing.avg_wait_ms),
-            )
+    if let Some(pipeline_str) = report.pipeline_summary(state.latency_stat, precision) {
+        let label = match state.latency_stat {
+            LatencyStat::Mean => "  Pipeline: ",
+            LatencyStat::P95 => "  Pipeline p95: ",
         };
         lines.push(Line::from(vec![
-            Span::styled("  Pipeline: ", Style::default().fg(colors.text_primary)),
+            Span::styled(label, Style::default().fg(colors.text_primary)),
             Span::styled(pipeline_str, Style::default().fg(colors.text_primary)),
         ]));
     } else {
@@ -441,8 +1245,8 @@ pub fn draw_bottleneck(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, col
         Span::styled("  GPU:      ", Style::default().fg(colors.text_primary)),
         Span::styled(
             format!(
-                "load_avg={:.1} util={:.0}%",
-                avg_gpu_load, gpu_util
+                "load_avg={} util={}",
+                format_decimal(report.avg_gpu_load, 1, precision), format_pct(report.gpu_util, 0, precision)
             ),
             Style::default().fg(colors.text_primary),
         ),
@@ -453,50 +1257,112 @@ pub fn draw_bottleneck(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, col
     lines.push(Line::from(vec![
         Span::styled("  Diagnosis: ", Style::default().fg(colors.text_primary)),
         Span::styled(
-            diagnosis.label(),
+            report.diagnosis.label(),
             Style::default()
-                .fg(diagnosis.color(colors))
+                .fg(report.diagnosis.color(colors))
                 .add_modifier(Modifier::BOLD),
         ),
     ]));
 
     lines.push(Line::from(vec![Span::styled(
-        format!("  \u{2192} {}", diagnosis.advice()),
+        format!("  \u{2192} {}", report.diagnosis.advice()),
         Style::default().fg(colors.text_secondary),
     )]));
-
-    // Breakdown percentage line
-    if timing.count > 0 && timing.gap_count > 0 {
-        let total = timing.avg_gap_ms + timing.avg_inference_ms + timing.avg_proxy_ms;
-        if total > 0.0 {
-            let infer_pct = (timing.avg_inference_ms / total * 100.0).round() as u64;
-            let agent_pct = (timing.avg_gap_ms / total * 100.0).round() as u64;
-            let proxy_pct = 100_u64.saturating_sub(infer_pct).saturating_sub(agent_pct);
-            let infer_label = if timing.has_backend_time { "inference" } else { "proc" };
-            lines.push(Line::from(vec![Span::styled(
+    if let Some(idle) = report.idle_suggestion(state.idle_target_util as f64) {
+        lines.push(Line::from(vec![
+            Span::styled("  Headroom:  ", Style::default().fg(colors.text_primary)),
+            Span::styled(
+                format!("--num-parallel {}", idle.optimal),
+                Style::default().fg(colors.accent).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
                 format!(
-                    "  Breakdown: {} {}%, agent {}%, proxy {}%",
-                    infer_label, infer_pct, agent_pct, proxy_pct,
+                    "  ({} running at {} util \u{2192} {} target)",
+                    idle.running,
+                    format_pct(idle.util_pct, 0, precision),
+                    format_pct(idle.target_pct, 0, precision),
                 ),
                 Style::default().fg(colors.text_secondary),
-            )]));
-        }
+            ),
+        ]));
+    }
+
+    // Breakdown percentage line
+    if let Some(b) = &report.breakdown {
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "  Breakdown: {} {}%, agent {}%, proxy {}%",
+                b.infer_label, b.infer_pct, b.agent_pct, b.proxy_pct,
+            ),
+            Style::default().fg(colors.text_secondary),
+        )]));
+    }
+    if report.gap_outliers {
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "  Agent gap: avg {}, trimmed {} (outliers skew the mean)",
+                format_duration_ms(report.timing.avg_gap_ms, precision),
+                format_duration_ms(report.trimmed_gap_ms, precision),
+            ),
+            Style::default().fg(colors.status_warn),
+        )]));
+    }
+
+    if let Some(herd) = detect_herd(&proxy.queue.pending, state.herd_min_burst) {
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "  Herd: {} of {} pending sent within {} \u{2192} stagger session starts",
+                herd.size,
+                herd.pending,
+                format_duration_ms(herd.spread_ms, precision),
+            ),
+            Style::default().fg(colors.accent),
+        )]));
     }
 
     // Golden point: suggested --num-parallel
-    if timing.count > 0 && timing.avg_inference_ms > 0.0 && healthy_backends > 0 {
-        let per_gpu = (timing.avg_inference_ms + timing.avg_gap_ms) / timing.avg_inference_ms;
-        let optimal = (healthy_backends as f64 * per_gpu).ceil() as u64;
+    if let Some(s) = &report.suggestion {
         lines.push(Line::from(vec![
             Span::styled("  Suggested: ", Style::default().fg(colors.text_primary)),
             Span::styled(
-                format!("--num-parallel {}",  optimal),
+                format!("--num-parallel {}",  s.optimal),
                 Style::default()
                     .fg(colors.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                format!("  ({:.1}/gpu \u{00d7} {} gpus)", per_gpu, healthy_backends),
+                format!("  ({}/gpu \u{00d7} {} gpus)", format_decimal(s.per_gpu, 1, precision), s.gpus),
+                Style::default().fg(colors.text_secondary),
+            ),
+        ]));
+    }
+    if verbosity == Verbosity::Detailed {
+        lines.push(Line::from(""));
+        for (label, pct) in [("Turn", &report.turn_percentiles), ("Wait", &report.wait_percentiles)] {
+            let text = match pct {
+                Some(p) => format!(
+                    "p50={}  p95={}  p99={}",
+                    format_duration_ms(p.p50, precision),
+                    format_duration_ms(p.p95, precision),
+                    format_duration_ms(p.p99, precision),
+                ),
+                None => "no data".to_string(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<10}", format!("{}:", label)), Style::default().fg(colors.text_primary)),
+                Span::styled(text, Style::default().fg(colors.text_secondary)),
+            ]));
+        }
+    }
+    for (label, suggestion) in report.scoped_suggestions() {
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {:<9}", label), Style::default().fg(colors.text_primary)),
+            Span::styled(
+                format!("--num-parallel {}", suggestion.optimal),
+                Style::default().fg(colors.accent),
+            ),
+            Span::styled(
+                format!("  ({}/gpu \u{00d7} {} gpus)", format_decimal(suggestion.per_gpu, 1, precision), suggestion.gpus),
                 Style::default().fg(colors.text_secondary),
             ),
         ]));
@@ -514,10 +1380,466 @@ pub fn draw_bottleneck(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, col
     frame.render_widget(paragraph, area);
 }
 
-fn format_latency(ms: f64) -> String {
-    if ms < 1000.0 {
-        format!("{:.0}ms", ms)
-    } else {
-        format!("{:.1}s", ms / 1000.0)
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_support::snapshot;
+
+    #[test]
+    fn session_stage_picks_largest_mean_and_needs_enough_turns() {
+        let session = |turns: serde_json::Value| -> SessionGroup {
+            serde_json::from_value(json!({"session_id": "s", "completed_turns": turns})).unwrap()
+        };
+        // 1s inference, 5s between turns: agent-bound
+        let agent = session(json!([
+            {"submitted_at": 100.0, "completed_at": 101.0, "processing_time_ms": 1000.0},
+            {"submitted_at": 106.0, "completed_at": 107.0, "processing_time_ms": 1000.0},
+            {"submitted_at": 112.0, "completed_at": 113.0, "processing_time_ms": 1000.0}
+        ]));
+        assert_eq!(session_stage(&agent), Some(Stage::Agent));
+
+        // Back-to-back turns that queue longer than they infer
+        let wait = session(json!([
+            {"submitted_at": 100.0, "completed_at": 103.0, "wait_time_ms": 2000.0, "processing_time_ms": 1000.0},
+            {"submitted_at": 103.0, "completed_at": 106.0, "wait_time_ms": 2000.0, "processing_time_ms": 1000.0},
+            {"submitted_at": 106.0, "completed_at": 109.0, "wait_time_ms": 2000.0, "processing_time_ms": 1000.0}
+        ]));
+        assert_eq!(session_stage(&wait), Some(Stage::Wait));
+
+        let proxy = session(json!([
+            {"processing_time_ms": 3000.0, "backend_time_ms": 1000.0},
+            {"processing_time_ms": 3000.0, "backend_time_ms": 1000.0},
+            {"processing_time_ms": 3000.0, "backend_time_ms": 1000.0}
+        ]));
+        assert_eq!(session_stage(&proxy), Some(Stage::Proxy));
+
+        let short = session(json!([{"processing_time_ms": 1000.0}, {"processing_time_ms": 1000.0}]));
+        assert_eq!(session_timing_stats(&short).dominant_stage(), Some(Stage::Inference));
+        assert_eq!(session_stage(&short), None);
+    }
+
+    fn two_backend_stats() -> serde_json::Value {
+        json!({
+            "pools": [{
+                "model": "org/model-a",
+                "backends": [
+                    {"url": "http://gpu:8001", "healthy": true, "requests": 10, "avg_latency_ms": 100.0},
+                    {"url": "http://gpu:8002", "healthy": true, "requests": 10, "avg_latency_ms": 300.0},
+                    {"url": "http://gpu:8003", "healthy": false, "requests": 0, "avg_latency_ms": 0.0}
+                ]
+            }]
+        })
+    }
+
+    #[test]
+    fn latency_thresholds_ignore_unhealthy_and_idle_backends() {
+        let proxy = snapshot(two_backend_stats(), json!({}));
+        let t = fleet_latency_thresholds(&proxy.stats).unwrap();
+        assert_eq!(t.count, 2);
+        assert_eq!(t.min_ms, 100.0);
+        assert_eq!(t.max_ms, 300.0);
+        assert_eq!(t.median_ms, 200.0);
+    }
+
+    #[test]
+    fn latency_thresholds_none_without_traffic() {
+        let proxy = snapshot(json!({}), json!({}));
+        assert!(fleet_latency_thresholds(&proxy.stats).is_none());
+    }
+
+    #[test]
+    fn empty_pool_does_not_skew_fleet_metrics() {
+        let proxy = snapshot(
+            json!({"pools": [
+                {"model": "org/empty"},
+                {"model": "org/model-a", "backends": [
+                    {"url": "http://gpu:8001", "healthy": true, "requests": 4, "avg_latency_ms": 250.0}
+                ]}
+            ]}),
+            json!({"backends": [{"url": "http://gpu:8001", "healthy": true, "gpu_load": 2}]}),
+        );
+        let t = fleet_latency_thresholds(&proxy.stats).unwrap();
+        assert_eq!((t.count, t.min_ms, t.max_ms), (1, 250.0, 250.0));
+
+        let backends = collect_gpu_backends(&proxy);
+        assert_eq!(backends.len(), 1);
+        assert!(compute_load_spread(&backends, Some(&t)).is_none());
+
+        let empty_only = snapshot(json!({"pools": [{"model": "org/empty"}]}), json!({}));
+        let report = compute_bottleneck(&empty_only, 0.0);
+        assert!(report.spread.is_none());
+        assert!(report.suggestion.is_none());
+    }
+
+    #[test]
+    fn load_spread_reports_uneven_load() {
+        let proxy = snapshot(
+            two_backend_stats(),
+            json!({"backends": [
+                {"url": "http://gpu:8001", "healthy": true, "gpu_load": 1},
+                {"url": "http://gpu:8002", "healthy": true, "gpu_load": 4}
+            ]}),
+        );
+        let backends = collect_gpu_backends(&proxy);
+        assert_eq!(backends.len(), 3);
+        assert_eq!(backends[1].port, "8002");
+        assert_eq!(backends[1].gpu_load, 4);
+
+        let t = fleet_latency_thresholds(&proxy.stats);
+        let spread = compute_load_spread(&backends, t.as_ref()).unwrap();
+        assert_eq!(spread.latency_diff_pct, 200.0);
+        assert_eq!((spread.load_min, spread.load_max), (1, 4));
+        assert_eq!(spread.label(), "uneven");
+    }
+
+    fn gpu_row(port: &str, gpu_load: u64, requests: u64) -> GpuBackendRow {
+        GpuBackendRow {
+            url: format!("http://localhost:{}", port),
+            port: port.to_string(),
+            healthy: true,
+            gpu_load,
+            load_reported: true,
+            inflight: 0,
+            requests,
+            avg_latency_ms: 100.0,
+            p95_latency_ms: None,
+        }
+    }
+
+    #[test]
+    fn load_outlier_identifies_overloaded_backend() {
+        let backends = vec![gpu_row("8001", 2, 5), gpu_row("8002", 2, 5), gpu_row("8003", 6, 5)];
+        let outlier = find_load_outlier(&backends, 2.0).unwrap();
+        assert_eq!(outlier.port, "8003");
+        assert_eq!(outlier.ratio, 3.0);
+    }
+
+    #[test]
+    fn load_outlier_requires_two_active_backends() {
+        let backends = vec![gpu_row("8001", 0, 0), gpu_row("8002", 6, 5)];
+        assert!(find_load_outlier(&backends, 2.0).is_none());
+        let balanced = vec![gpu_row("8001", 3, 5), gpu_row("8002", 4, 5)];
+        assert!(find_load_outlier(&balanced, 2.0).is_none());
+    }
+
+    #[test]
+    fn inflight_drift_joins_backends_by_url() {
+        let stats: Vec<BackendStats> = serde_json::from_value(json!([
+            {"url": "http://gpu:8001", "healthy": true, "inflight": 5},
+            {"url": "http://gpu:8002", "healthy": true, "inflight": 2},
+            {"url": "http://gpu:8003", "healthy": true, "inflight": 0},
+            {"url": "http://gpu:8004", "healthy": false, "inflight": 9},
+            {"url": "http://gpu:8005", "healthy": true, "inflight": 9}
+        ]))
+        .unwrap();
+        let queue: Vec<QueueBackend> = serde_json::from_value(json!([
+            {"url": "http://gpu:8003", "healthy": true, "gpu_load": 4},
+            {"url": "http://gpu:8002", "healthy": true, "gpu_load": 1},
+            {"url": "http://gpu:8001", "healthy": true, "gpu_load": 0},
+            {"url": "http://gpu:8004", "healthy": false, "gpu_load": 0}
+        ]))
+        .unwrap();
+        let drifts = find_inflight_drift(&stats, &queue, 2);
+        let urls: Vec<&str> = drifts.iter().map(|d| d.url.as_str()).collect();
+        // 8002 is within threshold, 8004 is unhealthy, 8005 has no queue entry
+        assert_eq!(urls, ["http://gpu:8001", "http://gpu:8003"]);
+        assert_eq!(drifts[0].delta(), 5);
+        assert_eq!(drifts[1].delta(), -4);
+    }
+
+    #[test]
+    fn timing_stats_measure_agent_gap_within_session() {
+        let queue = serde_json::from_value::<QueueResponse>(json!({
+            "sessions": [{
+                "session_id": "s1",
+                "completed_turns": [
+                    {"submitted_at": 100.0, "completed_at": 101.0, "total_time_ms": 1000.0, "processing_time_ms": 800.0},
+                    {"submitted_at": 103.0, "completed_at": 104.0, "total_time_ms": 1000.0, "processing_time_ms": 600.0}
+                ]
+            }]
+        }))
+        .unwrap();
+        let timing = collect_timing_stats(&queue);
+        assert_eq!(timing.count, 2);
+        assert_eq!(timing.gap_count, 1);
+        assert_eq!(timing.avg_gap_ms, 2000.0);
+        assert_eq!(timing.avg_inference_ms, 700.0);
+        assert_eq!(timing.inference_ms(LatencyStat::P95), 800.0);
+        assert!(!timing.has_backend_time);
+    }
+
+    #[test]
+    fn p95_turn_is_taken_over_whole_turns() {
+        // The slowest turn and the longest agent gap belong to different turns
+        let queue = serde_json::from_value::<QueueResponse>(json!({
+            "sessions": [{
+                "session_id": "s1",
+                "completed_turns": [
+                    {"submitted_at": 100.0, "completed_at": 105.0, "total_time_ms": 5000.0},
+                    {"submitted_at": 106.0, "completed_at": 107.0, "total_time_ms": 1000.0},
+                    {"submitted_at": 111.0, "completed_at": 112.0, "total_time_ms": 1000.0}
+                ]
+            }]
+        }))
+        .unwrap();
+        let timing = collect_timing_stats(&queue);
+        assert_eq!(timing.p95_total_ms + timing.gap_ms(LatencyStat::P95), 9000.0);
+        assert_eq!(timing.turn_ms(LatencyStat::P95), 5000.0);
+        assert_eq!(timing.turn_ms(LatencyStat::Mean), 7000.0 / 3.0 + 2500.0);
+    }
+
+    #[test]
+    fn backend_p95_groups_turns_by_backend() {
+        let turns: Vec<_> = (1..=20)
+            .map(|i| json!({"backend": "http://gpu:8001", "processing_time_ms": i as f64 * 100.0}))
+            .chain([json!({"backend": "http://gpu:8002", "processing_time_ms": 900.0, "backend_time_ms": 500.0})])
+            .collect();
+        let queue = serde_json::from_value::<QueueResponse>(json!({
+            "sessions": [{"session_id": "s1", "completed_turns": turns}]
+        }))
+        .unwrap();
+        let p95 = backend_p95_ms(&queue);
+        assert_eq!(p95["http://gpu:8001"], 1900.0);
+        assert_eq!(p95["http://gpu:8002"], 500.0);
+        assert_eq!(p95.len(), 2);
+    }
+
+    #[test]
+    fn partition_suggestions_use_partition_turns_only() {
+        let proxy = snapshot(
+            json!({}),
+            json!({
+                "backends": [
+                    {"url": "http://a:8001", "healthy": true, "partition": "h100"},
+                    {"url": "http://b:8001", "healthy": true, "partition": "a10"},
+                    {"url": "http://b:8002", "healthy": true, "partition": "a10"}
+                ],
+                "sessions": [{
+                    "session_id": "s1",
+                    "completed_turns": [
+                        {"backend": "http://a:8001", "submitted_at": 100.0, "completed_at": 101.0, "processing_time_ms": 1000.0},
+                        {"backend": "http://b:8001", "submitted_at": 103.0, "completed_at": 107.0, "processing_time_ms": 4000.0}
+                    ]
+                }]
+            }),
+        );
+        let parts = compute_partition_suggestions(&proxy);
+        assert_eq!(parts.len(), 2);
+        // a10: inference 4s, gap 2s -> 1.5/gpu x 2 gpus = 3
+        assert_eq!(parts[0].partition, "a10");
+        assert_eq!((parts[0].suggestion.gpus, parts[0].suggestion.optimal), (2, 3));
+        // h100: no gap measured in its own turns -> 1/gpu
+        assert_eq!(parts[1].partition, "h100");
+        assert_eq!(parts[1].suggestion.optimal, 1);
+    }
+
+    #[test]
+    fn model_suggestions_use_model_sessions_and_pool_backends() {
+        let turns = |base: f64, infer_s: f64| {
+            json!([
+                {"submitted_at": base, "completed_at": base + infer_s, "processing_time_ms": infer_s * 1000.0},
+                {"submitted_at": base + infer_s + 1.0, "completed_at": base + 2.0 * infer_s + 1.0, "processing_time_ms": infer_s * 1000.0}
+            ])
+        };
+        let backend = |url: &str, healthy: bool| json!({"url": url, "healthy": healthy});
+        let proxy = snapshot(
+            json!({"pools": [
+                {"model": "org/big", "backends": [backend("http://a:8001", true), backend("http://a:8002", true), backend("http://a:8003", false)]},
+                {"model": "small", "backends": [backend("http://b:8001", true)]},
+                {"model": "unused", "backends": [backend("http://c:8001", true)]}
+            ]}),
+            json!({"sessions": [
+                {"session_id": "s1", "model": "org/big", "completed_turns": turns(100.0, 1.0)},
+                {"session_id": "s2", "model": "small", "completed_turns": turns(200.0, 4.0)}
+            ]}),
+        );
+        let models = compute_model_suggestions(&proxy);
+        // "unused" has no timing data and is skipped
+        assert_eq!(models.len(), 2);
+        // big: inference 1s, gap 1s -> 2/gpu x 2 healthy = 4
+        assert_eq!(models[0].model, "org/big");
+        assert_eq!((models[0].suggestion.gpus, models[0].suggestion.optimal), (2, 4));
+        // small: inference 4s, gap 1s -> 1.25/gpu x 1 = 2
+        assert_eq!((models[1].suggestion.gpus, models[1].suggestion.optimal), (1, 2));
+
+        let report = compute_bottleneck(&proxy, 0.0);
+        let labels: Vec<&str> = report.scoped_suggestions().map(|(label, _)| label).collect();
+        assert_eq!(labels, ["big", "small"]);
+        assert_eq!(bottleneck_height(Verbosity::Normal, labels.len()), 16);
+
+        // Same name under two orgs keeps the org so the labels stay distinct
+        let mut report = report;
+        let suggestion = || ParallelSuggestion { optimal: 1, per_gpu: 1.0, gpus: 1 };
+        report.model_suggestions = ["a/llama", "b/llama", "b/qwen"]
+            .iter()
+            .map(|m| ModelSuggestion { model: m.to_string(), suggestion: suggestion() })
+            .collect();
+        let labels: Vec<&str> = report.scoped_suggestions().map(|(label, _)| label).collect();
+        assert_eq!(labels, ["a/llama", "b/llama", "qwen"]);
+
+        let single = snapshot(json!({"pools": [{"model": "small", "backends": [backend("http://b:8001", true)]}]}), json!({}));
+        assert!(compute_model_suggestions(&single).is_empty());
+    }
+
+    #[test]
+    fn partition_suggestions_empty_for_single_partition() {
+        let proxy = snapshot(
+            json!({}),
+            json!({"backends": [{"url": "http://a:8001", "healthy": true, "partition": "h100"}]}),
+        );
+        assert!(compute_partition_suggestions(&proxy).is_empty());
+    }
+
+    #[test]
+    fn trimmed_gap_ignores_stuck_turn() {
+        let timing = TimingStats {
+            count: 10,
+            avg_inference_ms: 1000.0,
+            avg_gap_ms: 1090.0,
+            avg_wait_ms: 0.0,
+            avg_proxy_ms: 0.0,
+            avg_total_ms: 1000.0,
+            p95_inference_ms: 1000.0,
+            p95_wait_ms: 0.0,
+            p95_proxy_ms: 0.0,
+            p95_total_ms: 1000.0,
+            p95_turn_ms: 1100.0,
+            has_backend_time: false,
+            gap_count: 10,
+            gap_samples: vec![100.0, 100.0, 100.0, 100.0, 100.0, 100.0, 100.0, 100.0, 100.0, 10000.0],
+        };
+        assert_eq!(timing.trimmed_gap_ms(0.0), 1090.0);
+        assert_eq!(timing.trimmed_gap_ms(0.05), 100.0);
+        assert!(timing.gap_outliers(0.05));
+        assert!(!timing.gap_outliers(0.0));
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let p = Percentiles::from_samples((1..=100).map(|v| v as f64).collect()).unwrap();
+        assert_eq!((p.p50, p.p95, p.p99), (50.0, 95.0, 99.0));
+        let single = Percentiles::from_samples(vec![7.0]).unwrap();
+        assert_eq!((single.p50, single.p99), (7.0, 7.0));
+        assert!(Percentiles::from_samples(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn bottleneck_flags_unhealthy_fleet() {
+        let proxy = snapshot(
+            two_backend_stats(),
+            json!({"backends": [
+                {"url": "http://gpu:8001", "healthy": true},
+                {"url": "http://gpu:8003", "healthy": false}
+            ]}),
+        );
+        let report = compute_bottleneck(&proxy, 0.0);
+        assert!(matches!(report.diagnosis, Diagnosis::Unhealthy));
+        assert_eq!((report.healthy_backends, report.total_backends), (1, 2));
+        assert!(report.suggestion.is_none());
+        assert!(report.pipeline_summary(LatencyStat::Mean, Precision::default()).is_none());
+    }
+
+    #[test]
+    fn bottleneck_flags_proxy_overhead_only_with_backend_time() {
+        let queue = |backend_time: serde_json::Value| {
+            json!({
+                "backends": [{"url": "http://gpu:8001", "healthy": true, "gpu_load": 1}],
+                "sessions": [{
+                    "session_id": "s1",
+                    "completed_turns": [
+                        {"total_time_ms": 1000.0, "processing_time_ms": 1000.0, "backend_time_ms": backend_time}
+                    ]
+                }]
+            })
+        };
+        let report = compute_bottleneck(&snapshot(json!({}), queue(json!(600.0))), 0.0);
+        assert!(matches!(report.diagnosis, Diagnosis::ProxyBound));
+        assert_eq!(report.timing.proxy_overhead(), Some(0.4));
+
+        let report = compute_bottleneck(&snapshot(json!({}), queue(json!(null))), 0.0);
+        assert!(!matches!(report.diagnosis, Diagnosis::ProxyBound));
+        assert!(report.timing.proxy_overhead().is_none());
+    }
+
+    #[test]
+    fn bottleneck_suggests_parallelism_from_agent_gap() {
+        let proxy = snapshot(
+            json!({}),
+            json!({
+                "backends": [{"url": "http://gpu:8001", "healthy": true, "gpu_load": 1}],
+                "sessions": [{
+                    "session_id": "s1",
+                    "completed_turns": [
+                        {"submitted_at": 100.0, "completed_at": 101.0, "processing_time_ms": 1000.0},
+                        {"submitted_at": 102.0, "completed_at": 103.0, "processing_time_ms": 1000.0}
+                    ]
+                }]
+            }),
+        );
+        let report = compute_bottleneck(&proxy, 0.0);
+        assert!(matches!(report.diagnosis, Diagnosis::AgentBound));
+        let s = report.suggestion.unwrap();
+        assert_eq!(s.gpus, 1);
+        assert_eq!(s.optimal, 2);
+        let b = report.breakdown.unwrap();
+        assert_eq!((b.infer_pct, b.agent_pct, b.proxy_pct), (50, 50, 0));
+    }
+
+    #[test]
+    fn idle_suggestion_scales_running_sessions_to_target() {
+        let session = |id: &str| {
+            json!({
+                "session_id": id, "total_turns": 10, "completed_requests": 3, "in_flight_requests": 1,
+                "completed_turns": [{"processing_time_ms": 1000.0}]
+            })
+        };
+        let proxy = snapshot(
+            json!({}),
+            json!({
+                "backends": [
+                    {"url": "http://gpu:8001", "healthy": true, "inflight": 1},
+                    {"url": "http://gpu:8002", "healthy": true}
+                ],
+                "sessions": [session("s1"), session("s2")]
+            }),
+        );
+        let report = compute_bottleneck(&proxy, 0.0);
+        assert!(matches!(report.diagnosis, Diagnosis::Idle));
+        let idle = report.idle_suggestion(80.0).unwrap();
+        assert_eq!((idle.running, idle.optimal), (2, 4));
+        assert!(report.idle_suggestion(50.0).is_none());
+    }
+
+    #[test]
+    fn idle_suggestion_needs_timing_data() {
+        let proxy = snapshot(
+            json!({}),
+            json!({
+                "backends": [
+                    {"url": "http://gpu:8001", "healthy": true, "inflight": 1},
+                    {"url": "http://gpu:8002", "healthy": true}
+                ],
+                "sessions": [{"session_id": "s1", "total_turns": 10}]
+            }),
+        );
+        let report = compute_bottleneck(&proxy, 0.0);
+        assert!(matches!(report.diagnosis, Diagnosis::Idle));
+        assert!(report.idle_suggestion(80.0).is_none());
+    }
+
+    #[test]
+    fn herd_needs_a_large_clustered_majority() {
+        let pending = |waits: &[f64]| -> Vec<QueueRequest> {
+            waits.iter().map(|w| serde_json::from_value(json!({"wait_time_ms": w})).unwrap()).collect()
+        };
+        let burst = pending(&[5000.0, 5100.0, 5300.0, 6200.0, 6900.0, 200.0]);
+        assert_eq!(detect_herd(&burst, 5), Some(HerdBurst { size: 5, pending: 6, spread_ms: 1900.0 }));
+        // Too small a burst for this sensitivity
+        assert!(detect_herd(&burst, 6).is_none());
+        // Steady arrivals spread over time
+        let steady = pending(&[0.0, 3000.0, 6000.0, 9000.0, 12000.0, 15000.0]);
+        assert!(detect_herd(&steady, 2).is_none());
     }
 }
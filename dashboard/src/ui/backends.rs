@@ -1,23 +1,311 @@
+use std::collections::{HashMap, HashSet};
+
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Rect};
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::Text;
 use ratatui::widgets::{Block, Borders, Cell, Row, Table};
+use serde::Serialize;
 
-use crate::model::{AppState, FocusedPanel, ProxySnapshot};
+use crate::model::{AppState, FocusedPanel, HealthWeights, LatencyStat, ProxySnapshot};
+use super::analysis::{backend_p95_ms, fleet_latency_thresholds, LatencyThresholds};
+use super::format::{format_count, format_delta, format_duration_ms, format_elapsed, format_pct, format_signed, Precision};
 use super::theme::ColorScheme;
 
+/// One backend within a model pool, joined with queue-side load data.
+#[derive(Serialize)]
+pub struct BackendView {
+    pub url: String,
+    pub healthy: bool,
+    pub partition: String,
+    /// `None` when the queue endpoint doesn't know this backend.
+    pub gpu_load: Option<u64>,
+    pub inflight: u64,
+    pub requests: u64,
+    pub errors: u64,
+    pub avg_latency_ms: f64,
+    /// From the queue's completed turns; `None` if none were served here.
+    pub p95_latency_ms: Option<f64>,
+    /// Mean latency over the collector's recent window, if it served any.
+    pub recent_latency_ms: Option<f64>,
+}
+
+/// A model pool with its aggregate stats and backends.
+#[derive(Serialize)]
+pub struct PoolView {
+    pub model: String,
+    pub healthy: usize,
+    pub total: usize,
+    pub gpu_load: u64,
+    pub inflight: u64,
+    pub requests: u64,
+    pub errors: u64,
+    pub backends: Vec<BackendView>,
+}
+
+/// Terminal width below which backends collapse to one line each (`--narrow`
+/// forces it). The whole terminal rather than the panel, so the wall layout's
+/// half-width column still gets the full table on a wide screen.
+const NARROW_WIDTH: u16 = 100;
+/// Fraction of capacity at which a pool's gauge turns warn / error.
+const SATURATION_WARN: f64 = 0.8;
+const SATURATION_ERROR: f64 = 1.0;
+/// Cells in the pool capacity bar.
+const GAUGE_WIDTH: usize = 5;
+
+impl PoolView {
+    /// Estimated concurrent capacity: healthy backends times the per-backend
+    /// concurrency.
+    pub fn capacity(&self, per_backend: u64) -> u64 {
+        self.healthy as u64 * per_backend
+    }
+
+    /// In-flight requests as a fraction of capacity, or `None` when the pool
+    /// has no capacity to measure against.
+    pub fn saturation(&self, per_backend: u64) -> Option<f64> {
+        match self.capacity(per_backend) {
+            0 => None,
+            cap => Some(self.inflight as f64 / cap as f64),
+        }
+    }
+
+    /// The backends to list: all of them, or with `limit` the busiest by
+    /// gpu_load then inflight, plus how many were left out. Pool aggregates
+    /// always cover every backend.
+    pub fn visible_backends(&self, limit: Option<usize>) -> (Vec<&BackendView>, usize) {
+        let mut shown: Vec<&BackendView> = self.backends.iter().collect();
+        let Some(n) = limit else {
+            return (shown, 0);
+        };
+        shown.sort_by(|a, b| {
+            b.gpu_load
+                .unwrap_or(0)
+                .cmp(&a.gpu_load.unwrap_or(0))
+                .then(b.inflight.cmp(&a.inflight))
+        });
+        let hidden = shown.len().saturating_sub(n);
+        shown.truncate(n);
+        (shown, hidden)
+    }
+}
+
+/// Build the backends table data from `/stats` pools and `/queue/status` backends.
+pub fn build_pool_views(proxy: &ProxySnapshot) -> Vec<PoolView> {
+    // Build lookup maps from queue backends
+    let gpu_load_map: HashMap<&str, u64> = proxy
+        .queue
+        .backends
+        .iter()
+        .map(|b| (b.url.as_str(), b.gpu_load))
+        .collect();
+    let inflight_map: HashMap<&str, u64> = proxy
+        .queue
+        .backends
+        .iter()
+        .map(|b| (b.url.as_str(), b.inflight))
+        .collect();
+    let p95_map = backend_p95_ms(&proxy.queue);
+
+    proxy
+        .stats
+        .pools
+        .iter()
+        .map(|pool| {
+            let backends: Vec<BackendView> = pool
+                .backends
+                .iter()
+                .map(|b| BackendView {
+                    url: b.url.clone(),
+                    healthy: b.healthy,
+                    partition: b.partition.clone(),
+                    gpu_load: gpu_load_map.get(b.url.as_str()).copied(),
+                    inflight: inflight_map.get(b.url.as_str()).copied().unwrap_or(0),
+                    requests: b.requests,
+                    errors: b.errors,
+                    avg_latency_ms: b.avg_latency_ms,
+                    p95_latency_ms: p95_map.get(&b.url).copied(),
+                    recent_latency_ms: proxy.recent_latency_ms.get(&b.url).copied(),
+                })
+                .collect();
+
+            PoolView {
+                model: pool.model.clone(),
+                healthy: backends.iter().filter(|b| b.healthy).count(),
+                total: backends.len(),
+                gpu_load: backends.iter().map(|b| b.gpu_load.unwrap_or(0)).sum(),
+                inflight: backends.iter().map(|b| b.inflight).sum(),
+                requests: backends.iter().map(|b| b.requests).sum(),
+                errors: backends.iter().map(|b| b.errors).sum(),
+                backends,
+            }
+        })
+        .collect()
+}
+
+/// Error rate at which the error component of the health score reaches zero.
+const SCORE_ERROR_RATE_FLOOR: f64 = 0.1;
+/// Latency over the fleet median at which the latency component reaches zero.
+const SCORE_LATENCY_RATIO_FLOOR: f64 = 3.0;
+/// Health scores at or above these are shown as good / fair.
+const SCORE_GOOD: u8 = 80;
+const SCORE_FAIR: u8 = 50;
+
+/// A 0-100 triage score for one backend: the weighted mean of its health
+/// flag, error rate, latency against the fleet median, and in-flight
+/// headroom against `capacity_per_backend`, each scaled to 0..1. Signals
+/// without data (no requests, no fleet median, no capacity) count as perfect.
+pub fn backend_health_score(
+    backend: &BackendView,
+    fleet_median_ms: Option<f64>,
+    capacity_per_backend: u64,
+    weights: &HealthWeights,
+) -> u8 {
+    let healthy = if backend.healthy { 1.0 } else { 0.0 };
+    let errors = match backend.requests {
+        0 => 1.0,
+        n => 1.0 - (backend.errors as f64 / n as f64 / SCORE_ERROR_RATE_FLOOR).min(1.0),
+    };
+    let latency = match fleet_median_ms.filter(|m| *m > 0.0 && backend.requests > 0) {
+        Some(median) => {
+            let ratio = backend.avg_latency_ms / median;
+            1.0 - ((ratio - 1.0) / (SCORE_LATENCY_RATIO_FLOOR - 1.0)).clamp(0.0, 1.0)
+        }
+        None => 1.0,
+    };
+    let headroom = match capacity_per_backend {
+        0 => 1.0,
+        cap => 1.0 - (backend.inflight as f64 / cap as f64).min(1.0),
+    };
+    let total = weights.healthy + weights.errors + weights.latency + weights.headroom;
+    if total <= 0.0 {
+        return 0;
+    }
+    let weighted = weights.healthy * healthy
+        + weights.errors * errors
+        + weights.latency * latency
+        + weights.headroom * headroom;
+    (weighted / total * 100.0).round() as u8
+}
+
+fn score_cell<'a>(score: Option<u8>, colors: &ColorScheme) -> Cell<'a> {
+    let Some(score) = score else {
+        return Cell::from("-").style(Style::default().fg(colors.text_disabled));
+    };
+    let color = if score >= SCORE_GOOD {
+        colors.status_ok
+    } else if score >= SCORE_FAIR {
+        colors.status_warn
+    } else {
+        colors.status_error
+    };
+    Cell::from(score.to_string()).style(Style::default().fg(color))
+}
+
+/// Backends that only one of `/stats` and `/queue/status` reports, e.g.
+/// when one endpoint has picked up a fleet change before the other.
+#[derive(Debug, Default, PartialEq)]
+pub struct BackendMismatch {
+    /// Listed by the queue but in no stats pool, so missing from the tables.
+    pub queue_only: Vec<String>,
+    /// In a stats pool but unknown to the queue, so without a gpu_load.
+    pub stats_only: Vec<String>,
+}
+
+impl BackendMismatch {
+    /// One-line summary, or `None` when both endpoints agree.
+    pub fn note(&self) -> Option<String> {
+        let ports = |urls: &[String]| {
+            urls.iter()
+                .map(|u| format!(":{}", u.rsplit(':').next().unwrap_or(u)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut parts = Vec::new();
+        if !self.queue_only.is_empty() {
+            parts.push(format!("{} in queue not in stats ({})", self.queue_only.len(), ports(&self.queue_only)));
+        }
+        if !self.stats_only.is_empty() {
+            parts.push(format!("{} in stats not in queue ({})", self.stats_only.len(), ports(&self.stats_only)));
+        }
+        (!parts.is_empty()).then(|| format!("backends {}", parts.join(", ")))
+    }
+}
+
+/// Compare the backend URL sets the two endpoints report.
+pub fn backend_mismatch(proxy: &ProxySnapshot) -> BackendMismatch {
+    let stats: HashSet<&str> = proxy
+        .stats
+        .pools
+        .iter()
+        .flat_map(|p| p.backends.iter())
+        .map(|b| b.url.as_str())
+        .collect();
+    let queue: HashSet<&str> = proxy.queue.backends.iter().map(|b| b.url.as_str()).collect();
+    let mut mismatch = BackendMismatch {
+        queue_only: queue.difference(&stats).map(|u| u.to_string()).collect(),
+        stats_only: stats.difference(&queue).map(|u| u.to_string()).collect(),
+    };
+    mismatch.queue_only.sort();
+    mismatch.stats_only.sort();
+    mismatch
+}
+
+/// Change in one backend's counters since the baseline snapshot.
+#[derive(Debug, PartialEq)]
+pub struct BackendDelta {
+    pub requests: i64,
+    pub errors: i64,
+    /// `None` for backends that weren't in the baseline.
+    pub latency_ms: Option<f64>,
+}
+
+/// Per-backend deltas between a captured baseline and the current snapshot,
+/// keyed by URL. Backends new since the baseline count from zero.
+pub fn backend_deltas(baseline: &ProxySnapshot, proxy: &ProxySnapshot) -> HashMap<String, BackendDelta> {
+    let before: HashMap<&str, _> = baseline
+        .stats
+        .pools
+        .iter()
+        .flat_map(|p| p.backends.iter())
+        .map(|b| (b.url.as_str(), b))
+        .collect();
+    proxy
+        .stats
+        .pools
+        .iter()
+        .flat_map(|p| p.backends.iter())
+        .map(|b| {
+            let base = before.get(b.url.as_str());
+            let delta = BackendDelta {
+                requests: b.requests as i64 - base.map_or(0, |o| o.requests as i64),
+                errors: b.errors as i64 - base.map_or(0, |o| o.errors as i64),
+                latency_ms: base.map(|o| b.avg_latency_ms - o.avg_latency_ms),
+            };
+            (b.url.clone(), delta)
+        })
+        .collect()
+}
+
+/// A counter with its change since the baseline appended, e.g. "120 +20".
+fn with_delta(value: u64, delta: Option<i64>) -> String {
+    match delta {
+        Some(d) => format!("{} {}", format_count(value), format_delta(d)),
+        None => format_count(value),
+    }
+}
+
 pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppState, colors: &ColorScheme) {
+    let precision = state.precision;
     let is_focused = state.focused_panel == FocusedPanel::Backends;
     let border_color = if is_focused { colors.border_focused } else { colors.border_normal };
 
-    if !proxy.connected {
+    if !proxy.connected.is_up() {
         let block = Block::default()
             .borders(Borders::ALL)
             .title(" GPU Backends ")
             .border_style(Style::default().fg(colors.border_normal));
         let msg = ratatui::widgets::Paragraph::new(Text::styled(
-            "  Proxy offline \u{2014} no backend data",
+            format!("  {} \u{2014} no backend data", proxy.connected.placeholder()),
             Style::default().fg(colors.text_disabled),
         ))
         .block(block);
@@ -25,21 +313,27 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppSta
         return;
     }
 
-    // Build lookup maps from queue backends
-    let gpu_load_map: std::collections::HashMap<&str, u64> = proxy
-        .queue
-        .backends
-        .iter()
-        .map(|b| (b.url.as_str(), b.gpu_load))
-        .collect();
-    let inflight_map: std::collections::HashMap<&str, u64> = proxy
-        .queue
-        .backends
-        .iter()
-        .map(|b| (b.url.as_str(), b.inflight))
-        .collect();
+    let pools = build_pool_views(proxy);
+    if state.narrow_backends || frame.area().width < NARROW_WIDTH {
+        draw_narrow(frame, area, proxy, &pools, state, colors);
+        return;
+    }
+    let latency_thresholds = fleet_latency_thresholds(&proxy.stats);
+    let deltas = state.baseline.as_ref().map(|base| backend_deltas(base, proxy));
+    let pool_delta = |pool: &PoolView, field: fn(&BackendDelta) -> i64| {
+        deltas
+            .as_ref()
+            .map(|d| pool.backends.iter().filter_map(|b| d.get(&b.url)).map(field).sum::<i64>())
+    };
+
+    let median_ms = latency_thresholds.as_ref().map(|t| t.median_ms);
+    let score = |b: &BackendView| {
+        state
+            .health_weights
+            .map(|w| backend_health_score(b, median_ms, state.capacity_per_backend, &w))
+    };
 
-    let header = Row::new(vec![
+    let mut header_cells = vec![
         Cell::from("Model / Backend"),
         Cell::from("Status"),
         Cell::from("Partition"),
@@ -47,8 +341,13 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppSta
         Cell::from("Proxy Inflt"),
         Cell::from("Requests"),
         Cell::from("Errors"),
-        Cell::from("Avg Latency"),
-    ])
+        Cell::from(format!("Latency {} / recent", state.latency_stat.label())),
+        Cell::from("Capacity"),
+    ];
+    if state.health_weights.is_some() {
+        header_cells.push(Cell::from("Score"));
+    }
+    let header = Row::new(header_cells)
     .style(
         Style::default()
             .fg(colors.table_header)
@@ -58,67 +357,65 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppSta
     let mut rows: Vec<Row> = Vec::new();
     let mut total_backends: usize = 0;
 
-    for (pool_idx, pool) in proxy.stats.pools.iter().enumerate() {
+    for (pool_idx, pool) in pools.iter().enumerate() {
         let is_selected = is_focused && pool_idx == state.backend_selected;
         let is_expanded = state.backend_expanded.contains(&pool.model);
 
         let arrow = if is_expanded { "\u{25be}" } else { "\u{25b8}" };
 
-        // Aggregate pool-level stats
-        let pool_healthy = pool.backends.iter().filter(|b| b.healthy).count();
-        let pool_total = pool.backends.len();
-        let pool_requests: u64 = pool.backends.iter().map(|b| b.requests).sum();
-        let pool_errors: u64 = pool.backends.iter().map(|b| b.errors).sum();
-        let pool_gpu_load: u64 = pool
-            .backends
-            .iter()
-            .map(|b| gpu_load_map.get(b.url.as_str()).copied().unwrap_or(0))
-            .sum();
-        let pool_inflight: u64 = pool
-            .backends
-            .iter()
-            .map(|b| inflight_map.get(b.url.as_str()).copied().unwrap_or(0))
-            .sum();
-
         // Model group header row
-        let health_summary = format!("{}/{} healthy", pool_healthy, pool_total);
-        let health_color = if pool_healthy == pool_total {
+        let health_summary = if pool.total == 0 {
+            "no backends registered".to_string()
+        } else {
+            format!("{}/{} healthy", pool.healthy, pool.total)
+        };
+        let health_color = if pool.total == 0 {
+            colors.status_error
+        } else if pool.healthy == pool.total {
             colors.status_ok
-        } else if pool_healthy > 0 {
+        } else if pool.healthy > 0 {
             colors.status_warn
         } else {
             colors.status_error
         };
 
         let row_style = if is_selected {
-            Style::default().bg(colors.row_selected_bg)
+            colors.selected_row()
         } else {
             Style::default().bg(colors.row_alt_bg)
         };
 
-        let model_row = Row::new(vec![
-            Cell::from(format!("{} {}", arrow, &pool.model))
-                .style(Style::default().fg(colors.accent).add_modifier(Modifier::BOLD)),
+        let mut model_cells = vec![
+            colors.first_cell(
+                format!("{} {}", arrow, &pool.model),
+                Style::default().fg(colors.accent).add_modifier(Modifier::BOLD),
+                is_selected,
+            ),
             Cell::from(health_summary).style(Style::default().fg(health_color)),
             Cell::from(""),
-            Cell::from(pool_gpu_load.to_string()).style(Style::default().fg(colors.accent_id)),
-            Cell::from(pool_inflight.to_string()).style(Style::default().fg(
-                if pool_inflight > 0 { colors.accent } else { colors.text_primary },
+            Cell::from(format_count(pool.gpu_load)).style(Style::default().fg(colors.accent_id)),
+            Cell::from(format_count(pool.inflight)).style(Style::default().fg(
+                if pool.inflight > 0 { colors.accent } else { colors.text_primary },
             )),
-            Cell::from(pool_requests.to_string()).style(Style::default().fg(colors.text_primary)),
-            Cell::from(pool_errors.to_string()).style(if pool_errors > 0 {
+            Cell::from(with_delta(pool.requests, pool_delta(pool, |d| d.requests))).style(Style::default().fg(colors.text_primary)),
+            Cell::from(with_delta(pool.errors, pool_delta(pool, |d| d.errors))).style(if pool.errors > 0 {
                 Style::default().fg(colors.status_error)
             } else {
                 Style::default().fg(colors.text_primary)
             }),
             Cell::from(""),
-        ])
-        .style(row_style);
-        rows.push(model_row);
+            capacity_cell(pool, state.capacity_per_backend, colors),
+        ];
+        if state.health_weights.is_some() {
+            // A pool is only as healthy as its worst backend
+            model_cells.push(score_cell(pool.backends.iter().filter_map(score).min(), colors));
+        }
+        rows.push(Row::new(model_cells).style(row_style));
 
         // Individual backend rows (only if expanded)
         if is_expanded {
-            for backend in &pool.backends {
+            let (shown, hidden) = pool.visible_backends(state.top_backends);
+            for backend in shown {
                 let status_style = if backend.healthy {
                     Style::default().fg(colors.status_ok)
                 } else {
@@ -126,71 +423,88 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppSta
                 };
                 let status_text = if backend.healthy { "healthy" } else { "down" };
 
-                let gpu_load = gpu_load_map
-                    .get(backend.url.as_str())
-                    .copied()
-                    .map(|v| v.to_string())
+                let gpu_load = backend
+                    .gpu_load
+                    .map(format_count)
                     .unwrap_or_else(|| "-".to_string());
 
-                let inflight = inflight_map
-                    .get(backend.url.as_str())
-                    .copied()
-                    .unwrap_or(0);
-
                 let partition_str = if backend.partition.is_empty() {
                     "-".to_string()
                 } else {
                     backend.partition.clone()
                 };
 
-                let row = Row::new(vec![
+                let delta = deltas.as_ref().and_then(|d| d.get(&backend.url));
+                let shown = match state.latency_stat {
+                    LatencyStat::Mean => Some(backend.avg_latency_ms),
+                    LatencyStat::P95 => backend.p95_latency_ms,
+                };
+                let mut latency_text = format!(
+                    "{} / {}",
+                    shown.map_or("-".to_string(), |ms| format_duration_ms(ms, precision)),
+                    backend.recent_latency_ms.map_or("-".to_string(), |ms| format_duration_ms(ms, precision)),
+                );
+                // Baseline deltas compare means only
+                if let Some(ms) = delta.and_then(|d| d.latency_ms).filter(|_| state.latency_stat == LatencyStat::Mean) {
+                    latency_text.push_str(&format!(" ({})", format_signed(ms, 0, precision)));
+                }
+
+                let mut cells = vec![
                     Cell::from(format!("  {}", shorten_url(&backend.url)))
                         .style(Style::default().fg(colors.text_primary)),
                     Cell::from(status_text).style(status_style),
                     Cell::from(partition_str).style(Style::default().fg(colors.text_secondary)),
                     Cell::from(gpu_load).style(Style::default().fg(colors.accent_id)),
-                    Cell::from(inflight.to_string()).style(Style::default().fg(
-                        if inflight > 0 { colors.accent } else { colors.text_primary },
+                    Cell::from(format_count(backend.inflight)).style(Style::default().fg(
+                        if backend.inflight > 0 { colors.accent } else { colors.text_primary },
                     )),
-                    Cell::from(backend.requests.to_string()).style(Style::default().fg(colors.text_primary)),
-                    Cell::from(backend.errors.to_string()).style(if backend.errors > 0 {
+                    Cell::from(with_delta(backend.requests, delta.map(|d| d.requests)))
+                        .style(Style::default().fg(colors.text_primary)),
+                    Cell::from(with_delta(backend.errors, delta.map(|d| d.errors))).style(if backend.errors > 0 {
                         Style::default().fg(colors.status_error)
                     } else {
                         Style::default().fg(colors.text_primary)
                     }),
-                    Cell::from(format!("{:.0}ms", backend.avg_latency_ms)).style(Style::default().fg(colors.accent_latency)),
-                ]);
-                rows.push(row);
+                    Cell::from(latency_text).style(Style::default().fg(latency_color(backend, latency_thresholds.as_ref(), colors))),
+                ];
+                if state.health_weights.is_some() {
+                    cells.extend([Cell::from(""), score_cell(score(backend), colors)]);
+                }
+                rows.push(Row::new(cells));
                 total_backends += 1;
             }
+            if hidden > 0 {
+                rows.push(Row::new(vec![Cell::from(format!("  (\u{2026} {} more)", hidden))
+                    .style(Style::default().fg(colors.text_disabled))]));
+                total_backends += hidden;
+            }
         } else {
             total_backends += pool.backends.len();
         }
     }
 
     if rows.is_empty() {
-        rows.push(Row::new(vec![Cell::from("  No backends registered")]));
+        rows.push(Row::new(vec![Cell::from(empty_message(state))]));
     }
 
-    let title = format!(
-        " GPU Backends ({} models, {} backends) ",
-        proxy.stats.pools.len(),
-        total_backends,
-    );
+    let title = panel_title(proxy, state, pools.len(), total_backends);
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Percentage(22), // model / backend
-            Constraint::Percentage(12), // status
-            Constraint::Percentage(10), // partition
-            Constraint::Percentage(8),  // gpu load
-            Constraint::Percentage(9),  // inflight
-            Constraint::Percentage(10), // requests
-            Constraint::Percentage(8),  // errors
-            Constraint::Percentage(11), // avg latency
-        ],
-    )
+    let mut widths = vec![
+        Constraint::Percentage(20), // model / backend
+        Constraint::Percentage(10), // status
+        Constraint::Percentage(8),  // partition
+        Constraint::Percentage(7),  // gpu load
+        Constraint::Percentage(8),  // inflight
+        Constraint::Percentage(8),  // requests
+        Constraint::Percentage(6),  // errors
+        Constraint::Percentage(15), // avg / recent latency
+        Constraint::Percentage(14), // capacity
+    ];
+    if state.health_weights.is_some() {
+        widths[0] = Constraint::Percentage(16);
+        widths.push(Constraint::Percentage(5)); // health score
+    }
+    let table = Table::new(rows, widths)
     .header(header)
     .block(
         Block::default()
@@ -202,6 +516,341 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppSta
     frame.render_widget(table, area);
 }
 
+fn empty_message(state: &AppState) -> String {
+    match &state.partition {
+        Some(partition) => format!("  No backends in partition {}", partition),
+        None => "  No backends registered".to_string(),
+    }
+}
+
+/// Panel title: counts, then any partition scope, endpoint mismatch and
+/// baseline notes.
+fn panel_title(proxy: &ProxySnapshot, state: &AppState, models: usize, total_backends: usize) -> String {
+    let mut title = format!(" GPU Backends ({} models, {} backends) ", models, total_backends);
+    if let Some(partition) = &state.partition {
+        title.push_str(&format!("\u{2502} partition {} ", partition));
+    }
+    // An older proxy without /queue/status lists no backends there at all
+    if let Some(note) = backend_mismatch(proxy).note().filter(|_| !proxy.queue.backends.is_empty()) {
+        title.push_str(&format!("\u{2502} \u{26a0} {} ", note));
+    }
+    if let Some(base) = &state.baseline {
+        let age = match (base.fetched_at, proxy.fetched_at) {
+            (Some(then), Some(now)) => format!(" {}", format_elapsed(now.saturating_duration_since(then).as_millis() as f64)),
+            _ => String::new(),
+        };
+        title.push_str(&format!("\u{2502} \u{0394} vs baseline{} ", age));
+    }
+    title
+}
+
+/// One row per pool and expanded backend, for panels too narrow for the
+/// table's columns. Same data, selection and expansion as the table.
+fn draw_narrow(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, pools: &[PoolView], state: &AppState, colors: &ColorScheme) {
+    let is_focused = state.focused_panel == FocusedPanel::Backends;
+    let mut rows: Vec<Row> = Vec::new();
+    let mut total_backends = 0;
+
+    for (pool_idx, pool) in pools.iter().enumerate() {
+        let is_selected = is_focused && pool_idx == state.backend_selected;
+        let is_expanded = state.backend_expanded.contains(&pool.model);
+        let arrow = if is_expanded { "\u{25be}" } else { "\u{25b8}" };
+        let health_color = if pool.total > 0 && pool.healthy == pool.total {
+            colors.status_ok
+        } else if pool.healthy > 0 {
+            colors.status_warn
+        } else {
+            colors.status_error
+        };
+        let row_style = if is_selected { colors.selected_row() } else { Style::default().bg(colors.row_alt_bg) };
+        let text = format!(
+            "{} {} {}/{} load={} inflt={}",
+            arrow,
+            pool.model,
+            pool.healthy,
+            pool.total,
+            format_count(pool.gpu_load),
+            format_count(pool.inflight),
+        );
+        rows.push(
+            Row::new(vec![colors.first_cell(text, Style::default().fg(health_color).add_modifier(Modifier::BOLD), is_selected)])
+                .style(row_style),
+        );
+
+        if is_expanded {
+            let (shown, hidden) = pool.visible_backends(state.top_backends);
+            total_backends += shown.len() + hidden;
+            let port = |b: &BackendView| b.url.rsplit(':').next().unwrap_or_default().to_string();
+            let mut ports: Vec<String> = pool.backends.iter().map(port).collect();
+            ports.sort();
+            let show_host = ports.windows(2).any(|w| w[0] == w[1]);
+            for backend in shown {
+                let color = if backend.healthy { colors.text_primary } else { colors.status_error };
+                let line = narrow_backend_line(backend, state.latency_stat, show_host, state.precision);
+                rows.push(Row::new(vec![Cell::from(format!("  {}", line)).style(Style::default().fg(color))]));
+            }
+            if hidden > 0 {
+                rows.push(Row::new(vec![Cell::from(format!("  (\u{2026} {} more)", hidden))
+                    .style(Style::default().fg(colors.text_disabled))]));
+            }
+        } else {
+            total_backends += pool.backends.len();
+        }
+    }
+    if rows.is_empty() {
+        rows.push(Row::new(vec![Cell::from(empty_message(state))]));
+    }
+
+    let border_color = if is_focused { colors.border_focused } else { colors.border_normal };
+    let table = Table::new(rows, [Constraint::Percentage(100)]).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(panel_title(proxy, state, pools.len(), total_backends))
+            .border_style(Style::default().fg(border_color)),
+    );
+    frame.render_widget(table, area);
+}
+
+/// A backend as one dense line, e.g. ":8001 \u{2713} load=3 inflt=2 5% 120ms".
+/// `show_host` prefixes the host, for pools where ports alone are ambiguous.
+fn narrow_backend_line(backend: &BackendView, stat: LatencyStat, show_host: bool, precision: Precision) -> String {
+    let port = backend.url.rsplit(':').next().unwrap_or(&backend.url);
+    let host = if show_host {
+        shorten_url(&backend.url).rsplit_once(':').map_or(String::new(), |(host, _)| host.to_string())
+    } else {
+        String::new()
+    };
+    let errors = match backend.requests {
+        0 => "-".to_string(),
+        n => format_pct(backend.errors as f64 / n as f64 * 100.0, 0, precision),
+    };
+    let latency = match stat {
+        LatencyStat::Mean => Some(backend.avg_latency_ms).filter(|_| backend.requests > 0),
+        LatencyStat::P95 => backend.p95_latency_ms,
+    };
+    format!(
+        "{}:{} {} load={} inflt={} {} {}",
+        host,
+        port,
+        if backend.healthy { "\u{2713}" } else { "\u{2717}" },
+        backend.gpu_load.map_or("-".to_string(), format_count),
+        format_count(backend.inflight),
+        errors,
+        latency.map_or("-".to_string(), |ms| format_duration_ms(ms, precision)),
+    )
+}
+
 fn shorten_url(url: &str) -> String {
     url.replace("http://", "").replace("https://", "")
 }
+
+/// Pool gauge of in-flight requests against estimated capacity.
+fn capacity_cell<'a>(pool: &PoolView, per_backend: u64, colors: &ColorScheme) -> Cell<'a> {
+    let Some(ratio) = pool.saturation(per_backend) else {
+        return Cell::from("-").style(Style::default().fg(colors.text_disabled));
+    };
+    let filled = ((ratio.min(1.0) * GAUGE_WIDTH as f64).round() as usize).min(GAUGE_WIDTH);
+    let color = if ratio >= SATURATION_ERROR {
+        colors.status_error
+    } else if ratio >= SATURATION_WARN {
+        colors.status_warn
+    } else {
+        colors.status_ok
+    };
+    Cell::from(format!(
+        "{}{} {}/{}",
+        "\u{2588}".repeat(filled),
+        "\u{2591}".repeat(GAUGE_WIDTH - filled),
+        format_count(pool.inflight),
+        format_count(pool.capacity(per_backend)),
+    ))
+    .style(Style::default().fg(color))
+}
+
+/// Latency color relative to the fleet: red for the slowest, yellow above
+/// the median, green at or below it. Backends outside the comparison set keep
+/// the neutral latency accent.
+fn latency_color(backend: &BackendView, thresholds: Option<&LatencyThresholds>, colors: &ColorScheme) -> Color {
+    let Some(t) = thresholds else {
+        return colors.accent_latency;
+    };
+    if !backend.healthy || backend.requests == 0 || t.count < 2 {
+        return colors.accent_latency;
+    }
+    if backend.avg_latency_ms >= t.max_ms {
+        colors.status_error
+    } else if backend.avg_latency_ms > t.median_ms {
+        colors.status_warn
+    } else {
+        colors.status_ok
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_support::snapshot;
+
+    fn backend(healthy: bool, requests: u64, errors: u64, avg_latency_ms: f64, inflight: u64) -> BackendView {
+        BackendView {
+            url: "http://gpu:8001".into(),
+            healthy,
+            partition: String::new(),
+            gpu_load: None,
+            inflight,
+            requests,
+            errors,
+            avg_latency_ms,
+            p95_latency_ms: None,
+            recent_latency_ms: None,
+        }
+    }
+
+    #[test]
+    fn narrow_line_packs_status_load_errors_and_latency() {
+        let mut b = backend(true, 100, 5, 120.0, 2);
+        b.gpu_load = Some(3);
+        assert_eq!(narrow_backend_line(&b, LatencyStat::Mean, false, Precision::default()), ":8001 \u{2713} load=3 inflt=2 5% 120ms");
+        assert_eq!(narrow_backend_line(&b, LatencyStat::P95, false, Precision::default()), ":8001 \u{2713} load=3 inflt=2 5% -");
+        let idle = backend(false, 0, 0, 0.0, 0);
+        assert_eq!(narrow_backend_line(&idle, LatencyStat::Mean, false, Precision::default()), ":8001 \u{2717} load=- inflt=0 - -");
+        assert!(narrow_backend_line(&idle, LatencyStat::Mean, true, Precision::default()).starts_with("gpu:8001 "));
+    }
+
+    #[test]
+    fn health_score_weighs_health_errors_latency_and_headroom() {
+        let w = HealthWeights::default();
+        // Healthy, error-free, at the median, idle
+        assert_eq!(backend_health_score(&backend(true, 100, 0, 1000.0, 0), Some(1000.0), 8, &w), 100);
+        // Down but otherwise clean loses the whole health weight
+        assert_eq!(backend_health_score(&backend(false, 100, 0, 1000.0, 0), Some(1000.0), 8, &w), 60);
+        // 5% errors: half the error weight; 2x median: half the latency weight
+        assert_eq!(backend_health_score(&backend(true, 100, 5, 2000.0, 0), Some(1000.0), 8, &w), 78);
+        // Full at capacity, 10%+ errors, 3x median: only the health flag left
+        assert_eq!(backend_health_score(&backend(true, 100, 20, 3000.0, 8), Some(1000.0), 8, &w), 40);
+        // No traffic and no fleet reference count as perfect
+        assert_eq!(backend_health_score(&backend(true, 0, 0, 0.0, 4), None, 8, &w), 93);
+        let errors_only = HealthWeights { healthy: 0.0, errors: 1.0, latency: 0.0, headroom: 0.0 };
+        assert_eq!(backend_health_score(&backend(false, 100, 5, 9000.0, 8), Some(1000.0), 8, &errors_only), 50);
+    }
+
+    #[test]
+    fn pool_views_aggregate_backends_and_join_queue_load() {
+        let proxy = snapshot(
+            json!({"pools": [{
+                "model": "org/model-a",
+                "backends": [
+                    {"url": "http://gpu:8001", "healthy": true, "requests": 5, "errors": 1},
+                    {"url": "http://gpu:8002", "healthy": false, "requests": 2}
+                ]
+            }]}),
+            json!({"backends": [
+                {"url": "http://gpu:8001", "healthy": true, "gpu_load": 3, "inflight": 2}
+            ]}),
+        );
+        let pools = build_pool_views(&proxy);
+        assert_eq!(pools.len(), 1);
+        let pool = &pools[0];
+        assert_eq!((pool.healthy, pool.total), (1, 2));
+        assert_eq!((pool.requests, pool.errors), (7, 1));
+        assert_eq!((pool.gpu_load, pool.inflight), (3, 2));
+        assert_eq!(pool.backends[0].gpu_load, Some(3));
+        assert_eq!(pool.backends[1].gpu_load, None);
+        assert_eq!(pool.backends[1].inflight, 0);
+        assert_eq!(pool.capacity(8), 8);
+        assert_eq!(pool.saturation(4), Some(0.5));
+    }
+
+    #[test]
+    fn visible_backends_keeps_busiest_and_counts_the_rest() {
+        let proxy = snapshot(
+            json!({"pools": [{"model": "m", "backends": [
+                {"url": "http://gpu:8001", "healthy": true},
+                {"url": "http://gpu:8002", "healthy": false},
+                {"url": "http://gpu:8003", "healthy": true},
+                {"url": "http://gpu:8004", "healthy": true}
+            ]}]}),
+            json!({"backends": [
+                {"url": "http://gpu:8001", "gpu_load": 1, "inflight": 1},
+                {"url": "http://gpu:8003", "gpu_load": 4, "inflight": 3},
+                {"url": "http://gpu:8004", "gpu_load": 1, "inflight": 2}
+            ]}),
+        );
+        let pool = &build_pool_views(&proxy)[0];
+        let (shown, hidden) = pool.visible_backends(Some(2));
+        let urls: Vec<&str> = shown.iter().map(|b| b.url.as_str()).collect();
+        assert_eq!(urls, ["http://gpu:8003", "http://gpu:8004"]);
+        assert_eq!(hidden, 2);
+        // Aggregates still cover the hidden backends
+        assert_eq!((pool.healthy, pool.total, pool.gpu_load), (3, 4, 6));
+        assert_eq!(pool.visible_backends(None).0.len(), 4);
+        assert_eq!(pool.visible_backends(Some(10)).1, 0);
+    }
+
+    #[test]
+    fn backend_mismatch_lists_backends_only_one_endpoint_reports() {
+        let proxy = snapshot(
+            json!({"pools": [{"model": "m", "backends": [
+                {"url": "http://gpu:8001", "healthy": true},
+                {"url": "http://gpu:8002", "healthy": true}
+            ]}]}),
+            json!({"backends": [
+                {"url": "http://gpu:8001", "gpu_load": 1},
+                {"url": "http://gpu:8004", "gpu_load": 2},
+                {"url": "http://gpu:8003", "gpu_load": 3}
+            ]}),
+        );
+        let mismatch = backend_mismatch(&proxy);
+        assert_eq!(mismatch.queue_only, ["http://gpu:8003", "http://gpu:8004"]);
+        assert_eq!(mismatch.stats_only, ["http://gpu:8002"]);
+        assert_eq!(
+            mismatch.note().unwrap(),
+            "backends 2 in queue not in stats (:8003 :8004), 1 in stats not in queue (:8002)"
+        );
+        assert_eq!(build_pool_views(&proxy)[0].backends[1].gpu_load, None);
+
+        let agreed = snapshot(
+            json!({"pools": [{"model": "m", "backends": [{"url": "http://gpu:8001"}]}]}),
+            json!({"backends": [{"url": "http://gpu:8001"}]}),
+        );
+        assert_eq!(backend_mismatch(&agreed).note(), None);
+    }
+
+    #[test]
+    fn backend_deltas_compare_against_baseline() {
+        let baseline = snapshot(
+            json!({"pools": [{"model": "m", "backends": [
+                {"url": "http://gpu:8001", "healthy": true, "requests": 100, "errors": 2, "avg_latency_ms": 400.0}
+            ]}]}),
+            json!({}),
+        );
+        let current = snapshot(
+            json!({"pools": [{"model": "m", "backends": [
+                {"url": "http://gpu:8001", "healthy": true, "requests": 130, "errors": 2, "avg_latency_ms": 450.0},
+                {"url": "http://gpu:8002", "healthy": true, "requests": 5}
+            ]}]}),
+            json!({}),
+        );
+        let deltas = backend_deltas(&baseline, &current);
+        assert_eq!(
+            deltas["http://gpu:8001"],
+            BackendDelta { requests: 30, errors: 0, latency_ms: Some(50.0) }
+        );
+        assert_eq!(deltas["http://gpu:8002"], BackendDelta { requests: 5, errors: 0, latency_ms: None });
+        assert_eq!(with_delta(130, Some(30)), "130 +30");
+        assert_eq!(with_delta(2, None), "2");
+    }
+
+    #[test]
+    fn empty_pool_yields_zeroed_view() {
+        let proxy = snapshot(json!({"pools": [{"model": "org/orphan-model"}]}), json!({}));
+        let pools = build_pool_views(&proxy);
+        assert_eq!(pools.len(), 1);
+        assert_eq!((pools[0].healthy, pools[0].total), (0, 0));
+        assert!(pools[0].backends.is_empty());
+        assert_eq!(pools[0].saturation(8), None);
+        assert_eq!(proxy.stats.empty_pools(), vec!["org/orphan-model"]);
+    }
+}
@@ -0,0 +1,159 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use unicode_width::UnicodeWidthStr;
+
+use crate::model::{AppState, ProxySnapshot, StatsResponse};
+use super::format::{format_pct, truncate};
+use super::theme::ColorScheme;
+
+/// Characters for the error-delta sparkline, lowest to highest.
+const SPARK_BARS: [char; 8] = ['\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}'];
+/// Widest model name shown before truncation.
+const MODEL_WIDTH: usize = 16;
+
+/// Error totals for one model pool.
+#[derive(Debug, PartialEq)]
+pub struct ModelErrors {
+    pub model: String,
+    pub requests: u64,
+    pub errors: u64,
+}
+
+impl ModelErrors {
+    pub fn rate_pct(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.requests as f64 * 100.0
+        }
+    }
+}
+
+/// Per-model error totals from `/stats` pools, worst error rate first.
+pub fn model_errors(stats: &StatsResponse) -> Vec<ModelErrors> {
+    let mut rows: Vec<ModelErrors> = stats
+        .pools
+        .iter()
+        .map(|pool| ModelErrors {
+            model: pool.model.clone(),
+            requests: pool.backends.iter().map(|b| b.requests).sum(),
+            errors: pool.backends.iter().map(|b| b.errors).sum(),
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.rate_pct()
+            .total_cmp(&a.rate_pct())
+            .then(b.errors.cmp(&a.errors))
+            .then(a.model.cmp(&b.model))
+    });
+    rows
+}
+
+/// One bar per value, scaled to the largest; all-zero input stays flat.
+pub fn sparkline(values: impl Iterator<Item = u64> + Clone) -> String {
+    let max = values.clone().max().unwrap_or(0);
+    values
+        .map(|v| {
+            if max == 0 {
+                SPARK_BARS[0]
+            } else {
+                SPARK_BARS[((v as f64 / max as f64) * (SPARK_BARS.len() - 1) as f64).round() as usize]
+            }
+        })
+        .collect()
+}
+
+/// Draw the Errors by Model panel.
+pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppState, colors: &ColorScheme) {
+    let precision = state.precision;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Errors by Model ")
+        .border_style(Style::default().fg(colors.border_focused));
+
+    let mut lines: Vec<Line> = Vec::new();
+    if !proxy.connected.is_up() {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", proxy.connected.placeholder()),
+            Style::default().fg(colors.text_disabled),
+        )));
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+        return;
+    }
+
+    let rows = model_errors(&proxy.stats);
+    lines.push(Line::from(Span::styled(
+        format!("  {:<w$} {:>7} {:>7}  Trend", "Model", "Errors", "Rate", w = MODEL_WIDTH),
+        Style::default().fg(colors.table_header).add_modifier(Modifier::BOLD),
+    )));
+    for row in &rows {
+        let rate = row.rate_pct();
+        let style = if row.errors > 0 && rate >= state.model_error_threshold {
+            Style::default().fg(colors.status_error).add_modifier(Modifier::BOLD)
+        } else if row.errors > 0 {
+            Style::default().fg(colors.status_warn)
+        } else {
+            Style::default().fg(colors.text_primary)
+        };
+        // Padded by display width, since `{:<w$}` counts chars
+        let model = truncate(&row.model, MODEL_WIDTH);
+        let model = format!("{}{}", model, " ".repeat(MODEL_WIDTH.saturating_sub(model.width())));
+        let trend = state
+            .model_error_trend
+            .get(&row.model)
+            .map(|t| sparkline(t.deltas.iter().copied()))
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} {:>7} {:>7}  ", model, row.errors, format_pct(rate, 1, precision)),
+                style,
+            ),
+            Span::styled(trend, Style::default().fg(colors.accent)),
+        ]));
+    }
+    if rows.iter().all(|r| r.errors == 0) {
+        lines.push(Line::from(Span::styled(
+            "  No errors",
+            Style::default().fg(colors.status_ok),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn model_errors_rank_worst_rate_first() {
+        let stats: StatsResponse = serde_json::from_value(json!({
+            "pools": [
+                {"model": "small", "backends": [
+                    {"url": "http://a:8001", "requests": 100, "errors": 1},
+                    {"url": "http://a:8002", "requests": 100, "errors": 1}
+                ]},
+                {"model": "large", "backends": [{"url": "http://b:8001", "requests": 50, "errors": 5}]},
+                {"model": "idle", "backends": []}
+            ]
+        }))
+        .unwrap();
+        let rows = model_errors(&stats);
+        let order: Vec<&str> = rows.iter().map(|r| r.model.as_str()).collect();
+        assert_eq!(order, ["large", "small", "idle"]);
+        assert_eq!((rows[1].requests, rows[1].errors), (200, 2));
+        assert_eq!(rows[0].rate_pct(), 10.0);
+    }
+
+    #[test]
+    fn sparkline_scales_to_largest_delta() {
+        assert_eq!(sparkline([0, 2, 4].into_iter()), "\u{2581}\u{2585}\u{2588}");
+        assert_eq!(sparkline([0, 0].into_iter()), "\u{2581}\u{2581}");
+        assert_eq!(sparkline(std::iter::empty::<u64>()), "");
+    }
+}
@@ -0,0 +1,305 @@
+//! Shared number formatting for durations, percentages and counts, so every
+//! panel honors the same `--precision`, `--uptime-style` and `--raw-counts`
+//! settings, plus width-aware truncation for text cells.
+
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Decimals for durations, percentages and ratios: `--precision` (0-3) when
+/// given, otherwise each metric's own default. Carried in `AppState`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Precision(Option<usize>);
+
+impl Precision {
+    pub fn new(decimals: Option<u8>) -> Self {
+        Precision(decimals.map(usize::from))
+    }
+
+    /// Decimals to use for a metric whose default is `default`.
+    pub fn decimals(self, default: usize) -> usize {
+        self.0.unwrap_or(default)
+    }
+}
+
+/// How uptimes are written.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UptimeStyle {
+    /// "250:13:07", hours unbounded.
+    Clock,
+    /// "13m 07s", "10h 13m", "2d 10h 13m".
+    Human,
+    /// ISO 8601 duration, e.g. "P2DT10H13M7S".
+    Iso,
+}
+
+static UPTIME_STYLE: AtomicU8 = AtomicU8::new(UptimeStyle::Human as u8);
+
+/// Set the uptime style. Called once at startup.
+pub fn set_uptime_style(style: UptimeStyle) {
+    UPTIME_STYLE.store(style as u8, Ordering::Relaxed);
+}
+
+fn uptime_style() -> UptimeStyle {
+    match UPTIME_STYLE.load(Ordering::Relaxed) {
+        s if s == UptimeStyle::Clock as u8 => UptimeStyle::Clock,
+        s if s == UptimeStyle::Iso as u8 => UptimeStyle::Iso,
+        _ => UptimeStyle::Human,
+    }
+}
+
+static GROUPING: AtomicBool = AtomicBool::new(true);
+
+/// Turn thousands separators in counts on or off. Called once at startup.
+pub fn set_grouping(grouping: bool) {
+    GROUPING.store(grouping, Ordering::Relaxed);
+}
+
+/// Format a count as "1,234,567", or plain digits with `--raw-counts`.
+pub fn format_count(n: u64) -> String {
+    count_with(n, GROUPING.load(Ordering::Relaxed))
+}
+
+/// Format a signed change as "+1,234" / "-56", grouped like `format_count`.
+pub fn format_delta(n: i64) -> String {
+    let sign = if n < 0 { '-' } else { '+' };
+    format!("{}{}", sign, format_count(n.unsigned_abs()))
+}
+
+/// Digits grouped in threes with commas, independent of the system locale.
+fn count_with(n: u64, grouping: bool) -> String {
+    let digits = n.to_string();
+    if !grouping {
+        return digits;
+    }
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Format a latency as "850ms" or "1.2s". Precision 0 keeps whole
+/// milliseconds ("1234ms") rather than rounding to whole seconds.
+pub fn format_duration_ms(ms: f64, precision: Precision) -> String {
+    duration_with(ms, precision.decimals(1))
+}
+
+/// Format a percentage with the configured decimals, or `default` decimals.
+pub fn format_pct(pct: f64, default: usize, precision: Precision) -> String {
+    pct_with(pct, precision.decimals(default))
+}
+
+/// Format a plain figure such as a ratio or a per-GPU rate.
+pub fn format_decimal(value: f64, default: usize, precision: Precision) -> String {
+    if !value.is_finite() {
+        return "-".to_string();
+    }
+    format!("{:.*}", precision.decimals(default), value)
+}
+
+/// Format a signed change such as a latency delta, e.g. "+12" / "-3.5".
+pub fn format_signed(value: f64, default: usize, precision: Precision) -> String {
+    if !value.is_finite() {
+        return "-".to_string();
+    }
+    format!("{:+.*}", precision.decimals(default), value)
+}
+
+fn duration_with(ms: f64, decimals: usize) -> String {
+    if !ms.is_finite() {
+        return "-".to_string();
+    }
+    match decimals {
+        0 => format!("{:.0}ms", ms),
+        _ if ms < 1000.0 => format!("{:.0}ms", ms),
+        d => format!("{:.*}s", d, ms / 1000.0),
+    }
+}
+
+fn pct_with(pct: f64, decimals: usize) -> String {
+    if !pct.is_finite() {
+        return "-".to_string();
+    }
+    format!("{:.*}%", decimals, pct)
+}
+
+/// Format a longer duration (session-level) as e.g. "45s", "2m30s", "1h05m".
+pub fn format_elapsed(ms: f64) -> String {
+    if !ms.is_finite() {
+        return "-".to_string();
+    }
+    let total_secs = (ms / 1000.0).round() as u64;
+    if total_secs < 60 {
+        format!("{}s", total_secs)
+    } else if total_secs < 3600 {
+        let mins = total_secs / 60;
+        let secs = total_secs % 60;
+        format!("{}m{:02}s", mins, secs)
+    } else {
+        let hours = total_secs / 3600;
+        let mins = (total_secs % 3600) / 60;
+        format!("{}h{:02}m", hours, mins)
+    }
+}
+
+/// Format a proxy uptime in the configured style.
+pub fn format_uptime(secs: f64) -> String {
+    uptime_with(secs, uptime_style())
+}
+
+fn uptime_with(secs: f64, style: UptimeStyle) -> String {
+    if !secs.is_finite() {
+        return "-".to_string();
+    }
+    let total = secs.max(0.0) as u64;
+    let (d, h, m, s) = (total / 86_400, (total % 86_400) / 3600, (total % 3600) / 60, total % 60);
+    match style {
+        UptimeStyle::Clock => format!("{:02}:{:02}:{:02}", total / 3600, m, s),
+        UptimeStyle::Human if d > 0 => format!("{}d {}h {:02}m", d, h, m),
+        UptimeStyle::Human if h > 0 => format!("{}h {:02}m", h, m),
+        UptimeStyle::Human => format!("{}m {:02}s", m, s),
+        UptimeStyle::Iso => {
+            let mut out = "P".to_string();
+            if d > 0 {
+                out.push_str(&format!("{}D", d));
+            }
+            out.push('T');
+            for (value, unit) in [(h, 'H'), (m, 'M')] {
+                if value > 0 {
+                    out.push_str(&format!("{}{}", value, unit));
+                }
+            }
+            if s > 0 || out.ends_with('T') {
+                out.push_str(&format!("{}S", s));
+            }
+            out
+        }
+    }
+}
+
+/// Format a byte count as "512 B", "12.3 KB" or "4.1 MB" (powers of 1024).
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Cut `text` to at most `max` terminal columns, marking the cut with "..".
+/// Wide (CJK) characters count as two columns and combining marks as zero,
+/// so a cut never splits a character from its marks or overflows the cell.
+pub fn truncate(text: &str, max: usize) -> String {
+    if text.width() <= max {
+        return text.to_string();
+    }
+    let budget = if max <= 2 { max } else { max - 2 };
+    let mut out = String::new();
+    let mut used = 0;
+    for ch in text.chars() {
+        let w = ch.width().unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        out.push(ch);
+        used += w;
+    }
+    if max > 2 {
+        out.push_str("..");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_finite_durations_show_placeholder() {
+        let auto = Precision::default();
+        assert_eq!(format_duration_ms(f64::NAN, auto), "-");
+        assert_eq!(format_duration_ms(f64::INFINITY, auto), "-");
+        assert_eq!(format_elapsed(f64::NEG_INFINITY), "-");
+        assert_eq!(format_pct(f64::NAN, 1, auto), "-");
+        assert_eq!(format_decimal(f64::NAN, 1, auto), "-");
+    }
+
+    #[test]
+    fn counts_group_thousands_unless_raw() {
+        assert_eq!(count_with(0, true), "0");
+        assert_eq!(count_with(999, true), "999");
+        assert_eq!(count_with(1_000, true), "1,000");
+        assert_eq!(count_with(1_234_567, true), "1,234,567");
+        assert_eq!(count_with(1_234_567, false), "1234567");
+        assert_eq!(format_delta(-1_500), "-1,500");
+        assert_eq!(format_delta(0), "+0");
+    }
+
+    #[test]
+    fn bytes_scale_by_1024() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(12_595), "12.3 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+
+    #[test]
+    fn uptime_styles_cover_minutes_hours_and_days() {
+        let (sub_hour, hours, days) = (787.0, 36_787.0, 901_987.0);
+        assert_eq!(uptime_with(sub_hour, UptimeStyle::Clock), "00:13:07");
+        assert_eq!(uptime_with(hours, UptimeStyle::Clock), "10:13:07");
+        assert_eq!(uptime_with(days, UptimeStyle::Clock), "250:33:07");
+        assert_eq!(uptime_with(sub_hour, UptimeStyle::Human), "13m 07s");
+        assert_eq!(uptime_with(hours, UptimeStyle::Human), "10h 13m");
+        assert_eq!(uptime_with(days, UptimeStyle::Human), "10d 10h 33m");
+        assert_eq!(uptime_with(sub_hour, UptimeStyle::Iso), "PT13M7S");
+        assert_eq!(uptime_with(hours, UptimeStyle::Iso), "PT10H13M7S");
+        assert_eq!(uptime_with(days, UptimeStyle::Iso), "P10DT10H33M7S");
+        assert_eq!(uptime_with(0.0, UptimeStyle::Iso), "PT0S");
+        assert_eq!(uptime_with(86_400.0, UptimeStyle::Iso), "P1DT0S");
+    }
+
+    #[test]
+    fn decimals_control_duration_and_pct() {
+        assert_eq!(duration_with(1234.0, 1), "1.2s");
+        assert_eq!(duration_with(1234.0, 2), "1.23s");
+        assert_eq!(duration_with(1234.0, 0), "1234ms");
+        assert_eq!(duration_with(850.0, 3), "850ms");
+        assert_eq!(pct_with(12.345, 2), "12.35%");
+        assert_eq!(pct_with(12.345, 0), "12%");
+
+        let two = Precision::new(Some(2));
+        assert_eq!(format_decimal(2.0, 1, Precision::default()), "2.0");
+        assert_eq!(format_decimal(2.0, 1, two), "2.00");
+        assert_eq!(format_signed(-12.0, 0, Precision::default()), "-12");
+        assert_eq!(format_signed(12.0, 0, two), "+12.00");
+    }
+
+    #[test]
+    fn truncate_is_char_aware() {
+        assert_eq!(truncate("hello", 5), "hello");
+        assert_eq!(truncate("hello world", 7), "hello..");
+        assert_eq!(truncate("ééééé", 4), "éé..");
+    }
+
+    #[test]
+    fn truncate_measures_display_width() {
+        // Each CJK character is two columns wide
+        assert_eq!(truncate("任务名称", 8), "任务名称");
+        assert_eq!(truncate("任务名称测试", 8), "任务名..");
+        assert_eq!(truncate("任务名称测试", 7), "任务..");
+        // Combining acute accents take no width and stay with their base
+        let combining = "e\u{301}e\u{301}e\u{301}e\u{301}";
+        assert_eq!(truncate(combining, 4), combining);
+        assert_eq!(truncate(&format!("{}x", combining), 4), "e\u{301}e\u{301}..");
+    }
+}
@@ -1,24 +1,25 @@
 use ratatui::Frame;
 use ratatui::layout::Rect;
-use ratatui::style::Style;
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
-use crate::model::ProxySnapshot;
+use crate::model::{Alert, AppState, Connection, ProxySnapshot, ReplayStatus, TimestampFormat};
+use super::format::{format_count, format_decimal, format_duration_ms, format_elapsed, format_signed, format_uptime};
+use super::timefmt::{format_epoch, format_now, utc};
 use super::theme::ColorScheme;
 
-pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, proxy_url: &str, colors: &ColorScheme) {
-    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, proxy_url: &str, state: &AppState, colors: &ColorScheme) {
+    let mut now = format_now("%Y-%m-%d %H:%M:%S");
+    if utc() {
+        now.push_str(" UTC");
+    }
 
-    let status_color = if proxy.connected {
-        colors.status_ok
-    } else {
-        colors.status_error
-    };
-    let status_text = if proxy.connected {
-        "CONNECTED"
-    } else {
-        "OFFLINE"
+    let (status_text, status_color) = match proxy.connected {
+        Connection::Up => ("CONNECTED", colors.status_ok),
+        _ if proxy.limited.is_some() => ("LIMITED", colors.status_warn),
+        Connection::Never => ("CONNECTING\u{2026}", colors.text_secondary),
+        Connection::Down => ("OFFLINE", colors.status_error),
     };
 
     let uptime = format_uptime(proxy.stats.uptime_seconds);
@@ -29,20 +30,139 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, proxy_url: &st
         proxy.stats.strategy.clone()
     };
 
-    let line = Line::from(vec![
+    let mut spans = Vec::new();
+    if let Some(replay) = &state.replay {
+        spans.push(Span::styled(
+            replay_badge(replay, state.timestamp_format),
+            Style::default()
+                .fg(colors.text_primary)
+                .bg(if replay.ended { colors.status_error } else { colors.accent })
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw("  "));
+    }
+    if let Some(age) = state.history_age {
+        spans.push(Span::styled(
+            format!(" HISTORY -{}s ", age.as_secs()),
+            Style::default()
+                .fg(colors.text_primary)
+                .bg(colors.status_warn)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw("  "));
+    }
+    spans.extend([
         Span::styled("Proxy: ", Style::default().fg(colors.text_primary)),
         Span::styled(proxy_url, Style::default().fg(colors.accent)),
         Span::raw("  "),
         Span::styled(status_text, Style::default().fg(status_color)),
+        Span::styled(format!(" {}", pulse(proxy.polls_succeeded)), Style::default().fg(status_color)),
+    ]);
+    if let Some(health) = proxy.limited.as_ref().filter(|h| !h.summary().is_empty()) {
+        spans.push(Span::styled(
+            format!(" ({})", health.summary()),
+            Style::default().fg(colors.text_secondary),
+        ));
+    }
+    spans.extend([
         Span::raw("  \u{2502}  "),
         Span::styled("LB: ", Style::default().fg(colors.text_primary)),
         Span::styled(strategy_display, Style::default().fg(colors.accent_id)),
         Span::raw("  \u{2502}  "),
         Span::styled(format!("Uptime: {}", uptime), Style::default().fg(colors.text_primary)),
         Span::raw("  \u{2502}  "),
+        Span::styled("Turns: ", Style::default().fg(colors.text_primary)),
+        Span::styled(
+            format_count(proxy.turns_completed),
+            Style::default().fg(colors.accent_count).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            proxy.turns_per_min.map_or(String::new(), |r| format!(" ({}/min)", format_decimal(r, 1, state.precision))),
+            Style::default().fg(colors.text_secondary),
+        ),
+        Span::raw("  \u{2502}  "),
         Span::styled(format!("Refreshed: {}", now), Style::default().fg(colors.text_secondary)),
     ]);
 
+    if let Some(cost) = state.pricing.as_ref().and_then(|p| proxy.queue.estimated_cost(p)) {
+        spans.push(Span::raw("  \u{2502}  "));
+        spans.push(Span::styled(format!("Cost: {}", format_cost(cost)), Style::default().fg(colors.accent_count)));
+    }
+
+    if let Some(skew) = proxy.significant_clock_skew() {
+        spans.push(Span::raw("  \u{2502}  "));
+        spans.push(Span::styled(
+            format!(
+                "\u{26a0} clock skew {}s ({} ahead)",
+                format_signed(skew, 1, state.precision),
+                if skew > 0.0 { "dashboard" } else { "proxy" }
+            ),
+            Style::default().fg(colors.status_warn).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if let Some(cadence) = proxy.lagging_cadence() {
+        spans.push(Span::raw("  \u{2502}  "));
+        spans.push(Span::styled(
+            format!(
+                "\u{26a0} update cadence: {} (target {})",
+                format_duration_ms(cadence.as_secs_f64() * 1000.0, state.precision),
+                format_duration_ms(proxy.target_interval.as_secs_f64() * 1000.0, state.precision)
+            ),
+            Style::default().fg(colors.status_warn).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    // Unacknowledged alerts flash by swapping to reverse video every other second
+    let flash_on = chrono::Local::now().timestamp() % 2 == 0;
+    let alert_style = |unacked: bool| {
+        let style = Style::default().fg(colors.status_error).add_modifier(Modifier::BOLD);
+        if flash_on && unacked {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style
+        }
+    };
+
+    let down: Vec<&Alert> = state.alerts.iter().filter(|a| matches!(a, Alert::BackendDown(_))).collect();
+    if !down.is_empty() {
+        let ports: Vec<String> = down
+            .iter()
+            .filter_map(|a| match a {
+                Alert::BackendDown(url) => Some(format!(":{}", url.rsplit(':').next().unwrap_or(url))),
+                Alert::CompletionStall => None,
+            })
+            .collect();
+        spans.push(Span::raw("  \u{2502}  "));
+        spans.push(Span::styled(
+            format!("\u{26a0} DOWN {}", ports.join(" ")),
+            alert_style(down.iter().any(|a| state.alert_unacked(a))),
+        ));
+    }
+
+    if let Some(silence) = state.completion_stall {
+        let style = alert_style(state.alert_unacked(&Alert::CompletionStall));
+        spans.push(Span::raw("  \u{2502}  "));
+        spans.push(Span::styled(
+            format!(
+                "\u{26a0} STALLED: no completions for {} ({} in flight)",
+                format_elapsed(silence.as_millis() as f64),
+                format_count(proxy.queue.summary.in_flight)
+            ),
+            style,
+        ));
+    }
+
+    if let Some(version) = proxy.stats.unknown_schema_version() {
+        spans.push(Span::raw("  \u{2502}  "));
+        spans.push(Span::styled(
+            format!("\u{26a0} unknown proxy schema v{}", version),
+            Style::default().fg(colors.status_warn).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    let line = Line::from(spans);
+
     let header = Paragraph::new(line)
         .wrap(Wrap { trim: false })
         .block(
@@ -55,10 +175,60 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, proxy_url: &st
     frame.render_widget(header, area);
 }
 
-fn format_uptime(secs: f64) -> String {
-    let total = secs as u64;
-    let h = total / 3600;
-    let m = (total % 3600) / 60;
-    let s = total % 60;
-    format!("{:02}:{:02}:{:02}", h, m, s)
+pub fn format_cost(cost: f64) -> String {
+    if cost < 1.0 {
+        format!("${:.4}", cost)
+    } else {
+        format!("${:.2}", cost)
+    }
+}
+
+const PULSE: [char; 4] = ['\u{00b7}', '\u{2022}', '\u{25cf}', '\u{2022}'];
+
+/// Liveness dot that steps once per successful poll rather than per frame,
+/// blank until the first poll lands.
+fn pulse(polls: u64) -> String {
+    if polls == 0 {
+        return " ".to_string();
+    }
+    PULSE[(polls % PULSE.len() as u64) as usize].to_string()
+}
+
+/// Playback position, e.g. " REPLAY 12/150 @14:03:07 2x PAUSED ", ending
+/// in END once the last frame is held.
+fn replay_badge(replay: &ReplayStatus, fmt: TimestampFormat) -> String {
+    let mut badge = format!(
+        " REPLAY {}/{} @{} {}x",
+        replay.frame,
+        replay.frames,
+        format_epoch(replay.recorded_at, fmt),
+        replay.speed
+    );
+    if replay.ended {
+        badge.push_str(" END");
+    } else if replay.paused {
+        badge.push_str(" PAUSED");
+    }
+    badge.push(' ');
+    badge
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pulse_steps_per_poll_and_wraps() {
+        assert_eq!(pulse(0), " ");
+        let frames: Vec<String> = (1..=5).map(pulse).collect();
+        assert_eq!(frames, ["\u{2022}", "\u{25cf}", "\u{2022}", "\u{00b7}", "\u{2022}"]);
+    }
+
+    #[test]
+    fn replay_badge_shows_position_speed_and_end() {
+        let replay = ReplayStatus { frame: 12, frames: 150, recorded_at: 1_700_000_000.5, speed: 0.5, paused: true, ended: false };
+        assert_eq!(replay_badge(&replay, TimestampFormat::Epoch), " REPLAY 12/150 @1700000000.500 0.5x PAUSED ");
+        let end = ReplayStatus { frame: 150, speed: 2.0, ended: true, ..replay };
+        assert_eq!(replay_badge(&end, TimestampFormat::Epoch), " REPLAY 150/150 @1700000000.500 2x END ");
+    }
 }
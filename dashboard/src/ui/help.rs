@@ -4,32 +4,174 @@ use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
+use unicode_width::UnicodeWidthStr;
+
+use crate::model::{AppState, FocusedPanel, LatencyStat};
 use super::theme::ColorScheme;
 
-pub fn draw(frame: &mut Frame, area: Rect, colors: &ColorScheme) {
+/// Keys that work everywhere, kept at the front of the bar so they are
+/// never cut off.
+pub const GLOBAL_KEYS: [(&str, &str); 2] = [("Tab/S-Tab", "Switch panel"), ("q/Esc", "Quit")];
+
+/// Shown in place of the entries that don't fit; `?` lists them all.
+const MORE_HINT: (&str, &str) = ("?", "More keys");
+
+/// Keys shown for the current context, as (key, description) pairs.
+fn legend(state: &AppState) -> Vec<(&'static str, &'static str)> {
+    if state.quit_prompt {
+        return vec![("y", "Quit"), ("any other key", "Cancel")];
+    }
+
+    // Detail modals capture all input while open
+    if state.modal_open() {
+        return vec![
+            ("\u{2191}/k", "Scroll up"),
+            ("\u{2193}/j", "Scroll down"),
+            ("PgUp/PgDn", "Page"),
+            ("Esc", "Close"),
+        ];
+    }
+
+    let mut keys = GLOBAL_KEYS.to_vec();
+    keys.extend(context_keys(state));
+    keys
+}
+
+/// Keys for the focused panel and current toggles, everything but the
+/// global keys; the `?` modal lists these in full.
+pub fn context_keys(state: &AppState) -> Vec<(&'static str, &'static str)> {
+    let mut keys = Vec::new();
+    match state.focused_panel {
+        FocusedPanel::Backends => {
+            keys.push(("\u{2191}\u{2193}/jk", "Select model"));
+            keys.push(("Enter", "Expand/Collapse"));
+        }
+        FocusedPanel::Sessions => {
+            keys.push(("\u{2191}\u{2193}/jk", "Select"));
+            keys.push(("Enter", "Expand/Detail"));
+            keys.push(("d", "Timeline"));
+            keys.push(("x", "Full text"));
+            keys.push(("r", if state.show_requests { "Show responses" } else { "Show requests" }));
+            keys.push(("i", if state.hide_idle_clients { "Show idle" } else { "Hide idle" }));
+            keys.push(("f", if state.hide_finished { "Show finished" } else { "Hide finished" }));
+            keys.push(("*", "Star"));
+            keys.push(("s", if state.starred_only { "Show all" } else { "Starred only" }));
+            keys.push(("e", if state.expand_failed { "Manual expand" } else { "Auto-expand failed" }));
+            keys.push(("c", if state.clients_only { "Show sessions" } else { "Clients only" }));
+            keys.push(("o/O", "Sort/Reverse"));
+        }
+    }
+    if let Some(replay) = &state.replay {
+        keys.push(("Space", if replay.paused { "Play" } else { "Pause" }));
+        keys.push(("+/-", "Speed"));
+        if replay.paused && !replay.ended {
+            keys.push((".", "Step"));
+        }
+    }
+    if state.history_offset.is_some() {
+        keys.push(("[/]", "Scrub"));
+        keys.push(("End", "Live"));
+    } else {
+        keys.push(("[", "History"));
+    }
+    if state.baseline.is_some() {
+        keys.push(("b/B", "Rebase/Clear baseline"));
+    } else {
+        keys.push(("b", "Baseline"));
+    }
+    if !state.alerts.is_empty() {
+        keys.push(("a", "Ack alerts"));
+    }
+    keys.push(("y", "Copy panel"));
+    keys.push(("Y", "Copy analysis"));
+    keys.push(("t", "Time format"));
+    keys.push(("v", "Analysis detail"));
+    keys.push(("p", match state.latency_stat {
+        LatencyStat::Mean => "p95 latency",
+        LatencyStat::P95 => "Mean latency",
+    }));
+    keys.push(("m", "Export report"));
+    keys.push(("?", "Keys & timing terms"));
+    keys.push(("M", "Models"));
+    keys
+}
+
+/// Columns one entry takes in the bar, including its leading separator.
+fn entry_width(index: usize, (key, desc): (&str, &str)) -> usize {
+    usize::from(index > 0) + 1 + key.width() + 1 + desc.width() + 1
+}
+
+/// The entries that fit in `width` columns. The global keys at the front
+/// always stay; the rest are kept in order until one doesn't fit, and
+/// are then replaced by `MORE_HINT`.
+fn fit(keys: Vec<(&'static str, &'static str)>, width: usize) -> Vec<(&'static str, &'static str)> {
+    let total: usize = keys.iter().enumerate().map(|(i, k)| entry_width(i, *k)).sum();
+    if total <= width {
+        return keys;
+    }
+    let pinned = keys.iter().take_while(|k| GLOBAL_KEYS.contains(k)).count();
+    let mut used: usize = keys[..pinned].iter().enumerate().map(|(i, k)| entry_width(i, *k)).sum();
+    let hint = entry_width(1, MORE_HINT);
+    let mut fitted = keys[..pinned].to_vec();
+    for key in &keys[pinned..] {
+        let w = entry_width(fitted.len(), *key);
+        if used + w + hint > width {
+            break;
+        }
+        used += w;
+        fitted.push(*key);
+    }
+    fitted.push(MORE_HINT);
+    fitted
+}
+
+pub fn draw(frame: &mut Frame, area: Rect, state: &AppState, colors: &ColorScheme) {
     let key_style = Style::default()
         .fg(colors.accent)
         .add_modifier(Modifier::BOLD);
     let desc_style = Style::default().fg(colors.text_secondary);
     let sep_style = Style::default().fg(colors.help_separator);
 
-    let line = Line::from(vec![
-        Span::styled(" Tab", key_style),
-        Span::styled(" Switch panel ", desc_style),
-        Span::styled("\u{2502}", sep_style),
-        Span::styled(" \u{2191}/k", key_style),
-        Span::styled(" Up ", desc_style),
-        Span::styled("\u{2502}", sep_style),
-        Span::styled(" \u{2193}/j", key_style),
-        Span::styled(" Down ", desc_style),
-        Span::styled("\u{2502}", sep_style),
-        Span::styled(" Enter", key_style),
-        Span::styled(" Expand/Collapse ", desc_style),
-        Span::styled("\u{2502}", sep_style),
-        Span::styled(" q/Esc", key_style),
-        Span::styled(" Quit", desc_style),
-    ]);
-
-    let paragraph = Paragraph::new(line);
+    let mut spans = Vec::new();
+    let mut width = area.width as usize;
+    if state.quit_prompt {
+        spans.push(Span::styled(
+            " Quit? (y/n) ",
+            Style::default().fg(colors.status_warn).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled("\u{2502}", sep_style));
+        width = width.saturating_sub(" Quit? (y/n) ".len() + 1);
+    }
+    for (i, (key, desc)) in fit(legend(state), width).into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled("\u{2502}", sep_style));
+        }
+        spans.push(Span::styled(format!(" {}", key), key_style));
+        spans.push(Span::styled(format!(" {} ", desc), desc_style));
+    }
+
+    let paragraph = Paragraph::new(Line::from(spans));
     frame.render_widget(paragraph, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_keys_survive_a_narrow_bar() {
+        let state = AppState { focused_panel: FocusedPanel::Sessions, ..AppState::default() };
+        let keys = legend(&state);
+        assert_eq!(fit(keys.clone(), 1000), keys);
+
+        let fitted = fit(keys.clone(), 80);
+        assert_eq!(fitted[..2], GLOBAL_KEYS);
+        assert_eq!(fitted.last(), Some(&MORE_HINT));
+        assert_eq!(fitted[2..fitted.len() - 1], keys[2..fitted.len() - 1]);
+        let width: usize = fitted.iter().enumerate().map(|(i, k)| entry_width(i, *k)).sum();
+        assert!(width <= 80);
+
+        // Too narrow for anything else: the global keys still come first
+        assert_eq!(fit(keys, 10), [GLOBAL_KEYS[0], GLOBAL_KEYS[1], MORE_HINT]);
+    }
+}
@@ -0,0 +1,158 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use unicode_width::UnicodeWidthStr;
+
+use crate::model::{AppState, ProxySnapshot};
+use super::analysis::collect_timing_stats;
+use super::help::{context_keys, GLOBAL_KEYS};
+use super::theme::ColorScheme;
+use super::turn_detail::centered_rect;
+
+/// One timing term as the panels print it.
+pub struct TimingTerm {
+    pub term: &'static str,
+    pub meaning: &'static str,
+    /// The `CompletedTurn` field(s) it comes from.
+    pub source: &'static str,
+    /// Whether the current proxy data produces this term.
+    pub live: bool,
+}
+
+/// The timing terms used across panels. With backend-reported time the
+/// backend call splits into inference + proxy; without it panels show the
+/// combined proc.
+pub fn timing_terms(has_backend_time: bool) -> Vec<TimingTerm> {
+    vec![
+        TimingTerm {
+            term: "wait",
+            meaning: "Queued in the proxy before a backend picked the request up",
+            source: "wait_time_ms",
+            live: true,
+        },
+        TimingTerm {
+            term: "inference / infer",
+            meaning: "Time the backend itself spent generating",
+            source: "backend_time_ms",
+            live: has_backend_time,
+        },
+        TimingTerm {
+            term: "proxy",
+            meaning: "Proxy overhead around the backend call",
+            source: "processing_time_ms - backend_time_ms",
+            live: has_backend_time,
+        },
+        TimingTerm {
+            term: "proc",
+            meaning: "The backend call as the proxy saw it (inference + proxy combined)",
+            source: "processing_time_ms",
+            live: !has_backend_time,
+        },
+        TimingTerm {
+            term: "total",
+            meaning: "Submission to response: wait + proc",
+            source: "total_time_ms",
+            live: true,
+        },
+        TimingTerm {
+            term: "agent",
+            meaning: "Agent time between turns: previous response to next submission",
+            source: "submitted_at - previous completed_at",
+            live: true,
+        },
+        TimingTerm {
+            term: "obs",
+            meaning: "Part of the agent gap spent building the observation",
+            source: "agent_obs_ms (agent_pre_ms)",
+            live: true,
+        },
+        TimingTerm {
+            term: "act",
+            meaning: "Part of the agent gap spent acting on the response",
+            source: "agent_act_ms (agent_post_ms)",
+            live: true,
+        },
+    ]
+}
+
+/// Draw the timing legend and full key list modal over `area` if it is open.
+pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppState, colors: &ColorScheme) {
+    if !state.show_legend {
+        return;
+    }
+
+    let popup = centered_rect(80, 60, area);
+    frame.render_widget(Clear, popup);
+
+    let has_backend_time = collect_timing_stats(&proxy.queue).has_backend_time;
+    let mut lines = vec![
+        Line::from(Span::styled(
+            if has_backend_time {
+                "This proxy reports backend time, so panels split proc into inference + proxy."
+            } else {
+                "This proxy doesn't report backend time, so panels show proc (inference + proxy)."
+            },
+            Style::default().fg(colors.text_secondary),
+        )),
+        Line::from(""),
+    ];
+    let term_width = timing_terms(has_backend_time).iter().map(|t| t.term.len()).max().unwrap_or(0);
+    for t in timing_terms(has_backend_time) {
+        let (term_style, text_style) = if t.live {
+            (
+                Style::default().fg(colors.accent).add_modifier(Modifier::BOLD),
+                Style::default().fg(colors.text_primary),
+            )
+        } else {
+            (Style::default().fg(colors.text_disabled), Style::default().fg(colors.text_disabled))
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<w$}  ", t.term, w = term_width), term_style),
+            Span::styled(t.meaning, text_style),
+            Span::styled(format!("  [{}]", t.source), Style::default().fg(colors.text_disabled)),
+        ]));
+    }
+
+    // Every key, including those the one-line help bar had no room for
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Keys", Style::default().fg(colors.text_secondary))));
+    let keys: Vec<(&str, &str)> = GLOBAL_KEYS.into_iter().chain(context_keys(state)).collect();
+    let key_width = keys.iter().map(|(key, _)| key.width()).max().unwrap_or(0);
+    for (key, desc) in keys {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{}{}  ", key, " ".repeat(key_width - key.width())),
+                Style::default().fg(colors.accent).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(desc, Style::default().fg(colors.text_primary)),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((state.modal_scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Keys & Timing Terms ")
+                .border_style(Style::default().fg(colors.border_focused)),
+        );
+    frame.render_widget(paragraph, popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn live_terms_follow_backend_time_reporting() {
+        let live = |has_backend_time| -> Vec<&'static str> {
+            timing_terms(has_backend_time).into_iter().filter(|t| t.live).map(|t| t.term).collect()
+        };
+        assert!(live(true).contains(&"proxy") && !live(true).contains(&"proc"));
+        assert!(live(false).contains(&"proc") && !live(false).contains(&"proxy"));
+        assert!(live(false).contains(&"wait") && live(false).contains(&"agent"));
+    }
+}
@@ -1,55 +1,181 @@
 pub mod analysis;
 pub mod backends;
+pub mod errors;
+pub mod format;
+pub mod session_detail;
 pub mod sessions;
+pub mod status;
 pub mod header;
 pub mod help;
+pub mod legend;
+pub mod models;
 pub mod queue;
 pub mod recent;
 pub mod theme;
+pub mod timefmt;
+pub mod toast;
+pub mod turn_detail;
 
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout};
 
-use crate::model::{AppState, ProxySnapshot, ThroughputSnapshot};
+use crate::model::{AppState, LayoutMode, ProxySnapshot, ReplayStatus, ThroughputSnapshot};
 use theme::ColorScheme;
 
+/// `scoped` is `proxy` narrowed to `--partition` (see `AppState::scoped`),
+/// computed once per frame by the caller.
 pub fn draw(
     frame: &mut Frame,
     proxy: &ProxySnapshot,
+    scoped: &ProxySnapshot,
     throughput: &ThroughputSnapshot,
     proxy_url: &str,
     state: &mut AppState,
     colors: &ColorScheme,
 ) {
+    match state.layout {
+        LayoutMode::Standard => draw_standard(frame, proxy, scoped, throughput, proxy_url, state, colors),
+        LayoutMode::Wall => draw_wall(frame, scoped, proxy_url, state, colors),
+    }
+
+    // Overlays draw last so they sit on top
+    toast::draw(frame, frame.area(), state, colors);
+    turn_detail::draw(frame, frame.area(), proxy, state, colors);
+    session_detail::draw(frame, frame.area(), proxy, state, colors);
+    legend::draw(frame, frame.area(), proxy, state, colors);
+    models::draw(frame, frame.area(), proxy, state, colors);
+}
+
+/// The default stacked layout with every panel.
+fn draw_standard(
+    frame: &mut Frame,
+    proxy: &ProxySnapshot,
+    scoped: &ProxySnapshot,
+    throughput: &ThroughputSnapshot,
+    proxy_url: &str,
+    state: &mut AppState,
+    colors: &ColorScheme,
+) {
+    let report = analysis::compute_bottleneck(scoped, state.gap_trim);
+    let bottleneck_height = analysis::bottleneck_height(state.bottleneck_verbosity, report.scoped_suggestions().count());
     let outer = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),   // header
             Constraint::Min(5),     // backends table
             Constraint::Length(10), // GPU performance + queue status
-            Constraint::Length(14), // bottleneck analysis (expanded)
+            Constraint::Length(bottleneck_height), // bottleneck analysis
             Constraint::Min(10),    // sessions
             Constraint::Length(5),  // recent completions
+            Constraint::Length(1),  // selection status
             Constraint::Length(1),  // help bar
         ])
         .split(frame.area());
 
-    header::draw(frame, outer[0], proxy, proxy_url, colors);
-    backends::draw(frame, outer[1], proxy, state, colors);
+    header::draw(frame, outer[0], scoped, proxy_url, state, colors);
+    backends::draw(frame, outer[1], scoped, state, colors);
 
-    // GPU Performance + Queue Status
+    // GPU Performance + Errors by Model + Queue Status
     let top_row = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(28), Constraint::Percentage(32)])
         .split(outer[2]);
 
-    analysis::draw_gpu_performance(frame, top_row[0], proxy, colors);
-    queue::draw(frame, top_row[1], proxy, colors);
+    analysis::draw_gpu_performance(frame, top_row[0], scoped, state, colors);
+    errors::draw(frame, top_row[1], scoped, state, colors);
+    queue::draw(frame, top_row[2], proxy, state, colors);
 
     // Bottleneck Analysis (full width, expanded height)
-    analysis::draw_bottleneck(frame, outer[3], proxy, colors);
+    analysis::draw_bottleneck(frame, outer[3], scoped, &report, state, colors);
 
     sessions::draw(frame, outer[4], proxy, state, colors);
-    recent::draw(frame, outer[5], throughput, colors);
-    help::draw(frame, outer[6], colors);
+    recent::draw(frame, outer[5], throughput, state, colors);
+    status::draw(frame, outer[6], proxy, scoped, state, colors);
+    help::draw(frame, outer[7], state, colors);
+}
+
+/// Big-screen layout: backends full height on the left, a GPU load chart
+/// top-right and the diagnosis bottom-right, with a margin for readability
+/// from across the room.
+fn draw_wall(
+    frame: &mut Frame,
+    scoped: &ProxySnapshot,
+    proxy_url: &str,
+    state: &mut AppState,
+    colors: &ColorScheme,
+) {
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // header
+            Constraint::Min(10),   // grid
+            Constraint::Length(1), // help bar
+        ])
+        .split(frame.area());
+
+    header::draw(frame, outer[0], scoped, proxy_url, state, colors);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .spacing(2)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(outer[1]);
+
+    backends::draw(frame, columns[0], scoped, state, colors);
+
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .spacing(1)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(columns[1]);
+
+    analysis::draw_gpu_load_chart(frame, right[0], scoped, colors);
+    let report = analysis::compute_bottleneck(scoped, state.gap_trim);
+    analysis::draw_bottleneck(frame, right[1], scoped, &report, state, colors);
+
+    help::draw(frame, outer[2], state, colors);
+}
+
+/// Everything a frame depends on besides key input, compared between loop
+/// iterations so unchanged frames are not redrawn.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameKey {
+    proxy: Option<std::time::Instant>,
+    throughput: (usize, Option<std::time::SystemTime>),
+    toasts: usize,
+    /// Replay position and controls, which change without a new poll while paused
+    replay: Option<ReplayStatus>,
+    /// Wall-clock second, so clocks and running durations keep ticking
+    second: i64,
+}
+
+impl FrameKey {
+    pub fn new(proxy: &ProxySnapshot, throughput: &ThroughputSnapshot, state: &AppState, second: i64) -> Self {
+        FrameKey {
+            proxy: proxy.fetched_at,
+            throughput: (throughput.total, throughput.recent.first().map(|c| c.modified)),
+            toasts: state.toasts.len(),
+            replay: state.replay,
+            second,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_key_changes_with_snapshot_or_clock() {
+        let proxy = ProxySnapshot::default();
+        let tp = ThroughputSnapshot::default();
+        let state = AppState::default();
+        let key = FrameKey::new(&proxy, &tp, &state, 100);
+        assert_eq!(key, FrameKey::new(&proxy, &tp, &state, 100));
+        assert_ne!(key, FrameKey::new(&proxy, &tp, &state, 101));
+
+        let polled = ProxySnapshot { fetched_at: Some(std::time::Instant::now()), ..ProxySnapshot::default() };
+        assert_ne!(key, FrameKey::new(&polled, &tp, &state, 100));
+    }
 }
@@ -0,0 +1,142 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use crate::model::{AppState, ProxySnapshot, StatsResponse};
+use super::theme::ColorScheme;
+use super::turn_detail::centered_rect;
+
+/// How a model the proxy knows about is being served.
+#[derive(Debug, PartialEq)]
+pub enum ModelStatus {
+    /// Configured with a pool of backends (`healthy` of `total` up).
+    Serving { healthy: usize, total: usize },
+    /// Configured, but no pool or an empty one: requests for it can't be routed.
+    NoBackends,
+    /// Has a pool but isn't in the configured `models` list.
+    Unlisted { healthy: usize, total: usize },
+    /// An empty pool for a model that isn't in the `models` list either.
+    UnlistedEmpty,
+}
+
+/// Every configured model in the proxy's order, then any pool serving a
+/// model the list doesn't mention.
+pub fn model_statuses(stats: &StatsResponse) -> Vec<(&str, ModelStatus)> {
+    let counts = |model: &str| {
+        stats
+            .pools
+            .iter()
+            .find(|p| p.model == model)
+            .map(|p| (p.backends.iter().filter(|b| b.healthy).count(), p.backends.len()))
+            .filter(|&(_, total)| total > 0)
+    };
+    let configured = stats.models.iter().map(|model| {
+        let status = match counts(model) {
+            Some((healthy, total)) => ModelStatus::Serving { healthy, total },
+            None => ModelStatus::NoBackends,
+        };
+        (model.as_str(), status)
+    });
+    let unlisted = stats
+        .pools
+        .iter()
+        .filter(|p| !stats.models.contains(&p.model))
+        .map(|p| {
+            let status = match counts(&p.model) {
+                Some((healthy, total)) => ModelStatus::Unlisted { healthy, total },
+                None => ModelStatus::UnlistedEmpty,
+            };
+            (p.model.as_str(), status)
+        });
+    configured.chain(unlisted).collect()
+}
+
+/// Draw the configured-models modal over `area` if it is open.
+pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppState, colors: &ColorScheme) {
+    if !state.show_models {
+        return;
+    }
+
+    let popup = centered_rect(60, 50, area);
+    frame.render_widget(Clear, popup);
+
+    let statuses = model_statuses(&proxy.stats);
+    let mut lines = Vec::new();
+    if statuses.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "The proxy reports no models.",
+            Style::default().fg(colors.text_secondary),
+        )));
+    }
+    let width = statuses.iter().map(|(m, _)| m.len()).max().unwrap_or(0);
+    for (model, status) in &statuses {
+        let (text, color) = match status {
+            ModelStatus::Serving { healthy, total } => (
+                format!("{}/{} backends healthy", healthy, total),
+                if healthy == total { colors.status_ok } else { colors.status_warn },
+            ),
+            ModelStatus::NoBackends => ("configured, no backends".to_string(), colors.status_error),
+            ModelStatus::Unlisted { healthy, total } => (
+                format!("{}/{} backends healthy, not in models list", healthy, total),
+                colors.status_warn,
+            ),
+            ModelStatus::UnlistedEmpty => ("no backends, not in models list".to_string(), colors.status_warn),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:<w$}  ", model, w = width),
+                Style::default().fg(colors.text_primary).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(text, Style::default().fg(color)),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((state.modal_scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Configured Models ({}) ", proxy.stats.models.len()))
+                .border_style(Style::default().fg(colors.border_focused)),
+        );
+    frame.render_widget(paragraph, popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::snapshot;
+    use serde_json::json;
+
+    #[test]
+    fn configured_models_are_cross_checked_against_pools() {
+        let proxy = snapshot(
+            json!({
+                "models": ["org/served", "org/empty", "org/missing"],
+                "pools": [
+                    {"model": "org/served", "backends": [
+                        {"url": "http://a:8001", "healthy": true},
+                        {"url": "http://a:8002", "healthy": false}
+                    ]},
+                    {"model": "org/empty", "backends": []},
+                    {"model": "org/extra", "backends": [{"url": "http://b:8001", "healthy": true}]},
+                    {"model": "org/stale", "backends": []}
+                ]
+            }),
+            json!({}),
+        );
+        assert_eq!(
+            model_statuses(&proxy.stats),
+            vec![
+                ("org/served", ModelStatus::Serving { healthy: 1, total: 2 }),
+                ("org/empty", ModelStatus::NoBackends),
+                ("org/missing", ModelStatus::NoBackends),
+                ("org/extra", ModelStatus::Unlisted { healthy: 1, total: 1 }),
+                ("org/stale", ModelStatus::UnlistedEmpty),
+            ]
+        );
+    }
+}
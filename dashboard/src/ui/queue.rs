@@ -1,34 +1,71 @@
 use ratatui::Frame;
 use ratatui::layout::Rect;
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
-use crate::model::ProxySnapshot;
+use crate::model::{AppState, LatencyStat, ProxySnapshot};
 use super::analysis::collect_timing_stats;
+use super::format::{format_count, format_decimal, format_duration_ms, format_elapsed, format_pct, Precision};
 use super::theme::ColorScheme;
 
-fn format_latency(ms: f64) -> String {
-    if ms < 1000.0 {
-        format!("{:.0}ms", ms)
+/// Proxy error rate (percent) at which the figure turns `status_warn`.
+const ERROR_RATE_WARN_PCT: f64 = 1.0;
+/// Proxy error rate (percent) at which the figure turns `status_error`.
+const ERROR_RATE_ERROR_PCT: f64 = 5.0;
+
+fn error_rate_color(pct: f64, colors: &ColorScheme) -> Color {
+    if pct >= ERROR_RATE_ERROR_PCT {
+        colors.status_error
+    } else if pct >= ERROR_RATE_WARN_PCT {
+        colors.status_warn
     } else {
-        format!("{:.1}s", ms / 1000.0)
+        colors.status_ok
     }
 }
 
-pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, colors: &ColorScheme) {
-    let content = if !proxy.connected {
-        vec![Line::from(Span::styled(
-            "  Proxy offline",
+/// Proxy-wide request rate and error rate from `/stats`, dashed out when offline.
+fn rate_lines<'a>(proxy: &ProxySnapshot, precision: Precision, colors: &ColorScheme) -> Vec<Line<'a>> {
+    let (rpm, rpm_color, errors, error_color) = if proxy.connected.is_up() {
+        let pct = proxy.stats.error_rate;
+        (
+            format_decimal(proxy.stats.requests_per_minute, 1, precision),
+            colors.accent_count,
+            format_pct(pct, 2, precision),
+            error_rate_color(pct, colors),
+        )
+    } else {
+        let dash = "\u{2014}".to_string();
+        (dash.clone(), colors.text_disabled, dash, colors.text_disabled)
+    };
+    vec![
+        Line::from(vec![
+            Span::styled("  Req/min:     ", Style::default().fg(colors.text_primary)),
+            Span::styled(rpm, Style::default().fg(rpm_color)),
+        ]),
+        Line::from(vec![
+            Span::styled("  Error rate:  ", Style::default().fg(colors.text_primary)),
+            Span::styled(errors, Style::default().fg(error_color)),
+        ]),
+    ]
+}
+
+pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppState, colors: &ColorScheme) {
+    let precision = state.precision;
+    let content = if !proxy.connected.is_up() {
+        let mut lines = vec![Line::from(Span::styled(
+            format!("  {}", proxy.connected.placeholder()),
             Style::default().fg(colors.text_disabled),
-        ))]
+        ))];
+        lines.extend(rate_lines(proxy, precision, colors));
+        lines
     } else {
         let s = &proxy.queue.summary;
         let mut lines = vec![
             Line::from(vec![
                 Span::styled("  Pending:     ", Style::default().fg(colors.text_primary)),
                 Span::styled(
-                    s.pending.to_string(),
+                    format_count(s.pending),
                     Style::default()
                         .fg(if s.pending > 0 { colors.status_warn } else { colors.status_ok })
                         .add_modifier(Modifier::BOLD),
@@ -37,38 +74,58 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, colors: &Color
             Line::from(vec![
                 Span::styled("  In-flight:   ", Style::default().fg(colors.text_primary)),
                 Span::styled(
-                    s.in_flight.to_string(),
+                    format_count(s.in_flight),
                     Style::default().fg(colors.accent).add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(vec![
                 Span::styled("  Last minute: ", Style::default().fg(colors.text_primary)),
                 Span::styled(
-                    s.completed_last_minute.to_string(),
+                    format_count(s.completed_last_minute),
                     Style::default().fg(colors.status_ok),
                 ),
             ]),
             Line::from(vec![
                 Span::styled("  Tracked:     ", Style::default().fg(colors.text_primary)),
-                Span::styled(s.total_tracked.to_string(), Style::default().fg(colors.text_primary)),
+                Span::styled(format_count(s.total_tracked), Style::default().fg(colors.text_primary)),
             ]),
         ];
+        if let Some(stall) = proxy.routing_stall() {
+            lines.push(Line::from(vec![
+                Span::styled("  Unrouted:    ", Style::default().fg(colors.text_primary)),
+                Span::styled(
+                    format!(
+                        "{} (oldest {}) \u{2014} routing stall",
+                        format_count(stall.count as u64),
+                        format_elapsed(stall.oldest.as_millis() as f64)
+                    ),
+                    Style::default().fg(colors.status_error).add_modifier(Modifier::BOLD),
+                ),
+            ]));
+        }
+        lines.extend(rate_lines(proxy, precision, colors));
 
-        // Avg turn timing line
+        // Turn timing line, mean or p95
         let timing = collect_timing_stats(&proxy.queue);
         if timing.count > 0 {
-            let avg_turn = timing.avg_total_ms + timing.avg_gap_ms;
+            let stat = state.latency_stat;
+            let turn_ms = timing.turn_ms(stat);
+            let inference = format_duration_ms(timing.inference_ms(stat), precision);
             let detail = if timing.has_backend_time {
-                format!("(inference={} agent={})", format_latency(timing.avg_inference_ms), format_latency(timing.avg_gap_ms))
+                format!("(inference={} agent={})", inference, format_duration_ms(timing.gap_ms(stat), precision))
             } else if timing.gap_count > 0 {
-                format!("(proc={} agent={})", format_latency(timing.avg_inference_ms), format_latency(timing.avg_gap_ms))
+                format!("(proc={} agent={})", inference, format_duration_ms(timing.gap_ms(stat), precision))
             } else {
-                format!("(proc={})", format_latency(timing.avg_inference_ms))
+                format!("(proc={})", inference)
+            };
+            let label = match stat {
+                LatencyStat::Mean => "  Avg turn:    ",
+                LatencyStat::P95 => "  P95 turn:    ",
             };
             lines.push(Line::from(vec![
-                Span::styled("  Avg turn:    ", Style::default().fg(colors.text_primary)),
+                Span::styled(label, Style::default().fg(colors.text_primary)),
                 Span::styled(
-                    format!("{} {}", format_latency(avg_turn), detail),
+                    format!("{} {}", format_duration_ms(turn_ms, precision), detail),
                     Style::default().fg(colors.text_primary),
                 ),
             ]));
@@ -88,3 +145,16 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, colors: &Color
 
     frame.render_widget(paragraph, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_rate_color_steps_at_thresholds() {
+        let colors = ColorScheme::dark();
+        assert_eq!(error_rate_color(0.5, &colors), colors.status_ok);
+        assert_eq!(error_rate_color(1.0, &colors), colors.status_warn);
+        assert_eq!(error_rate_color(7.5, &colors), colors.status_error);
+    }
+}
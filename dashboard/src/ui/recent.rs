@@ -1,13 +1,17 @@
+use std::time::{Duration, SystemTime};
+
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::Text;
 use ratatui::widgets::{Block, Borders, Cell, Row, Table};
 
-use crate::model::ThroughputSnapshot;
+use crate::model::{AppState, ThroughputSnapshot, TimestampFormat};
+use super::format::format_elapsed;
 use super::theme::ColorScheme;
+use super::timefmt::format_system_time;
 
-pub fn draw(frame: &mut Frame, area: Rect, tp: &ThroughputSnapshot, colors: &ColorScheme) {
+pub fn draw(frame: &mut Frame, area: Rect, tp: &ThroughputSnapshot, state: &AppState, colors: &ColorScheme) {
     if !tp.enabled {
         let block = Block::default()
             .borders(Borders::ALL)
@@ -25,6 +29,7 @@ pub fn draw(frame: &mut Frame, area: Rect, tp: &ThroughputSnapshot, colors: &Col
     let header = Row::new(vec![
         Cell::from("Time"),
         Cell::from("Spec"),
+        Cell::from("Duration"),
         Cell::from("Result"),
     ])
     .style(
@@ -33,10 +38,20 @@ pub fn draw(frame: &mut Frame, area: Rect, tp: &ThroughputSnapshot, colors: &Col
             .add_modifier(Modifier::BOLD),
     );
 
+    let highlight_window = Duration::from_secs(state.recent_highlight_secs);
+    let now = SystemTime::now();
+
     let rows: Vec<Row> = tp
         .recent
         .iter()
         .map(|entry| {
+            let is_fresh = state.recent_highlight_secs > 0
+                && now.duration_since(entry.modified).unwrap_or(Duration::ZERO) <= highlight_window;
+            let text_style = if is_fresh {
+                Style::default().fg(colors.accent).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
             let result_style = if entry.success {
                 Style::default().fg(colors.status_ok)
             } else {
@@ -45,18 +60,36 @@ pub fn draw(frame: &mut Frame, area: Rect, tp: &ThroughputSnapshot, colors: &Col
             let result_text = if entry.success { "PASS" } else { "FAIL" };
 
             Row::new(vec![
-                Cell::from(entry.time.clone()),
-                Cell::from(entry.spec_name.clone()),
-                Cell::from(result_text).style(result_style),
+                Cell::from(format_system_time(entry.modified, state.timestamp_format)).style(text_style),
+                Cell::from(entry.spec_name.clone()).style(text_style),
+                Cell::from(entry.duration.map_or("-".to_string(), |d| format_elapsed(d.as_millis() as f64)))
+                    .style(text_style),
+                Cell::from(result_text).style(if is_fresh {
+                    result_style.add_modifier(Modifier::BOLD)
+                } else {
+                    result_style
+                }),
             ])
         })
         .collect();
 
+    let time_width = match state.timestamp_format {
+        TimestampFormat::Clock => 10,
+        TimestampFormat::Iso => 31,
+        TimestampFormat::Epoch => 16,
+    };
+
+    let title = match tp.avg_run_secs {
+        Some(avg) => format!(" Recent Completions \u{2502} avg run {} ", format_elapsed(avg * 1000.0)),
+        None => " Recent Completions ".to_string(),
+    };
+
     let table = Table::new(
         rows,
         [
-            Constraint::Length(10),
+            Constraint::Length(time_width),
             Constraint::Min(30),
+            Constraint::Length(9),
             Constraint::Length(8),
         ],
     )
@@ -64,7 +97,7 @@ pub fn draw(frame: &mut Frame, area: Rect, tp: &ThroughputSnapshot, colors: &Col
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .title(" Recent Completions ")
+            .title(title)
             .border_style(Style::default().fg(colors.border_focused)),
     );
 
@@ -0,0 +1,241 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::model::{AppState, ProxySnapshot, SessionGroup};
+use super::format::{format_bytes, format_decimal, format_duration_ms, format_pct};
+use super::theme::ColorScheme;
+use super::turn_detail::centered_rect;
+
+/// Width of the "T12 " label column in front of each timeline row.
+const LABEL_WIDTH: usize = 5;
+
+/// One turn on the session timeline. Times are seconds relative to the
+/// first turn's submission; durations are milliseconds.
+#[derive(Debug, PartialEq)]
+pub struct TurnSpan {
+    pub turn_no: u64,
+    pub start: f64,
+    pub end: f64,
+    /// Agent time between the previous turn completing and this one being sent.
+    pub gap_ms: f64,
+    pub wait_ms: f64,
+    pub inference_ms: f64,
+    pub proxy_ms: f64,
+}
+
+/// Place each timestamped turn on a shared time axis, splitting it into the
+/// same wait / inference / proxy stages as the bottleneck breakdown.
+pub fn turn_spans(sess: &SessionGroup) -> Vec<TurnSpan> {
+    let Some(origin) = sess
+        .completed_turns
+        .iter()
+        .map(|t| t.submitted_at)
+        .filter(|t| *t > 0.0)
+        .reduce(f64::min)
+    else {
+        return Vec::new();
+    };
+
+    let mut spans = Vec::new();
+    for (i, turn) in sess.completed_turns.iter().enumerate() {
+        if turn.submitted_at <= 0.0 || turn.completed_at < turn.submitted_at {
+            continue;
+        }
+        let gap_ms = match i.checked_sub(1).map(|p| &sess.completed_turns[p]) {
+            Some(prev) if prev.completed_at > 0.0 => ((turn.submitted_at - prev.completed_at) * 1000.0).max(0.0),
+            _ => 0.0,
+        };
+        let (inference_ms, proxy_ms) = match turn.backend_time_ms {
+            Some(backend_ms) => (backend_ms, (turn.processing_time_ms - backend_ms).max(0.0)),
+            None => (turn.processing_time_ms, 0.0),
+        };
+        spans.push(TurnSpan {
+            turn_no: turn.turn_number.unwrap_or(i as u64 + 1),
+            start: turn.submitted_at - origin,
+            end: turn.completed_at - origin,
+            gap_ms,
+            wait_ms: turn.wait_time_ms,
+            inference_ms,
+            proxy_ms,
+        });
+    }
+    spans
+}
+
+/// Draw the session detail modal over `area` if one is open.
+pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppState, colors: &ColorScheme) {
+    let Some(session_id) = &state.session_detail else {
+        return;
+    };
+
+    let popup = centered_rect(90, 70, area);
+    frame.render_widget(Clear, popup);
+
+    let label_style = Style::default().fg(colors.table_header).add_modifier(Modifier::BOLD);
+    let text_style = Style::default().fg(colors.text_primary);
+    let wait_color = colors.status_warn;
+    let inference_color = colors.accent;
+    let proxy_color = colors.accent_id;
+    let gap_color = colors.text_disabled;
+    let precision = state.precision;
+
+    let mut lines: Vec<Line> = Vec::new();
+    let session = proxy.queue.find_session(session_id);
+    let spans = session.map(turn_spans);
+    match spans.filter(|s| !s.is_empty()) {
+        Some(spans) => {
+            let total_s = spans.iter().map(|s| s.end).fold(0.0, f64::max);
+            let sum = |f: fn(&TurnSpan) -> f64| spans.iter().map(f).sum::<f64>();
+            lines.push(Line::from(vec![
+                Span::styled("Turns:    ", label_style),
+                Span::styled(
+                    format!("{} over {}", spans.len(), format_duration_ms(total_s * 1000.0, precision)),
+                    text_style,
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Totals:   ", label_style),
+                Span::styled(format!("wait {}  ", format_duration_ms(sum(|s| s.wait_ms), precision)), Style::default().fg(wait_color)),
+                Span::styled(
+                    format!("inference {}  ", format_duration_ms(sum(|s| s.inference_ms), precision)),
+                    Style::default().fg(inference_color),
+                ),
+                Span::styled(format!("proxy {}  ", format_duration_ms(sum(|s| s.proxy_ms), precision)), Style::default().fg(proxy_color)),
+                Span::styled(format!("agent {}", format_duration_ms(sum(|s| s.gap_ms), precision)), Style::default().fg(gap_color)),
+            ]));
+            if let Some(ratio) = session.and_then(SessionGroup::wait_ratio) {
+                let (text, color) = if session.is_some_and(SessionGroup::is_queue_starved) {
+                    (format!("{}\u{00d7} inference \u{2014} queue-starved", format_decimal(ratio, 1, precision)), colors.status_warn)
+                } else {
+                    (format!("{}\u{00d7} inference", format_decimal(ratio, 1, precision)), colors.text_primary)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled("Wait:     ", label_style),
+                    Span::styled(text, Style::default().fg(color)),
+                ]));
+            }
+            // Hidden unless the proxy reports payload sizes
+            if let Some(bytes) = session.and_then(SessionGroup::payload_bytes) {
+                let mut text = format!(
+                    "in {}  out {}  ({} / {} per turn)",
+                    format_bytes(bytes.request),
+                    format_bytes(bytes.response),
+                    format_bytes(bytes.request / bytes.turns as u64),
+                    format_bytes(bytes.response / bytes.turns as u64),
+                );
+                if let Some(all) = proxy.queue.payload_bytes().filter(|a| a.request > 0) {
+                    text.push_str(&format!(
+                        "  \u{2502} {} of all request bytes",
+                        format_pct(bytes.request as f64 / all.request as f64 * 100.0, 0, precision)
+                    ));
+                }
+                lines.push(Line::from(vec![
+                    Span::styled("Payload:  ", label_style),
+                    Span::styled(text, text_style),
+                ]));
+            }
+            lines.push(Line::from(""));
+
+            // Inside the borders, after the row label
+            let width = (popup.width as usize).saturating_sub(2 + LABEL_WIDTH).max(1);
+            let col = |secs: f64| {
+                if total_s > 0.0 {
+                    ((secs / total_s) * width as f64).round() as usize
+                } else {
+                    0
+                }
+            };
+
+            for span in &spans {
+                let label = format!("T{}", span.turn_no);
+                let mut row = vec![Span::styled(format!("{:<w$}", label, w = LABEL_WIDTH), label_style)];
+                let gap_start = col(span.start - span.gap_ms / 1000.0);
+                row.push(Span::raw(" ".repeat(gap_start)));
+
+                // Cumulative boundaries keep rounding from drifting across segments
+                let mut cursor = gap_start;
+                let mut at = span.start - span.gap_ms / 1000.0;
+                let mut turn_cells = 0;
+                let segments: [(f64, char, Color); 4] = [
+                    (span.gap_ms, '\u{00b7}', gap_color),
+                    (span.wait_ms, '\u{2588}', wait_color),
+                    (span.inference_ms, '\u{2588}', inference_color),
+                    (span.proxy_ms, '\u{2588}', proxy_color),
+                ];
+                for (ms, ch, color) in segments {
+                    at = (at + ms / 1000.0).min(span.end);
+                    let next = col(at).max(cursor);
+                    if next > cursor {
+                        row.push(Span::styled(ch.to_string().repeat(next - cursor), Style::default().fg(color)));
+                        if ch != '\u{00b7}' {
+                            turn_cells += next - cursor;
+                        }
+                    }
+                    cursor = next;
+                }
+                // Keep very short turns visible
+                if turn_cells == 0 {
+                    row.push(Span::styled("\u{258f}", Style::default().fg(inference_color)));
+                }
+                lines.push(Line::from(row));
+            }
+        }
+        None => lines.push(Line::from(Span::styled(
+            "No timestamped turns for this session",
+            Style::default().fg(colors.text_disabled),
+        ))),
+    }
+
+    let legend = Line::from(vec![
+        Span::raw(" "),
+        Span::styled("\u{2588} wait ", Style::default().fg(wait_color)),
+        Span::styled("\u{2588} inference ", Style::default().fg(inference_color)),
+        Span::styled("\u{2588} proxy ", Style::default().fg(proxy_color)),
+        Span::styled("\u{00b7} agent ", Style::default().fg(gap_color)),
+        Span::styled("\u{2502} Esc close ", Style::default().fg(colors.text_secondary)),
+    ]);
+
+    let paragraph = Paragraph::new(lines)
+        .scroll((state.modal_scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Session Timeline \u{2502} {} ", session_id))
+                .title_bottom(legend)
+                .border_style(Style::default().fg(colors.border_focused)),
+        );
+
+    frame.render_widget(paragraph, popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn turn_spans_split_stages_and_measure_gaps() {
+        let sess: SessionGroup = serde_json::from_value(json!({
+            "session_id": "s1",
+            "completed_turns": [
+                {"submitted_at": 100.0, "completed_at": 102.0, "wait_time_ms": 500.0,
+                 "processing_time_ms": 1500.0, "backend_time_ms": 1200.0},
+                {"submitted_at": 105.0, "completed_at": 106.0, "wait_time_ms": 0.0,
+                 "processing_time_ms": 1000.0},
+                {"submitted_at": 0.0, "completed_at": 0.0},
+            ]
+        }))
+        .unwrap();
+
+        let spans = turn_spans(&sess);
+        assert_eq!(spans.len(), 2);
+        assert_eq!((spans[0].start, spans[0].end, spans[0].gap_ms), (0.0, 2.0, 0.0));
+        assert_eq!((spans[0].inference_ms, spans[0].proxy_ms), (1200.0, 300.0));
+        assert_eq!((spans[1].turn_no, spans[1].start, spans[1].gap_ms), (2, 5.0, 3000.0));
+        assert_eq!((spans[1].inference_ms, spans[1].proxy_ms), (1000.0, 0.0));
+    }
+}
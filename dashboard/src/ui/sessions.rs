@@ -1,24 +1,32 @@
-use chrono::{Local, TimeZone};
+use std::collections::HashMap;
+
+use chrono::Local;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Rect};
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::Text;
+use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Block, Borders, Cell, Row, Table};
 
-use crate::model::{AppState, SessionGroup, ClientGroup, FocusedPanel, ProxySnapshot};
+use crate::model::{AppState, SessionGroup, ClientGroup, CompletedTurn, FocusedPanel, ProxySnapshot, SessionSort, SortDir, TruncationLimits};
+use super::analysis::{session_stage, Stage};
+use super::format::{format_decimal, format_duration_ms, format_elapsed, truncate, Precision};
+use super::header::format_cost;
 use super::theme::ColorScheme;
+use super::timefmt::format_epoch;
 
 pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut AppState, colors: &ColorScheme) {
+    let precision = state.precision;
     let is_focused = state.focused_panel == FocusedPanel::Sessions;
     let border_color = if is_focused { colors.border_focused } else { colors.border_normal };
 
-    if !proxy.connected {
+    if !proxy.connected.is_up() {
         let block = Block::default()
             .borders(Borders::ALL)
             .title(" Sessions ")
             .border_style(Style::default().fg(colors.border_normal));
         let msg = ratatui::widgets::Paragraph::new(Text::styled(
-            "  Proxy offline",
+            format!("  {}", proxy.connected.placeholder()),
             Style::default().fg(colors.text_disabled),
         ))
         .block(block);
@@ -31,30 +39,96 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
     let clients = &queue.clients;
     let orphans = &queue.orphan_sessions;
 
-    let use_hierarchy = !clients.is_empty() || !orphans.is_empty();
+    let use_hierarchy = state.uses_hierarchy(queue);
+
+    // Live running time of in-flight requests; frozen while scrubbing history
+    let (as_of, since_fetch_ms) = match state.history_age {
+        Some(age) => (Local::now().timestamp_millis() as f64 / 1000.0 - age.as_secs_f64(), 0.0),
+        None => (
+            Local::now().timestamp_millis() as f64 / 1000.0,
+            proxy.fetched_at.map_or(0.0, |t| t.elapsed().as_secs_f64() * 1000.0),
+        ),
+    };
+    let slow_ms = state.slow_request_secs as f64 * 1000.0;
+    let mut running: HashMap<&str, Activity> = HashMap::new();
+    for req in &queue.in_flight {
+        if let (Some(sid), Some(ms)) = (req.session_id.as_deref(), req.running_ms(as_of, since_fetch_ms)) {
+            let activity = running.entry(sid).or_insert_with(|| Activity::new(as_of));
+            activity.running_ms = Some(activity.running_ms.map_or(ms, |m| m.max(ms)));
+        }
+    }
+    for req in &queue.pending {
+        if let Some(sid) = req.session_id.as_deref() {
+            let ms = req.wait_time_ms + since_fetch_ms;
+            let activity = running.entry(sid).or_insert_with(|| Activity::new(as_of));
+            activity.waiting_ms = Some(activity.waiting_ms.map_or(ms, |m| m.max(ms)));
+        }
+    }
+
+    // Cost column only when prices are configured and the proxy reports tokens
+    let show_cost = state
+        .pricing
+        .as_ref()
+        .is_some_and(|p| queue.estimated_cost(p).is_some());
 
-    let header = Row::new(vec![
-        Cell::from(""),
-        Cell::from("ID / Label"),
-        Cell::from("Detail"),
-        Cell::from("Latency"),
-        Cell::from("Time"),
-        Cell::from("Port"),
-    ])
+    let summary_view = use_hierarchy && state.clients_only;
+    let mut header_cells = if summary_view {
+        vec![
+            Cell::from(""),
+            Cell::from("Client"),
+            Cell::from("Command"),
+            Cell::from("Sessions / Turns"),
+            Cell::from("Active / Failed"),
+            Cell::from("Rate"),
+        ]
+    } else {
+        vec![
+            Cell::from(""),
+            Cell::from("ID / Label"),
+            Cell::from("Detail"),
+            Cell::from("Latency"),
+            Cell::from("Time"),
+            Cell::from("Backend"),
+            Cell::from("Stage"),
+        ]
+    };
+    if show_cost {
+        header_cells.push(Cell::from("Cost"));
+    }
+    let header = Row::new(header_cells)
     .style(
         Style::default()
             .fg(colors.table_header)
             .add_modifier(Modifier::BOLD),
     );
 
+    let mut widths = vec![
+        Constraint::Length(2),      // arrow
+        Constraint::Percentage(14), // ID/label/turn#
+        Constraint::Percentage(38), // detail/response
+        Constraint::Percentage(24), // latency
+        Constraint::Percentage(16), // time/status
+        Constraint::Length(10),     // port / backend count
+    ];
+    if !summary_view {
+        widths.push(Constraint::Length(6)); // dominant stage
+    }
+    if show_cost {
+        widths.push(Constraint::Length(9)); // cost
+    }
+
+    let limits = TextLimits::new(area, &widths, &state.truncation);
+
     let mut rows: Vec<Row> = Vec::new();
     let mut selected_visual_row: Option<usize> = None;
     let mut visual_idx: usize = 0;
     let mut selectable_idx: usize = 0;
 
+    let now_epoch = Local::now().timestamp_millis() as f64 / 1000.0;
+
     if use_hierarchy {
         // === Hierarchical: Client -> Session -> Turn ===
-        for (client_idx, client) in clients.iter().enumerate() {
+        for (client_idx, client) in clients.iter().enumerate().filter(|(_, c)| !state.client_hidden(c, now_epoch)) {
             // Client row
             let is_selected = is_focused && selectable_idx == state.session_selected;
             if selectable_idx == state.session_selected {
@@ -66,8 +140,11 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
 
             let (status_text, status_color) = client_aggregate_status(client, colors);
             let total_turns: u64 = client.sessions.iter().map(|s| s.total_requests).sum();
-            let sess_count = format!("{} sess ({}t)", client.sessions.len(), total_turns);
-            let cmd_short = shorten_command(&client.client_command);
+            let mut sess_count = format!("{} sess ({}t)", client.sessions.len(), total_turns);
+            if let Some(rate) = client.turns_per_minute(now_epoch, state.rate_window_secs as f64) {
+                sess_count.push_str(&format!(" {}t/m", format_decimal(rate, 1, precision)));
+            }
+            let cmd_short = shorten_command(&client.client_command, limits.command);
 
             // Find the most recent backend port used by this client
             let last_port: String = client.sessions.iter()
@@ -79,7 +156,7 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
                 .unwrap_or_default();
 
             let row_style = if is_selected {
-                Style::default().bg(colors.row_selected_bg)
+                colors.selected_row()
             } else {
                 Style::default()
             };
@@ -87,21 +164,39 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
             // Format client ID as client-{n}/{pid}
             let client_label = format!("client-{}/{}", client_idx + 1, client.client_id.rsplit(':').next().unwrap_or(&client.client_id));
 
-            rows.push(Row::new(vec![
-                Cell::from(arrow),
+            if summary_view {
+                let mut cells = client_summary_cells(client, client_label, cmd_short, now_epoch, state, colors);
+                cells.insert(0, colors.first_cell(String::new(), Style::default(), is_selected));
+                if show_cost {
+                    cells.push(cost_cell(state.pricing.as_ref().and_then(|p| client.estimated_cost(p)), colors));
+                }
+                rows.push(Row::new(cells).style(row_style));
+                visual_idx += 1;
+                selectable_idx += 1;
+                continue;
+            }
+
+            let mut cells = vec![
+                colors.first_cell(arrow.to_string(), Style::default(), is_selected),
                 Cell::from(client_label).style(Style::default().fg(colors.accent).add_modifier(Modifier::BOLD)),
                 Cell::from(cmd_short).style(Style::default().fg(colors.text_primary)),
                 Cell::from(sess_count),
                 Cell::from(status_text).style(Style::default().fg(status_color).add_modifier(Modifier::BOLD)),
                 Cell::from(last_port).style(Style::default().fg(colors.accent_latency)),
-            ]).style(row_style));
+                Cell::from(""),
+            ];
+            if show_cost {
+                cells.push(cost_cell(state.pricing.as_ref().and_then(|p| client.estimated_cost(p)), colors));
+            }
+            rows.push(Row::new(cells).style(row_style));
             visual_idx += 1;
             selectable_idx += 1;
 
             // Expanded: show sessions under this client
             if is_expanded {
-                for sess in &client.sessions {
-                    let sess_rows = render_session(sess, state, is_focused, &mut selectable_idx, &mut selected_visual_row, visual_idx, true, colors);
+                // Same order as build_selectable_items, so selection stays on its row
+                for sess in state.session_sort.0.apply(state.session_sort.1, &client.sessions) {
+                    let sess_rows = render_session(sess, state, running.get(sess.session_id.as_str()).copied().unwrap_or(Activity::new(as_of)), is_focused, &mut selectable_idx, &mut selected_visual_row, visual_idx, true, &limits, colors);
                     for r in sess_rows {
                         rows.push(r);
                         visual_idx += 1;
@@ -111,7 +206,7 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
         }
 
         // Orphan sessions
-        if !orphans.is_empty() {
+        if !orphans.is_empty() && !summary_view {
             // Separator
             rows.push(Row::new(vec![
                 Cell::from("\u{2500}\u{2500}").style(Style::default().fg(colors.border_normal)),
@@ -124,7 +219,7 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
             visual_idx += 1;
 
             for sess in orphans {
-                let sess_rows = render_session(sess, state, is_focused, &mut selectable_idx, &mut selected_visual_row, visual_idx, false, colors);
+                let sess_rows = render_session(sess, state, running.get(sess.session_id.as_str()).copied().unwrap_or(Activity::new(as_of)), is_focused, &mut selectable_idx, &mut selected_visual_row, visual_idx, false, &limits, colors);
                 for r in sess_rows {
                     rows.push(r);
                     visual_idx += 1;
@@ -134,7 +229,7 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
     } else {
         // === Flat fallback (old proxy) ===
         for sess in &queue.sessions {
-            let sess_rows = render_session(sess, state, is_focused, &mut selectable_idx, &mut selected_visual_row, visual_idx, false, colors);
+            let sess_rows = render_session(sess, state, running.get(sess.session_id.as_str()).copied().unwrap_or(Activity::new(as_of)), is_focused, &mut selectable_idx, &mut selected_visual_row, visual_idx, false, &limits, colors);
             for r in sess_rows {
                 rows.push(r);
                 visual_idx += 1;
@@ -154,7 +249,7 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
         .filter(|r| r.session_id.is_none())
         .collect();
 
-    if !ungrouped_in_flight.is_empty() || !ungrouped_pending.is_empty() {
+    if !summary_view && (!ungrouped_in_flight.is_empty() || !ungrouped_pending.is_empty()) {
         rows.push(Row::new(vec![
             Cell::from("\u{2500}").style(Style::default().fg(colors.border_normal)),
             Cell::from(""),
@@ -165,17 +260,20 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
         ]));
 
         for req in &ungrouped_in_flight {
-            let processing_str = req
-                .processing_time_ms
-                .map(format_duration_ms)
-                .unwrap_or_else(|| "-".to_string());
+            let running_ms = req.running_ms(as_of, since_fetch_ms);
+            let processing_str = running_ms.map(|ms| format_duration_ms(ms, precision)).unwrap_or_else(|| "-".to_string());
+            let processing_color = if running_ms.is_some_and(|ms| ms > slow_ms) {
+                colors.status_warn
+            } else {
+                colors.accent
+            };
 
             rows.push(Row::new(vec![
                 Cell::from(""),
                 Cell::from(req.request_id.clone()),
                 Cell::from(format!("(ungrouped) {}", shorten_model(&req.model))),
-                Cell::from(format_duration_ms(req.wait_time_ms)),
-                Cell::from(processing_str).style(Style::default().fg(colors.accent)),
+                Cell::from(format_duration_ms(req.wait_time_ms, precision)),
+                Cell::from(processing_str).style(Style::default().fg(processing_color)),
                 Cell::from(""),
             ]));
         }
@@ -185,7 +283,7 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
                 Cell::from(""),
                 Cell::from(req.request_id.clone()),
                 Cell::from(format!("(ungrouped) {}", shorten_model(&req.model))),
-                Cell::from(format_duration_ms(req.wait_time_ms)).style(Style::default().fg(colors.status_warn)),
+                Cell::from(format_duration_ms(req.wait_time_ms, precision)).style(Style::default().fg(colors.status_warn)),
                 Cell::from("PENDING").style(Style::default().fg(colors.status_warn)),
                 Cell::from(""),
             ]));
@@ -216,6 +314,16 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
         if ungrouped_count > 0 {
             t.push_str(&format!(" + {} ungrouped", ungrouped_count));
         }
+        let hidden = clients.iter().filter(|c| state.client_hidden(c, now_epoch)).count();
+        if hidden > 0 {
+            t.push_str(&format!(" ({} idle hidden)", hidden));
+        }
+        let all_sessions = clients.iter().flat_map(|c| c.sessions.iter()).chain(orphans.iter());
+        push_finished_hidden(&mut t, state, all_sessions);
+        let (sort, dir) = state.session_sort;
+        if (sort, dir) != (SessionSort::Proxy, SortDir::Descending) {
+            t.push_str(&format!(" \u{2502} sort: {} {}", sort.label(), dir.arrow()));
+        }
         t.push_str(" \u{2502} turns: last 60s ");
         t
     } else {
@@ -226,48 +334,106 @@ pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &mut Ap
         } else {
             format!(" Sessions ({})", sess_count)
         };
+        push_finished_hidden(&mut t, state, queue.sessions.iter());
         t.push_str(" \u{2502} turns: last 60s ");
         t
     };
 
+    let ungrouped_count = ungrouped_in_flight.len() + ungrouped_pending.len();
+    let active_count = queue.in_flight.len() + queue.pending.len();
+    let ungrouped_hint = mostly_ungrouped(ungrouped_count, active_count, state.ungrouped_warn_ratio).then(|| {
+        Line::styled(
+            format!(
+                " \u{26a0} {}/{} requests are ungrouped \u{2014} is session tracking enabled? ",
+                ungrouped_count, active_count
+            ),
+            Style::default().fg(colors.status_warn).add_modifier(Modifier::BOLD),
+        )
+    });
+
     state.session_table_state.select(selected_visual_row);
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(2),      // arrow
-            Constraint::Percentage(14), // ID/label/turn#
-            Constraint::Percentage(38), // detail/response
-            Constraint::Percentage(24), // latency
-            Constraint::Percentage(16), // time/status
-            Constraint::Length(6),      // port
-        ],
-    )
+    let table = Table::new(rows, widths)
     .header(header)
-    .block(
-        Block::default()
+    .block({
+        let block = Block::default()
             .borders(Borders::ALL)
             .title(title)
-            .border_style(Style::default().fg(border_color)),
-    )
+            .border_style(Style::default().fg(border_color));
+        match ungrouped_hint {
+            Some(hint) => block.title_bottom(hint),
+            None => block,
+        }
+    })
     .row_highlight_style(Style::default());
 
     frame.render_stateful_widget(table, area, &mut state.session_table_state);
 }
 
+/// Below this many active requests the ungrouped ratio is too noisy to warn on.
+const UNGROUPED_MIN_REQUESTS: usize = 5;
+
+/// Whether enough active requests lack a session_id to suggest the harness
+/// isn't passing session metadata.
+fn mostly_ungrouped(ungrouped: usize, total: usize, ratio: f64) -> bool {
+    total >= UNGROUPED_MIN_REQUESTS && ungrouped as f64 / total as f64 > ratio
+}
+
+/// Longest running and waiting times among a session's queued requests.
+#[derive(Clone, Copy, Debug, Default)]
+struct Activity {
+    running_ms: Option<f64>,
+    waiting_ms: Option<f64>,
+    /// Epoch seconds the view is drawn at; drives the spinner
+    as_of: f64,
+}
+
+impl Activity {
+    fn new(as_of: f64) -> Self {
+        Activity { as_of, ..Activity::default() }
+    }
+}
+
+const SPINNER: [char; 4] = ['\u{25d0}', '\u{25d3}', '\u{25d1}', '\u{25d2}'];
+
+/// Progress shown for a session that has requests queued but no completed
+/// turn yet: a spinner plus how long its oldest request has been waiting or
+/// running, so a freshly started session looks alive rather than stuck.
+fn starting_progress(sess: &SessionGroup, activity: &Activity, precision: Precision) -> Option<String> {
+    let active = sess.pending_requests > 0 || sess.in_flight_requests > 0;
+    if !sess.completed_turns.is_empty() || !active {
+        return None;
+    }
+    let spinner = SPINNER[activity.as_of.max(0.0) as usize % SPINNER.len()];
+    let since_first = match (activity.running_ms, activity.waiting_ms) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    };
+    Some(match since_first {
+        Some(ms) => format!(" {} {}", spinner, format_duration_ms(ms, precision)),
+        None => format!(" {}", spinner),
+    })
+}
+
 /// Render a session (and its turns if expanded) as Row(s).
 #[allow(clippy::too_many_arguments)]
 fn render_session<'a>(
     sess: &SessionGroup,
     state: &AppState,
+    activity: Activity,
     is_focused: bool,
     selectable_idx: &mut usize,
     selected_visual_row: &mut Option<usize>,
     current_visual_idx: usize,
     indented: bool,
+    limits: &TextLimits,
     colors: &ColorScheme,
 ) -> Vec<Row<'a>> {
+    let precision = state.precision;
     let mut rows = Vec::new();
+    if state.session_hidden(sess) {
+        return rows;
+    }
 
     let is_selected = is_focused && *selectable_idx == state.session_selected;
     if *selectable_idx == state.session_selected {
@@ -276,30 +442,29 @@ fn render_session<'a>(
     let is_expanded = state.session_expanded.contains(&sess.session_id);
     let arrow = if is_expanded { "v" } else { ">" };
 
-    let (status_text, status_color) = session_status(sess, colors);
+    let (status_text, mut status_color) = session_status(sess, colors);
     // Use total_turns (survives cleanup) when available, fallback to total_requests
     let total = if sess.total_turns > 0 { sess.total_turns } else { sess.total_requests };
     let turns_text = format!("{}/{}", sess.completed_requests, total);
 
-    let task_short = if sess.task_id.len() > 50 {
-        format!("{}..", &sess.task_id[..48])
+    let prefix = if indented { "  " } else { "" };
+
+    // Under a client the task sits in the narrow ID column, otherwise in Detail
+    let task_limit = if indented {
+        limits.task.min(limits.id.saturating_sub(prefix.len()))
     } else {
-        sess.task_id.clone()
+        limits.task
     };
-
-    let prefix = if indented { "  " } else { "" };
+    let task_short = truncate(&sess.task_id, task_limit);
 
     let row_style = if is_selected {
-        Style::default().bg(colors.row_selected_bg)
+        colors.selected_row()
     } else {
         Style::default()
     };
 
-    let sess_id_short = if sess.session_id.len() > 7 {
-        sess.session_id[..7].to_string()
-    } else {
-        sess.session_id.clone()
-    };
+    let sess_id_short: String = sess.session_id.chars().take(7).collect();
+    let star = if state.starred.contains(&sess.session_id) { "\u{2605} " } else { "" };
 
     let (id_label, detail_text) = if indented {
         // Under a client: show task name in ID column, session ID as detail
@@ -322,29 +487,55 @@ fn render_session<'a>(
         String::new()
     };
 
-    rows.push(Row::new(vec![
-        Cell::from(format!("{}{}", prefix, arrow)),
-        Cell::from(format!("{}{}", prefix, id_label)).style(Style::default().fg(colors.accent_id)),
-        Cell::from(detail_text).style(Style::default().fg(colors.text_primary)),
+    // Finished sessions are dimmed so active work stands out
+    let (id_color, detail_color) = if sess.is_finished() {
+        (colors.text_disabled, colors.text_disabled)
+    } else {
+        (colors.accent_id, colors.text_primary)
+    };
+
+    // While a request is in flight, show how long it has been running instead
+    let running_ms = activity.running_ms.filter(|_| sess.in_flight_requests > 0);
+    if running_ms.is_some_and(|ms| ms > state.slow_request_secs as f64 * 1000.0) {
+        status_color = colors.status_warn;
+    }
+    let elapsed_str = match starting_progress(sess, &activity, precision) {
+        Some(progress) => progress,
+        None => match running_ms {
+            Some(ms) => format!(" {}", format_duration_ms(ms, precision)),
+            None => elapsed_str,
+        },
+    };
+
+    let mut cells = vec![
+        colors.first_cell(format!("{}{}", prefix, arrow), Style::default(), is_selected),
+        Cell::from(format!("{}{}{}", prefix, star, id_label)).style(Style::default().fg(id_color)),
+        Cell::from(detail_text).style(Style::default().fg(detail_color)),
         Cell::from(turns_text).style(Style::default().fg(colors.accent_count)),
         Cell::from(format!("{}{}", status_text, elapsed_str)).style(Style::default().fg(status_color).add_modifier(Modifier::BOLD)),
-        Cell::from(""),  // port column (empty for session)
-    ]).style(row_style));
+        affinity_cell(sess.distinct_backends(), state.expect_affinity, colors),
+        stage_cell(session_stage(sess), colors),
+    ];
+    // Extra cost cell is dropped by the table when the column is hidden
+    if let Some(pricing) = &state.pricing {
+        cells.push(cost_cell(sess.estimated_cost(pricing), colors));
+    }
+    rows.push(Row::new(cells).style(row_style));
 
     *selectable_idx += 1;
 
     // Expanded turns - one line per turn, spread across all columns
     if is_expanded {
         for (i, turn) in sess.completed_turns.iter().enumerate() {
-            let total_str = format_duration_ms(turn.total_time_ms);
-            let wait_str = format_duration_ms(turn.wait_time_ms);
+            let total_str = format_duration_ms(turn.total_time_ms, precision);
+            let wait_str = format_duration_ms(turn.wait_time_ms, precision);
 
             // Layer 2: if backend_time_ms available, split into infer/proxy; else fallback to proc=
             let timing_str = if let Some(backend_ms) = turn.backend_time_ms {
                 let proxy_ms = turn.processing_time_ms - backend_ms;
-                format!("infer={} proxy={}", format_duration_ms(backend_ms), format_duration_ms(proxy_ms.max(0.0)))
+                format!("infer={} proxy={}", format_duration_ms(backend_ms, precision), format_duration_ms(proxy_ms.max(0.0), precision))
             } else {
-                format!("proc={}", format_duration_ms(turn.processing_time_ms))
+                format!("proc={}", format_duration_ms(turn.processing_time_ms, precision))
             };
 
             let backend_short = turn
@@ -357,8 +548,8 @@ fn render_session<'a>(
             let turn_prefix = if indented { "    " } else { "  " };
 
             // Timestamps (HH:MM:SS)
-            let sent_time = format_epoch(turn.submitted_at);
-            let recv_time = format_epoch(turn.completed_at);
+            let sent_time = format_epoch(turn.submitted_at, state.timestamp_format);
+            let recv_time = format_epoch(turn.completed_at, state.timestamp_format);
 
             // Agent gap = time between previous turn completion and this turn submission
             let gap_ms: Option<f64> = if i > 0 {
@@ -378,11 +569,11 @@ fn render_session<'a>(
                 let detail = match (turn.agent_obs_ms, turn.agent_act_ms) {
                     (Some(obs), Some(act)) => format!(
                         "agent={}(obs={} act={})",
-                        format_duration_ms(gap),
-                        format_duration_ms(obs),
-                        format_duration_ms(act),
+                        format_duration_ms(gap, precision),
+                        format_duration_ms(obs, precision),
+                        format_duration_ms(act, precision),
                     ),
-                    _ => format!("agent={}", format_duration_ms(gap)),
+                    _ => format!("agent={}", format_duration_ms(gap, precision)),
                 };
                 let style = if gap > 5000.0 {
                     Style::default().fg(colors.status_warn)
@@ -394,33 +585,89 @@ fn render_session<'a>(
                 (format!("{}\u{2192}{}", sent_time, recv_time), Style::default().fg(colors.text_primary))
             };
 
-            // Response snippet - gets the widest column (Detail)
-            let resp_snippet = turn.response_summary.as_deref()
-                .filter(|s| !s.is_empty())
-                .map(|s| {
-                    if s.len() > 120 { format!("\u{2190} {}..", &s[..118]) }
-                    else { format!("\u{2190} {}", s) }
-                })
-                .unwrap_or_default();
+            // Response (or with `r`, request) snippet - gets the widest column
+            // (Detail); full wrapped request/response in full-text mode
+            let (detail_text, row_height) = if state.session_full_text.contains(&sess.session_id) {
+                let lines = full_text_lines(turn.request_summary.as_deref(), turn.response_summary.as_deref(), limits.detail);
+                let height = lines.len().max(1) as u16;
+                (Text::from(lines.into_iter().map(Line::from).collect::<Vec<_>>()), height)
+            } else {
+                (Text::from(turn_snippet(turn, state.show_requests, limits.response)), 1)
+            };
 
             // Spread across cells:
-            // [0] empty  [1] T#/total  [2] response  [3] latency  [4] time/agent  [5] port
+            // [0] empty  [1] T#/total  [2] response/request  [3] latency  [4] time/agent  [5] port
             let turn_label = format!("{}T{}/{}", turn_prefix, turn.turn_number.unwrap_or(i as u64 + 1), total);
             let latency_detail = format!("{} wait={} {}", total_str, wait_str, timing_str);
-            rows.push(Row::new(vec![
-                Cell::from(""),
+
+            let turn_selected = is_focused && *selectable_idx == state.session_selected;
+            if *selectable_idx == state.session_selected {
+                *selected_visual_row = Some(current_visual_idx + 1 + i);
+            }
+            let turn_row_style = if turn_selected {
+                colors.selected_row()
+            } else {
+                Style::default()
+            };
+
+            let mut cells = vec![
+                colors.first_cell(String::new(), Style::default(), turn_selected),
                 Cell::from(turn_label).style(Style::default().fg(colors.text_primary)),
-                Cell::from(resp_snippet).style(Style::default().fg(colors.accent)),
+                Cell::from(detail_text).style(Style::default().fg(colors.accent)),
                 Cell::from(latency_detail).style(Style::default().fg(colors.text_primary)),
                 Cell::from(cell3_text).style(cell3_style),
                 Cell::from(backend_short).style(Style::default().fg(colors.accent_latency)),
-            ]));
+                Cell::from(""),
+            ];
+            if let Some(pricing) = &state.pricing {
+                cells.push(cost_cell(turn.estimated_cost(pricing), colors));
+            }
+            rows.push(Row::new(cells).style(turn_row_style).height(row_height));
+            *selectable_idx += 1;
         }
     }
 
     rows
 }
 
+/// A session's dominant stage, "\u{2014}" until it has enough turns.
+fn stage_cell<'a>(stage: Option<Stage>, colors: &ColorScheme) -> Cell<'a> {
+    let Some(stage) = stage else {
+        return Cell::from("\u{2014}").style(Style::default().fg(colors.text_disabled));
+    };
+    let color = match stage {
+        Stage::Wait => colors.status_warn,
+        Stage::Inference => colors.accent_latency,
+        Stage::Proxy => colors.status_error,
+        Stage::Agent => colors.accent_id,
+    };
+    Cell::from(stage.label()).style(Style::default().fg(color))
+}
+
+fn cost_cell<'a>(cost: Option<f64>, colors: &ColorScheme) -> Cell<'a> {
+    match cost {
+        Some(c) => Cell::from(format_cost(c)).style(Style::default().fg(colors.accent_count)),
+        None => Cell::from("-").style(Style::default().fg(colors.text_disabled)),
+    }
+}
+
+/// Append "(N finished hidden)" to a panel title when the toggle hides any,
+/// and mark the starred-only filter.
+fn push_finished_hidden<'a>(title: &mut String, state: &AppState, sessions: impl Iterator<Item = &'a SessionGroup>) {
+    // Finished sessions hidden only by `--session` or starring aren't the toggle's doing
+    let hidden = if state.hide_finished {
+        sessions.filter(|s| s.is_finished() && state.session_hidden(s)).count()
+    } else {
+        0
+    };
+    if hidden > 0 {
+        title.push_str(&format!(" ({} finished hidden)", hidden));
+    }
+    if state.starred_only {
+        title.push_str(" (\u{2605} starred only)");
+    }
+}
+
 /// Aggregate status for a client group.
 fn client_aggregate_status(client: &ClientGroup, colors: &ColorScheme) -> (&'static str, Color) {
     let mut has_inflight = false;
@@ -442,6 +689,35 @@ fn client_aggregate_status(client: &ClientGroup, colors: &ColorScheme) -> (&'sta
     }
 }
 
+/// Aggregate columns for a client row in the client summary view:
+/// label, command, session/turn totals, active/failed sessions, turn rate.
+fn client_summary_cells<'a>(
+    client: &ClientGroup,
+    label: String,
+    command: String,
+    now: f64,
+    state: &AppState,
+    colors: &ColorScheme,
+) -> Vec<Cell<'a>> {
+    let totals = client.totals();
+    let (status_text, status_color) = client_aggregate_status(client, colors);
+    let rate = client
+        .turns_per_minute(now, state.rate_window_secs as f64)
+        .map_or("-".to_string(), |r| format!("{}t/m", format_decimal(r, 1, state.precision)));
+    let failed_color = if totals.failed > 0 { colors.status_error } else { colors.text_secondary };
+    vec![
+        Cell::from(label).style(Style::default().fg(colors.accent).add_modifier(Modifier::BOLD)),
+        Cell::from(command).style(Style::default().fg(colors.text_primary)),
+        Cell::from(format!("{} sess  {}/{}t", totals.sessions, totals.turns_done, totals.turns_total))
+            .style(Style::default().fg(colors.accent_count)),
+        Cell::from(Line::from(vec![
+            Span::styled(format!("{} {}", status_text, totals.active), Style::default().fg(status_color).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" / {} failed", totals.failed), Style::default().fg(failed_color)),
+        ])),
+        Cell::from(rate).style(Style::default().fg(colors.accent_latency)),
+    ]
+}
+
 /// Status for a single session.
 fn session_status(sess: &SessionGroup, colors: &ColorScheme) -> (&'static str, Color) {
     if sess.in_flight_requests > 0 {
@@ -450,23 +726,196 @@ fn session_status(sess: &SessionGroup, colors: &ColorScheme) -> (&'static str, C
         ("PENDING", colors.status_warn)
     } else if sess.failed_requests > 0 {
         ("FAILED", colors.status_error)
+    } else if sess.is_finished() {
+        ("DONE", colors.text_disabled)
     } else {
         ("IDLE", colors.status_ok)
     }
 }
 
-/// Shorten a command line for display.
-fn shorten_command(cmd: &str) -> String {
-    let trimmed = cmd
-        .strip_prefix("python -m ")
-        .or_else(|| cmd.strip_prefix("python3 -m "))
-        .or_else(|| cmd.strip_prefix("python "))
-        .or_else(|| cmd.strip_prefix("python3 "))
-        .unwrap_or(cmd);
-    if trimmed.len() > 60 {
-        format!("{}..", &trimmed[..58])
+/// Badge counting the backends a session's turns were routed to. With
+/// affinity expected, sessions that moved between backends are flagged.
+fn affinity_cell<'a>(backends: usize, expect_affinity: bool, colors: &ColorScheme) -> Cell<'a> {
+    let color = match backends {
+        0 => return Cell::from(""),
+        1 => colors.text_secondary,
+        _ if expect_affinity => colors.status_warn,
+        _ => colors.text_secondary,
+    };
+    let label = if backends == 1 { "1 backend".to_string() } else { format!("{} backends", backends) };
+    Cell::from(label).style(Style::default().fg(color))
+}
+
+/// Argument groups (a flag plus its value counts as one) kept after the
+/// program name when a command has to be shortened.
+const COMMAND_KEPT_ARGS: usize = 2;
+
+/// Shorten a command line for display, keeping what identifies the client:
+/// the module or script name first, then a couple of leading arguments.
+/// Interpreter boilerplate and script directories are dropped, and any
+/// arguments that don't fit are replaced by a trailing "..".
+fn shorten_command(cmd: &str, max: usize) -> String {
+    let mut tokens = cmd.split_whitespace().peekable();
+    let Some(first) = tokens.next() else {
+        return String::new();
+    };
+
+    let (name, is_module) = if basename(first).starts_with("python") {
+        // Skip interpreter flags such as -u / -O up to `-m` or the script
+        let mut module = false;
+        while let Some(tok) = tokens.next_if(|t| t.starts_with('-')) {
+            if tok == "-m" {
+                module = true;
+                break;
+            }
+        }
+        match tokens.next() {
+            Some(target) if module => (target, true),
+            Some(target) => (basename(target), false),
+            None => (first, false),
+        }
+    } else {
+        (basename(first), false)
+    };
+
+    // Group `--flag value` pairs so a flag is never shown without its value
+    let mut groups: Vec<String> = Vec::new();
+    while let Some(tok) = tokens.next() {
+        let mut group = tok.to_string();
+        if tok.starts_with('-') && !tok.contains('=') {
+            if let Some(value) = tokens.next_if(|t| !t.starts_with('-')) {
+                group.push(' ');
+                group.push_str(value);
+            }
+        }
+        groups.push(group);
+    }
+
+    let full = std::iter::once(name.to_string()).chain(groups.iter().cloned()).collect::<Vec<_>>().join(" ");
+    if full.width() <= max {
+        return full;
+    }
+    if name.width() > max {
+        return if is_module { shorten_module(name, max) } else { truncate(name, max) };
+    }
+
+    // Leave room for the " .." marking dropped arguments
+    let budget = max.saturating_sub(3);
+    let mut out = name.to_string();
+    for group in groups.iter().take(COMMAND_KEPT_ARGS) {
+        if out.width() + 1 + group.width() > budget {
+            break;
+        }
+        out.push(' ');
+        out.push_str(group);
+    }
+    if out.width() + 3 <= max {
+        out.push_str(" ..");
+    }
+    out
+}
+
+/// Final path component of a script path.
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Drop leading package components of a dotted module path until it fits,
+/// since the trailing module name is the most specific part.
+fn shorten_module(module: &str, max: usize) -> String {
+    let mut rest = module;
+    while let Some((_, tail)) = rest.split_once('.') {
+        rest = tail;
+        if rest.width() + 2 <= max {
+            return format!("..{}", rest);
+        }
+    }
+    truncate(rest, max)
+}
+
+/// Most lines one turn may take in full-text mode, so a single verbose
+/// session can't push the rest of the panel off screen.
+const FULL_TEXT_MAX_LINES: usize = 8;
+
+/// Request and response text hard-wrapped to `width` columns, capped at
+/// `FULL_TEXT_MAX_LINES` with a note counting what was cut.
+fn full_text_lines(request: Option<&str>, response: Option<&str>, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (marker, text) in [("\u{2192} ", request), ("\u{2190} ", response)] {
+        let Some(text) = text.filter(|t| !t.is_empty()) else {
+            continue;
+        };
+        let wrapped = wrap_text(text, width.saturating_sub(2).max(1));
+        for (i, l) in wrapped.into_iter().enumerate() {
+            lines.push(format!("{}{}", if i == 0 { marker } else { "  " }, l));
+        }
+    }
+    if lines.len() > FULL_TEXT_MAX_LINES {
+        let hidden = lines.len() - (FULL_TEXT_MAX_LINES - 1);
+        lines.truncate(FULL_TEXT_MAX_LINES - 1);
+        lines.push(format!("\u{2026} +{} lines (Enter for full)", hidden));
+    }
+    lines
+}
+
+/// One-line turn snippet in `width` columns: the response behind "\u{2190}",
+/// or the request behind "\u{2192}" (the full-text markers) when `request`.
+fn turn_snippet(turn: &CompletedTurn, request: bool, width: usize) -> String {
+    let (marker, text) = if request {
+        ("\u{2192}", turn.request_summary.as_deref())
     } else {
-        trimmed.to_string()
+        ("\u{2190}", turn.response_summary.as_deref())
+    };
+    text.filter(|s| !s.is_empty())
+        .map(|s| format!("{} {}", marker, truncate(&s.replace('\n', " "), width.saturating_sub(2))))
+        .unwrap_or_default()
+}
+
+/// Split `text` on newlines, then hard-wrap each line at `width` columns.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut out = Vec::new();
+    for line in text.lines() {
+        let mut current = String::new();
+        let mut used = 0;
+        for ch in line.chars() {
+            let w = ch.width().unwrap_or(0);
+            if used + w > width && !current.is_empty() {
+                out.push(std::mem::take(&mut current));
+                used = 0;
+            }
+            current.push(ch);
+            used += w;
+        }
+        out.push(current);
+    }
+    out
+}
+
+/// Character budgets for free-text cells, from rendered column widths
+/// capped by any `--max-*-len` flags.
+struct TextLimits {
+    id: usize,
+    /// Full Detail column width, for wrapped text.
+    detail: usize,
+    command: usize,
+    task: usize,
+    response: usize,
+}
+
+impl TextLimits {
+    fn new(area: Rect, widths: &[Constraint], caps: &TruncationLimits) -> Self {
+        let inner = area.inner(Margin { horizontal: 1, vertical: 1 });
+        let columns = Layout::horizontal(widths.iter().copied()).spacing(1).split(inner);
+        let id = columns.get(1).map_or(0, |c| c.width as usize);
+        let detail = columns.get(2).map_or(0, |c| c.width as usize);
+        let cap = |w: usize, flag: Option<usize>| flag.map_or(w, |f| f.min(w));
+        Self {
+            id,
+            detail,
+            command: cap(detail, caps.command),
+            task: cap(detail, caps.task),
+            response: cap(detail, caps.response),
+        }
     }
 }
 
@@ -478,38 +927,101 @@ fn shorten_model(model: &str) -> String {
     }
 }
 
-fn format_epoch(epoch: f64) -> String {
-    if epoch <= 0.0 {
-        return "-".to_string();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finished_hidden_counts_only_the_toggle() {
+        let finished = SessionGroup { session_id: "s1".into(), total_turns: 1, ..SessionGroup::default() };
+        let mut state = AppState { starred_only: true, ..AppState::default() };
+        let mut title = String::new();
+        push_finished_hidden(&mut title, &state, [&finished].into_iter());
+        assert_eq!(title, " (\u{2605} starred only)");
+
+        state.hide_finished = true;
+        title.clear();
+        push_finished_hidden(&mut title, &state, [&finished].into_iter());
+        assert!(title.starts_with(" (1 finished hidden)"));
     }
-    let secs = epoch as i64;
-    let nanos = ((epoch - secs as f64) * 1_000_000_000.0) as u32;
-    match Local.timestamp_opt(secs, nanos) {
-        chrono::LocalResult::Single(dt) => dt.format("%H:%M:%S").to_string(),
-        _ => "-".to_string(),
+
+    #[test]
+    fn turn_snippet_switches_between_response_and_request() {
+        let turn = CompletedTurn {
+            request_summary: Some("list the\nfiles".into()),
+            response_summary: Some("ls -la".into()),
+            ..CompletedTurn::default()
+        };
+        assert_eq!(turn_snippet(&turn, false, 20), "\u{2190} ls -la");
+        assert_eq!(turn_snippet(&turn, true, 20), "\u{2192} list the files");
+        assert_eq!(turn_snippet(&turn, true, 8), "\u{2192} list..");
+        assert_eq!(turn_snippet(&CompletedTurn::default(), true, 20), "");
     }
-}
 
-fn format_duration_ms(ms: f64) -> String {
-    if ms < 1000.0 {
-        format!("{:.0}ms", ms)
-    } else {
-        format!("{:.1}s", ms / 1000.0)
+    #[test]
+    fn session_without_completed_turns_shows_spinner_and_age() {
+        let mut sess = SessionGroup { total_requests: 2, in_flight_requests: 1, ..SessionGroup::default() };
+        let activity = Activity { running_ms: Some(1500.0), waiting_ms: Some(4000.0), as_of: 1.0 };
+        let progress = starting_progress(&sess, &activity, Precision::default()).unwrap();
+        assert!(progress.starts_with(&format!(" {} ", SPINNER[1])), "{progress}");
+        assert!(progress.ends_with(&format_duration_ms(4000.0, Precision::default())), "{progress}");
+        // Nothing known about the queued request yet: spinner alone
+        assert_eq!(starting_progress(&sess, &Activity::new(0.0), Precision::default()).as_deref(), Some(" \u{25d0}"));
+
+        sess.completed_turns.push(CompletedTurn::default());
+        assert_eq!(starting_progress(&sess, &activity, Precision::default()), None);
+        let idle = SessionGroup { total_requests: 2, ..SessionGroup::default() };
+        assert_eq!(starting_progress(&idle, &activity, Precision::default()), None);
     }
-}
 
-/// Format a longer duration (session-level) as e.g. "45s", "2m30s", "1h05m".
-fn format_elapsed(ms: f64) -> String {
-    let total_secs = (ms / 1000.0).round() as u64;
-    if total_secs < 60 {
-        format!("{}s", total_secs)
-    } else if total_secs < 3600 {
-        let mins = total_secs / 60;
-        let secs = total_secs % 60;
-        format!("{}m{:02}s", mins, secs)
-    } else {
-        let hours = total_secs / 3600;
-        let mins = (total_secs % 3600) / 60;
-        format!("{}h{:02}m", hours, mins)
+    #[test]
+    fn full_text_wraps_and_caps_lines() {
+        assert_eq!(wrap_text("abcdef\ngh", 4), vec!["abcd", "ef", "gh"]);
+        assert_eq!(wrap_text("任务名称", 5), vec!["任务", "名称"]);
+
+        let lines = full_text_lines(Some("ask"), Some("answer"), 40);
+        assert_eq!(lines, vec!["\u{2192} ask", "\u{2190} answer"]);
+
+        let long = "x".repeat(200);
+        let lines = full_text_lines(None, Some(&long), 12);
+        assert_eq!(lines.len(), FULL_TEXT_MAX_LINES);
+        assert_eq!(lines[FULL_TEXT_MAX_LINES - 1], "\u{2026} +13 lines (Enter for full)");
+    }
+
+    #[test]
+    fn shorten_command_strips_python_prefix() {
+        assert_eq!(shorten_command("python -m agent.run --x", 80), "agent.run --x");
+        assert_eq!(shorten_command("python3 run.py", 5), "run..");
+    }
+
+    #[test]
+    fn shorten_command_keeps_module_name() {
+        let cmd = "python -u -m my.long.package.module --config cfg.yaml --workers 8 --seed 1";
+        assert_eq!(shorten_command(cmd, 43), "my.long.package.module --config cfg.yaml ..");
+        assert_eq!(shorten_command(cmd, 26), "my.long.package.module ..");
+        assert_eq!(shorten_command(cmd, 16), "..package.module");
+        assert_eq!(shorten_command(cmd, 9), "..module");
+    }
+
+    #[test]
+    fn shorten_command_drops_script_directory() {
+        assert_eq!(shorten_command("/usr/bin/python3 /opt/agents/bin/runner.py --task t1", 80), "runner.py --task t1");
+        assert_eq!(shorten_command("/opt/agents/bin/agent-cli serve", 80), "agent-cli serve");
+    }
+
+    #[test]
+    fn shorten_command_keeps_flag_values_together() {
+        let cmd = "python3 eval.py --model m --temp=0.2 --verbose --out /tmp/results";
+        assert_eq!(shorten_command(cmd, 31), "eval.py --model m --temp=0.2 ..");
+        assert_eq!(shorten_command(cmd, 20), "eval.py --model m ..");
+        assert_eq!(shorten_command(cmd, 12), "eval.py ..");
+    }
+
+    #[test]
+    fn ungrouped_hint_needs_enough_requests() {
+        assert!(!mostly_ungrouped(3, 3, 0.5));
+        assert!(mostly_ungrouped(4, 5, 0.5));
+        assert!(!mostly_ungrouped(2, 5, 0.5));
+        assert!(!mostly_ungrouped(0, 0, 0.5));
     }
 }
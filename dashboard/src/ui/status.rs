@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+use crate::model::{AppState, FocusedPanel, ProxySnapshot, QueueResponse, SelectableItem};
+use super::theme::ColorScheme;
+
+/// How long a one-shot action's feedback stays visible.
+const STATUS_MESSAGE_TTL: Duration = Duration::from_secs(5);
+
+/// Draw a one-line summary of what the focused panel's selection points at.
+/// `scoped` is `proxy` under `--partition`, matching the backends panel.
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    proxy: &ProxySnapshot,
+    scoped: &ProxySnapshot,
+    state: &AppState,
+    colors: &ColorScheme,
+) {
+    let (panel, target) = match state.focused_panel {
+        FocusedPanel::Backends => (
+            "Backends",
+            scoped
+                .stats
+                .pools
+                .get(state.backend_selected)
+                .map(|p| format!("model {}", p.model)),
+        ),
+        FocusedPanel::Sessions => (
+            "Sessions",
+            state
+                .resolve_selected(&proxy.queue)
+                .map(|item| describe_selection(&proxy.queue, &item)),
+        ),
+    };
+
+    let mut spans = vec![
+        Span::styled(
+            format!(" {} ", panel),
+            Style::default().fg(colors.accent).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("\u{2502} ", Style::default().fg(colors.help_separator)),
+        match target {
+            Some(t) => Span::styled(t, Style::default().fg(colors.text_primary)),
+            None => Span::styled("nothing selected", Style::default().fg(colors.text_disabled)),
+        },
+    ];
+
+    if let Some((msg, at)) = &state.status_message {
+        if at.elapsed() < STATUS_MESSAGE_TTL {
+            spans.push(Span::styled("  \u{2502} ", Style::default().fg(colors.help_separator)));
+            spans.push(Span::styled(msg.clone(), Style::default().fg(colors.status_ok)));
+        }
+    }
+
+    let line = Line::from(spans);
+
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// Describe a sessions-panel selection as a path, e.g.
+/// "client-3/12345, session abc1234, turn 2".
+fn describe_selection(queue: &QueueResponse, item: &SelectableItem) -> String {
+    let session_id = match item {
+        SelectableItem::Client(cid) => return client_label(queue, cid),
+        SelectableItem::Session(sid) | SelectableItem::Turn(sid, _) => sid,
+    };
+
+    let mut parts = Vec::new();
+    if let Some(client) = queue
+        .clients
+        .iter()
+        .find(|c| c.sessions.iter().any(|s| &s.session_id == session_id))
+    {
+        parts.push(client_label(queue, &client.client_id));
+    }
+    parts.push(format!("session {}", short_id(session_id)));
+
+    if let SelectableItem::Turn(_, idx) = item {
+        let turn_no = queue
+            .find_session(session_id)
+            .and_then(|s| s.completed_turns.get(*idx))
+            .and_then(|t| t.turn_number)
+            .unwrap_or(*idx as u64 + 1);
+        parts.push(format!("turn {}", turn_no));
+    }
+
+    parts.join(", ")
+}
+
+/// Same `client-{n}/{pid}` label the sessions panel renders.
+fn client_label(queue: &QueueResponse, client_id: &str) -> String {
+    let n = queue
+        .clients
+        .iter()
+        .position(|c| c.client_id == client_id)
+        .map_or(0, |i| i + 1);
+    format!("client-{}/{}", n, client_id.rsplit(':').next().unwrap_or(client_id))
+}
+
+fn short_id(id: &str) -> &str {
+    match id.char_indices().nth(7) {
+        Some((i, _)) => &id[..i],
+        None => id,
+    }
+}
@@ -1,4 +1,18 @@
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Cell;
+
+/// How a selected table row is marked beyond `row_selected_bg`, which some
+/// terminals render nearly invisible.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionStyle {
+    /// Left-edge bar in the first cell plus bold text.
+    Marker,
+    /// Reverse video.
+    Reverse,
+    /// Background color only.
+    Background,
+}
 
 #[derive(Clone, Copy, Debug)]
 pub struct ColorScheme {
@@ -15,6 +29,7 @@ pub struct ColorScheme {
     pub table_header: Color,
     pub row_selected_bg: Color,
     pub row_alt_bg: Color,
+    pub selection: SelectionStyle,
 
     // Accents
     pub accent: Color,
@@ -42,6 +57,7 @@ impl ColorScheme {
             table_header: Color::Yellow,
             row_selected_bg: Color::DarkGray,
             row_alt_bg: Color::Rgb(30, 30, 40),
+            selection: SelectionStyle::Marker,
             accent: Color::LightBlue,
             accent_id: Color::LightYellow,
             accent_count: Color::LightGreen,
@@ -63,6 +79,7 @@ impl ColorScheme {
             table_header: Color::Rgb(140, 100, 0),
             row_selected_bg: Color::Rgb(210, 220, 235),
             row_alt_bg: Color::Rgb(240, 240, 248),
+            selection: SelectionStyle::Marker,
             accent: Color::Blue,
             accent_id: Color::Rgb(160, 110, 0),
             accent_count: Color::Rgb(0, 130, 60),
@@ -74,3 +91,31 @@ impl ColorScheme {
         }
     }
 }
+
+/// Left-edge bar drawn in the first cell of the selected row.
+const SELECTION_MARKER: &str = "\u{258c}";
+
+impl ColorScheme {
+    /// Row style for the selected row.
+    pub fn selected_row(&self) -> Style {
+        let style = Style::default().bg(self.row_selected_bg);
+        match self.selection {
+            SelectionStyle::Marker => style.add_modifier(Modifier::BOLD),
+            SelectionStyle::Reverse => style.add_modifier(Modifier::REVERSED),
+            SelectionStyle::Background => style,
+        }
+    }
+
+    /// First cell of a row, prefixed with the selection marker when
+    /// `selected` (taking the place of a leading indent space if present).
+    pub fn first_cell(&self, text: String, style: Style, selected: bool) -> Cell<'static> {
+        if !selected || self.selection != SelectionStyle::Marker {
+            return Cell::from(text).style(style);
+        }
+        let rest = text.strip_prefix(' ').map(str::to_string).unwrap_or(text);
+        Cell::from(Line::from(vec![
+            Span::styled(SELECTION_MARKER, Style::default().fg(self.border_focused)),
+            Span::styled(rest, style),
+        ]))
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+
+use crate::model::TimestampFormat;
+
+/// Render every wall-clock time in UTC rather than the local zone (`--utc`).
+static UTC: AtomicBool = AtomicBool::new(false);
+
+/// Choose the zone for all rendered times. Called once at startup.
+pub fn set_utc(utc: bool) {
+    UTC.store(utc, Ordering::Relaxed);
+}
+
+pub fn utc() -> bool {
+    UTC.load(Ordering::Relaxed)
+}
+
+/// The current time formatted with a chrono `pattern` in the chosen zone.
+pub fn format_now(pattern: &str) -> String {
+    format_datetime(Utc::now(), pattern, utc())
+}
+
+fn format_datetime(dt: DateTime<Utc>, pattern: &str, utc: bool) -> String {
+    if utc {
+        dt.format(pattern).to_string()
+    } else {
+        dt.with_timezone(&Local).format(pattern).to_string()
+    }
+}
+
+/// Format an epoch-seconds timestamp from the proxy in the chosen style.
+pub fn format_epoch(epoch: f64, fmt: TimestampFormat) -> String {
+    format_epoch_in(epoch, fmt, utc())
+}
+
+fn format_epoch_in(epoch: f64, fmt: TimestampFormat, utc: bool) -> String {
+    if epoch <= 0.0 {
+        return "-".to_string();
+    }
+    if fmt == TimestampFormat::Epoch {
+        return format!("{:.3}", epoch);
+    }
+    let secs = epoch as i64;
+    let nanos = ((epoch - secs as f64) * 1_000_000_000.0) as u32;
+    match Utc.timestamp_opt(secs, nanos) {
+        chrono::LocalResult::Single(dt) => match fmt {
+            TimestampFormat::Clock => format_datetime(dt, "%H:%M:%S", utc),
+            _ => format_datetime(dt, "%Y-%m-%dT%H:%M:%S%.3f%:z", utc),
+        },
+        _ => "-".to_string(),
+    }
+}
+
+/// Format a local filesystem time (e.g. artifact mtime) in the chosen style.
+pub fn format_system_time(t: SystemTime, fmt: TimestampFormat) -> String {
+    let epoch = t
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0);
+    format_epoch(epoch, fmt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn epoch_mode_prints_raw_seconds() {
+        assert_eq!(format_epoch(1700000000.25, TimestampFormat::Epoch), "1700000000.250");
+        assert_eq!(format_epoch(0.0, TimestampFormat::Iso), "-");
+    }
+
+    #[test]
+    fn utc_mode_ignores_local_zone() {
+        assert_eq!(format_epoch_in(1700000000.0, TimestampFormat::Clock, true), "22:13:20");
+        assert_eq!(
+            format_epoch_in(1700000000.5, TimestampFormat::Iso, true),
+            "2023-11-14T22:13:20.500+00:00"
+        );
+        let dt = Utc.timestamp_opt(1700000000, 0).unwrap();
+        assert_eq!(format_datetime(dt, "%Y-%m-%d %H:%M:%S %Z", true), "2023-11-14 22:13:20 UTC");
+    }
+
+    #[test]
+    fn iso_mode_includes_date_and_offset() {
+        let s = format_epoch(1700000000.0, TimestampFormat::Iso);
+        assert_eq!(s.len(), "2023-11-14T22:13:20.000+00:00".len());
+        assert!(s.contains('T'));
+    }
+}
@@ -0,0 +1,59 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::model::AppState;
+use super::theme::ColorScheme;
+
+/// Toasts shown at once; the rest collapse into a "+N more" line.
+const MAX_VISIBLE: usize = 3;
+
+/// Draw pending toasts stacked in the bottom-right corner of `area`.
+pub fn draw(frame: &mut Frame, area: Rect, state: &AppState, colors: &ColorScheme) {
+    if state.toasts.is_empty() {
+        return;
+    }
+
+    let mut lines: Vec<Line> = state
+        .toasts
+        .iter()
+        .rev()
+        .take(MAX_VISIBLE)
+        .map(|t| {
+            let color = if t.good { colors.status_ok } else { colors.status_error };
+            Line::from(Span::styled(
+                format!(" {} ", t.text),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ))
+        })
+        .collect();
+    let hidden = state.toasts.len().saturating_sub(MAX_VISIBLE);
+    if hidden > 0 {
+        lines.push(Line::from(Span::styled(
+            format!(" +{} more ", hidden),
+            Style::default().fg(colors.text_secondary),
+        )));
+    }
+
+    let width = lines.iter().map(|l| l.width()).max().unwrap_or(0) as u16 + 2;
+    let height = lines.len() as u16 + 2;
+    // Sit just above the help bar
+    let popup = Rect {
+        x: area.right().saturating_sub(width + 1),
+        y: area.bottom().saturating_sub(height + 1),
+        width: width.min(area.width),
+        height: height.min(area.height),
+    };
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors.border_focused)),
+        ),
+        popup,
+    );
+}
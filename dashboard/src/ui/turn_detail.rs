@@ -0,0 +1,151 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use crate::model::{AppState, ProxySnapshot};
+use super::header::format_cost;
+use super::format::format_duration_ms;
+use super::theme::ColorScheme;
+use super::timefmt::format_epoch;
+
+/// Draw the turn detail modal over `area` if one is open.
+pub fn draw(frame: &mut Frame, area: Rect, proxy: &ProxySnapshot, state: &AppState, colors: &ColorScheme) {
+    let precision = state.precision;
+    let Some((session_id, turn_idx)) = &state.turn_detail else {
+        return;
+    };
+
+    let popup = centered_rect(80, 70, area);
+    frame.render_widget(Clear, popup);
+
+    let label_style = Style::default().fg(colors.table_header).add_modifier(Modifier::BOLD);
+    let text_style = Style::default().fg(colors.text_primary);
+
+    let turn = proxy
+        .queue
+        .find_session(session_id)
+        .and_then(|s| s.completed_turns.get(*turn_idx));
+
+    let mut lines: Vec<Line> = Vec::new();
+    let title = match turn {
+        Some(turn) => {
+            let turn_no = turn.turn_number.unwrap_or(*turn_idx as u64 + 1);
+            lines.push(Line::from(vec![
+                Span::styled("Backend:  ", label_style),
+                Span::styled(turn.backend.clone().unwrap_or_else(|| "-".to_string()), text_style),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Sent:     ", label_style),
+                Span::styled(
+                    format!(
+                        "{} \u{2192} {}",
+                        format_epoch(turn.submitted_at, state.timestamp_format),
+                        format_epoch(turn.completed_at, state.timestamp_format),
+                    ),
+                    text_style,
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Timing:   ", label_style),
+                Span::styled(
+                    format!(
+                        "total={} wait={} proc={}",
+                        format_duration_ms(turn.total_time_ms, precision),
+                        format_duration_ms(turn.wait_time_ms, precision),
+                        format_duration_ms(turn.processing_time_ms, precision),
+                    ),
+                    text_style,
+                ),
+            ]));
+            if turn.prompt_tokens.is_some() || turn.completion_tokens.is_some() {
+                let mut tokens = format!(
+                    "prompt={} completion={}",
+                    turn.prompt_tokens.map_or("-".to_string(), |t| t.to_string()),
+                    turn.completion_tokens.map_or("-".to_string(), |t| t.to_string()),
+                );
+                if let Some(cost) = state.pricing.as_ref().and_then(|p| turn.estimated_cost(p)) {
+                    tokens.push_str(&format!("  cost={}", format_cost(cost)));
+                }
+                lines.push(Line::from(vec![
+                    Span::styled("Tokens:   ", label_style),
+                    Span::styled(tokens, text_style),
+                ]));
+            }
+            lines.push(Line::from(""));
+
+            push_section(&mut lines, "Request", turn.request_summary.as_deref(), label_style, colors);
+            lines.push(Line::from(""));
+            push_section(&mut lines, "Response", turn.response_summary.as_deref(), label_style, colors);
+
+            format!(" Turn {} \u{2502} {} ", turn_no, session_id)
+        }
+        None => {
+            lines.push(Line::from(Span::styled(
+                "Turn no longer tracked by the proxy",
+                Style::default().fg(colors.text_disabled),
+            )));
+            " Turn Detail ".to_string()
+        }
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((state.modal_scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_bottom(" \u{2191}/\u{2193} scroll \u{2502} Esc close ")
+                .border_style(Style::default().fg(colors.border_focused)),
+        );
+
+    frame.render_widget(paragraph, popup);
+}
+
+/// Push a labelled block of free text, one `Line` per source line so
+/// newlines in the summary survive wrapping.
+fn push_section<'a>(
+    lines: &mut Vec<Line<'a>>,
+    label: &'a str,
+    text: Option<&str>,
+    label_style: Style,
+    colors: &ColorScheme,
+) {
+    lines.push(Line::from(Span::styled(format!("{}:", label), label_style)));
+    match text.filter(|t| !t.is_empty()) {
+        Some(t) => {
+            for l in t.lines() {
+                lines.push(Line::from(Span::styled(
+                    l.to_string(),
+                    Style::default().fg(colors.accent),
+                )));
+            }
+        }
+        None => lines.push(Line::from(Span::styled(
+            "(none)",
+            Style::default().fg(colors.text_disabled),
+        ))),
+    }
+}
+
+/// A rect of `pct_x` x `pct_y` percent centered in `area`.
+pub fn centered_rect(pct_x: u16, pct_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - pct_y) / 2),
+            Constraint::Percentage(pct_y),
+            Constraint::Percentage((100 - pct_y) / 2),
+        ])
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - pct_x) / 2),
+            Constraint::Percentage(pct_x),
+            Constraint::Percentage((100 - pct_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}